@@ -0,0 +1,233 @@
+//! A derive macro for [firestore-path](https://docs.rs/firestore-path).
+//!
+//! Use it through the `derive` feature of `firestore-path`.
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr};
+
+/// Derives typed constructors of Firestore paths and names for a struct.
+///
+/// The `#[firestore_path("...")]` attribute describes the path.
+/// Each segment is either a literal id or a `{field}` placeholder that is replaced with the value
+/// of the struct field `field`. The fields used by placeholders must implement `AsRef<str>`.
+/// Literal ids are validated at compile time.
+///
+/// If the path ends with a document id, the following methods are generated:
+///
+/// - `fn document_path(&self) -> Result<DocumentPath, Error>`
+/// - `fn document_name<D: Into<RootDocumentName>>(&self, root_document_name: D) -> Result<DocumentName, Error>`
+///
+/// If the path ends with a collection id, `collection_path` and `collection_name` are generated instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{DatabaseName,DocumentName,DocumentPath};
+/// use firestore_path_derive::FirestorePath;
+/// use std::str::FromStr;
+///
+/// #[derive(FirestorePath)]
+/// #[firestore_path("chatrooms/{chatroom_id}/messages/{message_id}")]
+/// struct Message {
+///     chatroom_id: String,
+///     message_id: String,
+/// }
+///
+/// let message = Message {
+///     chatroom_id: "chatroom1".to_string(),
+///     message_id: "message1".to_string(),
+/// };
+/// assert_eq!(
+///     message.document_path()?,
+///     DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?
+/// );
+/// assert_eq!(
+///     message.document_name(DatabaseName::from_str("projects/my-project/databases/my-database")?)?,
+///     DocumentName::from_str(
+///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+///     )?
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+#[proc_macro_derive(FirestorePath, attributes(firestore_path))]
+pub fn derive_firestore_path(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+enum Segment {
+    Field(syn::Ident),
+    Literal(String),
+}
+
+fn expand(input: &DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let template = template(input)?;
+    let field_names = field_names(input)?;
+    let segments = template
+        .value()
+        .split('/')
+        .map(|segment| parse_segment(segment, &template, &field_names))
+        .collect::<Result<Vec<Segment>, Error>>()?;
+
+    let steps = segments.iter().enumerate().map(|(index, segment)| {
+        let (id_type, push) = if index % 2 == 0 {
+            (
+                quote!(::firestore_path::CollectionId),
+                quote!(push_collection),
+            )
+        } else {
+            (quote!(::firestore_path::DocumentId), quote!(push_doc))
+        };
+        let id = match segment {
+            Segment::Field(field) => quote! {
+                <#id_type as ::std::convert::TryFrom<&str>>::try_from(
+                    ::std::convert::AsRef::<str>::as_ref(&self.#field),
+                )?
+            },
+            Segment::Literal(s) => quote! {
+                {
+                    const ID: #id_type = #id_type::from_static(#s);
+                    ID
+                }
+            },
+        };
+        if index == 0 {
+            quote! {
+                let mut path = ::firestore_path::AnyPath::from(
+                    ::firestore_path::CollectionPath::new(None, #id),
+                );
+            }
+        } else {
+            quote! {
+                path.#push(#id)?;
+            }
+        }
+    });
+
+    let (path_type, name_type, path_method, name_method, variant, kind) = if segments.len() % 2 == 0
+    {
+        (
+            quote!(::firestore_path::DocumentPath),
+            quote!(::firestore_path::DocumentName),
+            format_ident!("document_path"),
+            format_ident!("document_name"),
+            quote!(::firestore_path::AnyPath::Document),
+            "document",
+        )
+    } else {
+        (
+            quote!(::firestore_path::CollectionPath),
+            quote!(::firestore_path::CollectionName),
+            format_ident!("collection_path"),
+            format_ident!("collection_name"),
+            quote!(::firestore_path::AnyPath::Collection),
+            "collection",
+        )
+    };
+    let path_doc = format!("Returns the {} path `{}`.", kind, template.value());
+    let name_doc = format!(
+        "Returns the {} name `{{root_document_name}}/{}`.",
+        kind,
+        template.value()
+    );
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc = #path_doc]
+            pub fn #path_method(&self) -> ::std::result::Result<#path_type, ::firestore_path::Error> {
+                #(#steps)*
+                match path {
+                    #variant(path) => ::std::result::Result::Ok(path),
+                    _ => ::std::unreachable!("the kind of the path is checked at compile time"),
+                }
+            }
+
+            #[doc = #name_doc]
+            pub fn #name_method<D>(
+                &self,
+                root_document_name: D,
+            ) -> ::std::result::Result<#name_type, ::firestore_path::Error>
+            where
+                D: ::std::convert::Into<::firestore_path::RootDocumentName>,
+            {
+                #name_type::try_new(root_document_name, self.#path_method()?)
+            }
+        }
+    })
+}
+
+fn field_names(input: &DeriveInput) -> Result<Vec<syn::Ident>, Error> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(fields
+                .named
+                .iter()
+                .filter_map(|field| field.ident.clone())
+                .collect()),
+            Fields::Unit => Ok(vec![]),
+            Fields::Unnamed(_) => Err(Error::new_spanned(
+                &input.ident,
+                "FirestorePath cannot be derived for tuple structs",
+            )),
+        },
+        _ => Err(Error::new_spanned(
+            &input.ident,
+            "FirestorePath can only be derived for structs",
+        )),
+    }
+}
+
+fn parse_segment(
+    segment: &str,
+    template: &LitStr,
+    field_names: &[syn::Ident],
+) -> Result<Segment, Error> {
+    match segment
+        .strip_prefix('{')
+        .and_then(|segment| segment.strip_suffix('}'))
+    {
+        Some(field) => match field_names.iter().find(|field_name| *field_name == field) {
+            Some(field_name) => Ok(Segment::Field(field_name.clone())),
+            None => Err(Error::new_spanned(
+                template,
+                format!("no field `{}` in the struct", field),
+            )),
+        },
+        None => {
+            if segment.is_empty() || segment.contains(['{', '}']) {
+                return Err(Error::new_spanned(
+                    template,
+                    format!("invalid segment `{}`", segment),
+                ));
+            }
+            Ok(Segment::Literal(segment.to_string()))
+        }
+    }
+}
+
+fn template(input: &DeriveInput) -> Result<LitStr, Error> {
+    let mut attrs = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("firestore_path"));
+    let Some(attr) = attrs.next() else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "missing #[firestore_path(\"...\")] attribute",
+        ));
+    };
+    if let Some(attr) = attrs.next() {
+        return Err(Error::new_spanned(
+            attr,
+            "duplicate #[firestore_path(\"...\")] attribute",
+        ));
+    }
+    attr.parse_args::<LitStr>()
+}
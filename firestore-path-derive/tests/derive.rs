@@ -0,0 +1,109 @@
+//! Tests for `#[derive(FirestorePath)]`.
+
+use std::str::FromStr;
+
+use firestore_path::{
+    CollectionName, CollectionPath, DatabaseName, DocumentId, DocumentName, DocumentPath,
+};
+use firestore_path_derive::FirestorePath;
+
+#[derive(FirestorePath)]
+#[firestore_path("chatrooms/{chatroom_id}/messages/{message_id}")]
+struct Message {
+    chatroom_id: String,
+    message_id: DocumentId,
+}
+
+#[derive(FirestorePath)]
+#[firestore_path("chatrooms/{chatroom_id}/messages")]
+struct Messages<'a> {
+    chatroom_id: &'a str,
+}
+
+#[derive(FirestorePath)]
+#[firestore_path("settings/global")]
+struct GlobalSettings;
+
+#[derive(FirestorePath)]
+#[firestore_path("c/{d1}/c/{d2}/c/{d3}/c/{d4}/c/{d5}")]
+struct Nested<'a> {
+    d1: &'a str,
+    d2: &'a str,
+    d3: &'a str,
+    d4: &'a str,
+    d5: &'a str,
+}
+
+#[test]
+fn test_document() -> anyhow::Result<()> {
+    let message = Message {
+        chatroom_id: "chatroom1".to_string(),
+        message_id: DocumentId::from_str("message1")?,
+    };
+    assert_eq!(
+        message.document_path()?,
+        DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?
+    );
+    assert_eq!(
+        message.document_name(DatabaseName::from_str(
+            "projects/my-project/databases/my-database"
+        )?)?,
+        DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+        )?
+    );
+
+    let message = Message {
+        chatroom_id: "chat/room1".to_string(),
+        message_id: DocumentId::from_str("message1")?,
+    };
+    assert!(message.document_path().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_collection() -> anyhow::Result<()> {
+    let messages = Messages {
+        chatroom_id: "chatroom1",
+    };
+    assert_eq!(
+        messages.collection_path()?,
+        CollectionPath::from_str("chatrooms/chatroom1/messages")?
+    );
+    assert_eq!(
+        messages.collection_name(DatabaseName::from_str(
+            "projects/my-project/databases/my-database"
+        )?)?,
+        CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+        )?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_literal_segments() -> anyhow::Result<()> {
+    assert_eq!(
+        GlobalSettings.document_path()?,
+        DocumentPath::from_str("settings/global")?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_name_length() -> anyhow::Result<()> {
+    let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    let long_id = "x".repeat(1_500);
+    let nested = Nested {
+        d1: &long_id,
+        d2: &long_id,
+        d3: &long_id,
+        d4: &long_id,
+        d5: &long_id,
+    };
+    assert!(nested.document_path().is_ok());
+    assert!(nested.document_name(database_name.clone()).is_err());
+    let nested = Nested { d5: "d5", ..nested };
+    assert!(nested.document_name(database_name).is_ok());
+    Ok(())
+}
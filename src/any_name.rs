@@ -0,0 +1,749 @@
+use crate::{
+    error::ErrorKind, limits, CollectionId, CollectionName, CollectionPath, DatabaseName,
+    DocumentId, DocumentName, DocumentPath, Error, Relation, RootDocumentName, Segment,
+};
+
+/// A collection name, a document name or a root document name.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{AnyName,CollectionName,DocumentName,RootDocumentName};
+/// use std::str::FromStr;
+///
+/// let any_name = AnyName::from(DocumentName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+/// )?);
+/// assert_eq!(
+///     any_name.to_string(),
+///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+/// );
+/// assert_eq!(
+///     any_name.parent(),
+///     Some(AnyName::from(CollectionName::from_str(
+///         "projects/my-project/databases/my-database/documents/chatrooms"
+///     )?))
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// # Ordering
+///
+/// `AnyName`s are ordered hierarchically: by the `RootDocumentName` and then segment by segment, so that parents come before their children.
+///
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum AnyName {
+    /// A collection name.
+    Collection(CollectionName),
+    /// A document name.
+    Document(DocumentName),
+    /// A root document name.
+    RootDocument(RootDocumentName),
+}
+
+impl AnyName {
+    /// Returns the deepest common ancestor of this `AnyName` and `other`.
+    ///
+    /// A name is considered to be an ancestor of itself.
+    /// Returns `None` if they are in different databases.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionName,DocumentName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let any_name1 = AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    /// )?);
+    /// let any_name2 = AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message2"
+    /// )?);
+    /// assert_eq!(
+    ///     any_name1.common_ancestor(&any_name2),
+    ///     Some(AnyName::from(CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    ///     )?))
+    /// );
+    ///
+    /// let any_name3 = AnyName::from(CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/users"
+    /// )?);
+    /// assert_eq!(
+    ///     any_name1.common_ancestor(&any_name3),
+    ///     Some(AnyName::from(RootDocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents"
+    ///     )?))
+    /// );
+    ///
+    /// let any_name4 = AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/(default)/documents/chatrooms/chatroom1/messages/message1"
+    /// )?);
+    /// assert_eq!(any_name1.common_ancestor(&any_name4), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn common_ancestor(&self, other: &AnyName) -> Option<AnyName> {
+        if self.database_name() != other.database_name() {
+            return None;
+        }
+        let segments = self.segments();
+        let common_len = segments
+            .iter()
+            .zip(other.segments())
+            .take_while(|(a, b)| *a == b)
+            .count();
+        let mut common_ancestor = self.clone();
+        for _ in common_len..segments.len() {
+            common_ancestor = common_ancestor.parent()?;
+        }
+        Some(common_ancestor)
+    }
+
+    /// Converts this `AnyName` into its canonical string representation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     AnyName::from(document_name).into_string(),
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_string(self) -> String {
+        match self {
+            AnyName::Collection(collection_name) => collection_name.into_string(),
+            AnyName::Document(document_name) => document_name.into_string(),
+            AnyName::RootDocument(root_document_name) => root_document_name.into_string(),
+        }
+    }
+
+    /// Returns the parent of this `AnyName`.
+    ///
+    /// - The parent of a `DocumentName` is a `CollectionName`.
+    /// - The parent of a `CollectionName` is a `DocumentName` or a `RootDocumentName`.
+    /// - A `RootDocumentName` has no parent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionName,DocumentName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let any_name = AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// let any_name = any_name.parent().unwrap();
+    /// assert_eq!(
+    ///     any_name,
+    ///     AnyName::from(CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms"
+    ///     )?)
+    /// );
+    /// let any_name = any_name.parent().unwrap();
+    /// assert_eq!(
+    ///     any_name,
+    ///     AnyName::from(RootDocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents"
+    ///     )?)
+    /// );
+    /// assert_eq!(any_name.parent(), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parent(&self) -> Option<AnyName> {
+        match self {
+            AnyName::Collection(collection_name) => Some(
+                collection_name
+                    .parent()
+                    .map(AnyName::Document)
+                    .unwrap_or_else(|| {
+                        AnyName::RootDocument(collection_name.root_document_name().clone())
+                    }),
+            ),
+            AnyName::Document(document_name) => Some(AnyName::Collection(document_name.parent())),
+            AnyName::RootDocument(_) => None,
+        }
+    }
+
+    /// Truncates this `AnyName` to its parent in place.
+    ///
+    /// Returns `false` and does nothing if this `AnyName` has no parent (`RootDocumentName`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionName,DocumentName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let mut any_name = AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// assert!(any_name.pop());
+    /// assert_eq!(
+    ///     any_name,
+    ///     AnyName::from(CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms"
+    ///     )?)
+    /// );
+    /// assert!(any_name.pop());
+    /// assert!(!any_name.pop());
+    /// assert_eq!(
+    ///     any_name,
+    ///     AnyName::from(RootDocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents"
+    ///     )?)
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn pop(&mut self) -> bool {
+        match self.take() {
+            AnyName::Collection(collection_name) => {
+                let (root_document_name, collection_path) = collection_name.into_parts();
+                *self = match collection_path.into_parent() {
+                    Some(document_path) => {
+                        AnyName::Document(DocumentName::new(root_document_name, document_path))
+                    }
+                    None => AnyName::RootDocument(root_document_name),
+                };
+                true
+            }
+            AnyName::Document(document_name) => {
+                *self = AnyName::Collection(document_name.into_parent());
+                true
+            }
+            AnyName::RootDocument(root_document_name) => {
+                *self = AnyName::RootDocument(root_document_name);
+                false
+            }
+        }
+    }
+
+    /// Appends `collection_id` to this `AnyName` (a `DocumentName` or a `RootDocumentName`) in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this `AnyName` is a `CollectionName`, `collection_id` is invalid or the name becomes too long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,DocumentName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let mut any_name = AnyName::from(RootDocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents"
+    /// )?);
+    /// any_name.push_collection("chatrooms")?;
+    /// any_name.push_doc("chatroom1")?;
+    /// assert_eq!(
+    ///     any_name,
+    ///     AnyName::from(DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?)
+    /// );
+    /// assert!(any_name.push_doc("chatroom2").is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn push_collection<E, T>(&mut self, collection_id: T) -> Result<(), Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<CollectionId, Error = E>,
+    {
+        if let AnyName::Collection(_) = self {
+            return Err(Error::from(ErrorKind::SegmentKindMismatch));
+        }
+        let collection_id = collection_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::CollectionIdConversion(e.to_string())))?;
+        self.check_pushed_len(collection_id.as_ref())?;
+        if let AnyName::Document(document_name) = self {
+            limits::check_depth(document_name.depth() + 1)?;
+        }
+        *self = match self.take() {
+            AnyName::Collection(collection_name) => AnyName::Collection(collection_name),
+            AnyName::Document(document_name) => {
+                let (root_document_name, document_path) = document_name.into_parts();
+                AnyName::Collection(CollectionName::new(
+                    root_document_name,
+                    CollectionPath::new(Some(document_path), collection_id),
+                ))
+            }
+            AnyName::RootDocument(root_document_name) => AnyName::Collection(CollectionName::new(
+                root_document_name,
+                CollectionPath::from(collection_id),
+            )),
+        };
+        Ok(())
+    }
+
+    /// Appends `document_id` to this `AnyName` (a `CollectionName`) in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this `AnyName` is not a `CollectionName`, `document_id` is invalid or the name becomes too long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionName,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let mut any_name = AnyName::from(CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?);
+    /// any_name.push_doc("chatroom1")?;
+    /// assert_eq!(
+    ///     any_name,
+    ///     AnyName::from(DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?)
+    /// );
+    /// assert!(any_name.push_doc("chatroom2").is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn push_doc<E, T>(&mut self, document_id: T) -> Result<(), Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DocumentId, Error = E>,
+    {
+        if !matches!(self, AnyName::Collection(_)) {
+            return Err(Error::from(ErrorKind::SegmentKindMismatch));
+        }
+        let document_id = document_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::DocumentIdConversion(e.to_string())))?;
+        self.check_pushed_len(document_id.as_ref())?;
+        *self = match self.take() {
+            AnyName::Collection(collection_name) => {
+                let (root_document_name, collection_path) = collection_name.into_parts();
+                AnyName::Document(DocumentName::new(
+                    root_document_name,
+                    DocumentPath::new(collection_path, document_id),
+                ))
+            }
+            any_name => any_name,
+        };
+        Ok(())
+    }
+
+    /// Returns the `Relation` of this `AnyName` to `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionName,DocumentName,Relation,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let any_name = AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// assert_eq!(any_name.relation(&any_name), Relation::Equal);
+    /// assert_eq!(
+    ///     any_name.relation(&AnyName::from(RootDocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents"
+    ///     )?)),
+    ///     Relation::Descendant
+    /// );
+    /// assert_eq!(
+    ///     any_name.relation(&AnyName::from(CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    ///     )?)),
+    ///     Relation::Ancestor
+    /// );
+    /// assert_eq!(
+    ///     any_name.relation(&AnyName::from(DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom2"
+    ///     )?)),
+    ///     Relation::Sibling
+    /// );
+    /// assert_eq!(
+    ///     any_name.relation(&AnyName::from(DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/users/user1"
+    ///     )?)),
+    ///     Relation::Disjoint
+    /// );
+    /// assert_eq!(
+    ///     any_name.relation(&AnyName::from(DocumentName::from_str(
+    ///         "projects/my-project/databases/(default)/documents/chatrooms/chatroom1"
+    ///     )?)),
+    ///     Relation::DifferentDatabase
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn relation(&self, other: &AnyName) -> Relation {
+        if self.database_name() != other.database_name() {
+            return Relation::DifferentDatabase;
+        }
+        let segments = self.segments();
+        let other_segments = other.segments();
+        if segments == other_segments {
+            Relation::Equal
+        } else if other_segments.starts_with(&segments) {
+            Relation::Ancestor
+        } else if segments.starts_with(&other_segments) {
+            Relation::Descendant
+        } else if segments.len() == other_segments.len()
+            && segments[..segments.len() - 1] == other_segments[..other_segments.len() - 1]
+        {
+            Relation::Sibling
+        } else {
+            Relation::Disjoint
+        }
+    }
+
+    fn database_name(&self) -> &DatabaseName {
+        match self {
+            AnyName::Collection(collection_name) => collection_name.database_name(),
+            AnyName::Document(document_name) => document_name.database_name(),
+            AnyName::RootDocument(root_document_name) => root_document_name.as_database_name(),
+        }
+    }
+
+    pub(crate) fn segments(&self) -> Vec<Segment<'_>> {
+        match self {
+            AnyName::Collection(collection_name) => collection_name.segments().collect(),
+            AnyName::Document(document_name) => document_name.segments().collect(),
+            AnyName::RootDocument(_) => vec![],
+        }
+    }
+
+    // Checks the length of the name after pushing `segment`, before `self` is modified.
+    fn check_pushed_len(&self, segment: &str) -> Result<(), Error> {
+        let len = match self {
+            AnyName::Collection(collection_name) => collection_name.encoded_len(),
+            AnyName::Document(document_name) => document_name.encoded_len(),
+            AnyName::RootDocument(root_document_name) => root_document_name.to_string().len(),
+        };
+        if len + 1 + segment.len() > limits::MAX_DOCUMENT_NAME_BYTES {
+            return Err(Error::from(ErrorKind::LengthOutOfBounds));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn root_document_name(&self) -> &RootDocumentName {
+        match self {
+            AnyName::Collection(collection_name) => collection_name.root_document_name(),
+            AnyName::Document(document_name) => document_name.root_document_name(),
+            AnyName::RootDocument(root_document_name) => root_document_name,
+        }
+    }
+
+    fn take(&mut self) -> AnyName {
+        let placeholder = AnyName::RootDocument(self.root_document_name().clone());
+        std::mem::replace(self, placeholder)
+    }
+}
+
+impl std::cmp::Ord for AnyName {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.root_document_name()
+            .cmp(other.root_document_name())
+            .then_with(|| self.segments().cmp(&other.segments()))
+    }
+}
+
+impl std::cmp::PartialOrd for AnyName {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::convert::From<CollectionName> for AnyName {
+    fn from(collection_name: CollectionName) -> Self {
+        Self::Collection(collection_name)
+    }
+}
+
+impl std::convert::From<DocumentName> for AnyName {
+    fn from(document_name: DocumentName) -> Self {
+        Self::Document(document_name)
+    }
+}
+
+impl std::convert::From<RootDocumentName> for AnyName {
+    fn from(root_document_name: RootDocumentName) -> Self {
+        Self::RootDocument(root_document_name)
+    }
+}
+
+impl std::fmt::Display for AnyName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnyName::Collection(collection_name) => collection_name.fmt(f),
+            AnyName::Document(document_name) => document_name.fmt(f),
+            AnyName::RootDocument(root_document_name) => root_document_name.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        for s in [
+            "projects/my-project/databases/my-database/documents",
+            "projects/my-project/databases/my-database/documents/chatrooms",
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        ] {
+            let any_name = match s.split('/').count() {
+                5 => AnyName::from(RootDocumentName::from_str(s)?),
+                6 => AnyName::from(CollectionName::from_str(s)?),
+                _ => AnyName::from(DocumentName::from_str(s)?),
+            };
+            assert_eq!(any_name.to_string(), s);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_common_ancestor() -> anyhow::Result<()> {
+        fn any_name(s: &str) -> anyhow::Result<AnyName> {
+            Ok(match s.split('/').count() {
+                5 => AnyName::from(RootDocumentName::from_str(s)?),
+                n if n % 2 == 0 => AnyName::from(CollectionName::from_str(s)?),
+                _ => AnyName::from(DocumentName::from_str(s)?),
+            })
+        }
+        let r = "projects/my-project/databases/my-database/documents";
+        for (a, b, expected) in [
+            (r.to_string(), r.to_string(), Some(r.to_string())),
+            (format!("{r}/c"), r.to_string(), Some(r.to_string())),
+            (format!("{r}/c"), format!("{r}/c"), Some(format!("{r}/c"))),
+            (format!("{r}/c1"), format!("{r}/c2"), Some(r.to_string())),
+            (format!("{r}/c/d"), format!("{r}/c"), Some(format!("{r}/c"))),
+            (
+                format!("{r}/c/d1"),
+                format!("{r}/c/d2"),
+                Some(format!("{r}/c")),
+            ),
+            (
+                format!("{r}/c/d/c"),
+                format!("{r}/c/d"),
+                Some(format!("{r}/c/d")),
+            ),
+            (
+                format!("{r}/c/d/c1/d"),
+                format!("{r}/c/d/c2/d"),
+                Some(format!("{r}/c/d")),
+            ),
+            (
+                format!("{r}/c/d"),
+                "projects/my-project/databases/(default)/documents/c/d".to_string(),
+                None,
+            ),
+        ] {
+            let (a, b) = (any_name(&a)?, any_name(&b)?);
+            let expected = expected.as_deref().map(any_name).transpose()?;
+            assert_eq!(a.common_ancestor(&b), expected);
+            assert_eq!(b.common_ancestor(&a), expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_collection_and_push_doc_and_pop() -> anyhow::Result<()> {
+        let r = "projects/my-project/databases/my-database/documents";
+        let mut any_name = AnyName::from(RootDocumentName::from_str(r)?);
+        assert!(any_name.push_doc("chatroom1").is_err());
+        assert!(any_name.push_collection("chat/rooms").is_err());
+        assert_eq!(any_name.to_string(), r);
+
+        any_name.push_collection("chatrooms")?;
+        assert!(any_name.push_collection("messages").is_err());
+        any_name.push_doc("chatroom1")?;
+        any_name.push_collection("messages")?;
+        any_name.push_doc("message1")?;
+        assert_eq!(
+            any_name.to_string(),
+            format!("{r}/chatrooms/chatroom1/messages/message1")
+        );
+
+        let mut popped = vec![];
+        while any_name.pop() {
+            popped.push(any_name.to_string());
+        }
+        assert_eq!(
+            popped,
+            vec![
+                format!("{r}/chatrooms/chatroom1/messages"),
+                format!("{r}/chatrooms/chatroom1"),
+                format!("{r}/chatrooms"),
+                r.to_string(),
+            ]
+        );
+        assert_eq!(any_name.to_string(), r);
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_collection_and_push_doc_max_length() -> anyhow::Result<()> {
+        let r = "projects/my-project/databases/my-database/documents";
+        let mut any_name = AnyName::from(RootDocumentName::from_str(r)?);
+        for _ in 0..4 {
+            any_name.push_collection("x".repeat(1_500))?;
+            any_name.push_doc("x")?;
+        }
+        let before = any_name.to_string();
+        let rest = 6_144 - before.len() - 1;
+        assert!(any_name.push_collection("x".repeat(rest + 1)).is_err());
+        assert_eq!(any_name.to_string(), before);
+        any_name.push_collection("x".repeat(rest))?;
+        assert_eq!(any_name.to_string().len(), 6_144);
+        assert!(any_name.push_doc("x").is_err());
+        assert_eq!(any_name.to_string().len(), 6_144);
+        Ok(())
+    }
+
+    #[test]
+    fn test_relation() -> anyhow::Result<()> {
+        fn any_name(s: &str) -> anyhow::Result<AnyName> {
+            Ok(match s.split('/').count() {
+                5 => AnyName::from(RootDocumentName::from_str(s)?),
+                n if n % 2 == 0 => AnyName::from(CollectionName::from_str(s)?),
+                _ => AnyName::from(DocumentName::from_str(s)?),
+            })
+        }
+        let r = "projects/my-project/databases/my-database/documents";
+        for (a, b, expected, reversed) in [
+            (
+                r.to_string(),
+                r.to_string(),
+                Relation::Equal,
+                Relation::Equal,
+            ),
+            (
+                r.to_string(),
+                format!("{r}/c/d"),
+                Relation::Ancestor,
+                Relation::Descendant,
+            ),
+            (
+                format!("{r}/c"),
+                format!("{r}/c/d/c"),
+                Relation::Ancestor,
+                Relation::Descendant,
+            ),
+            (
+                format!("{r}/c1"),
+                format!("{r}/c2"),
+                Relation::Sibling,
+                Relation::Sibling,
+            ),
+            (
+                format!("{r}/c/d/c1"),
+                format!("{r}/c/d/c2"),
+                Relation::Sibling,
+                Relation::Sibling,
+            ),
+            (
+                format!("{r}/c1/d"),
+                format!("{r}/c2/d"),
+                Relation::Disjoint,
+                Relation::Disjoint,
+            ),
+            (
+                format!("{r}/c/d1"),
+                format!("{r}/c/d2/c"),
+                Relation::Disjoint,
+                Relation::Disjoint,
+            ),
+            (
+                format!("{r}/c/d"),
+                "projects/my-project/databases/(default)/documents/c/d".to_string(),
+                Relation::DifferentDatabase,
+                Relation::DifferentDatabase,
+            ),
+        ] {
+            let (a, b) = (any_name(&a)?, any_name(&b)?);
+            assert_eq!(a.relation(&b), expected);
+            assert_eq!(b.relation(&a), reversed);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_ord() -> anyhow::Result<()> {
+        let r = "projects/my-project/databases/my-database/documents";
+        let expected = vec![
+            r.to_string(),
+            format!("{r}/a"),
+            format!("{r}/a/b"),
+            format!("{r}/a/b/c"),
+            format!("{r}/a/b/c/d"),
+            format!("{r}/a/b/c2"),
+            format!("{r}/a/b2"),
+            format!("{r}/a-"),
+            format!("{r}/a-/b"),
+            format!("{r}/a2"),
+        ];
+        let mut any_names = expected
+            .iter()
+            .rev()
+            .map(|s| {
+                Ok(match s.split('/').count() {
+                    5 => AnyName::from(RootDocumentName::from_str(s)?),
+                    n if n % 2 == 0 => AnyName::from(CollectionName::from_str(s)?),
+                    _ => AnyName::from(DocumentName::from_str(s)?),
+                })
+            })
+            .collect::<anyhow::Result<Vec<AnyName>>>()?;
+        any_names.sort();
+        assert_eq!(
+            any_names
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parent() -> anyhow::Result<()> {
+        let any_name = AnyName::from(DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
+        )?);
+        let mut parents = vec![];
+        let mut current = any_name.parent();
+        while let Some(parent) = current {
+            current = parent.parent();
+            parents.push(parent.to_string());
+        }
+        assert_eq!(
+            parents,
+            vec![
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages",
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+                "projects/my-project/databases/my-database/documents/chatrooms",
+                "projects/my-project/databases/my-database/documents",
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,485 @@
+use crate::{
+    collection_id, document_id, error::ErrorKind, limits, CollectionId, CollectionPath, DocumentId,
+    DocumentPath, Error, ParseOptions, Segment,
+};
+
+/// A collection path or a document path.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{AnyPath,CollectionPath,DocumentPath};
+/// use std::str::FromStr;
+///
+/// let any_path = AnyPath::from(DocumentPath::from_str("chatrooms/chatroom1")?);
+/// assert_eq!(any_path.to_string(), "chatrooms/chatroom1");
+/// assert_eq!(
+///     any_path.parent(),
+///     Some(AnyPath::from(CollectionPath::from_str("chatrooms")?))
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// # Ordering
+///
+/// `AnyPath`s are ordered hierarchically (segment by segment), so that parents come before their children.
+///
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum AnyPath {
+    /// A collection path.
+    Collection(CollectionPath),
+    /// A document path.
+    Document(DocumentPath),
+}
+
+impl AnyPath {
+    /// Returns the deepest common ancestor of this `AnyPath` and `other`.
+    ///
+    /// A path is considered to be an ancestor of itself.
+    /// Returns `None` if they are in different root collections.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyPath,CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let any_path1 = AnyPath::from(DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?);
+    /// let any_path2 = AnyPath::from(DocumentPath::from_str("chatrooms/chatroom2")?);
+    /// assert_eq!(
+    ///     any_path1.common_ancestor(&any_path2),
+    ///     Some(AnyPath::from(CollectionPath::from_str("chatrooms")?))
+    /// );
+    ///
+    /// let any_path3 = AnyPath::from(CollectionPath::from_str("users")?);
+    /// assert_eq!(any_path1.common_ancestor(&any_path3), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn common_ancestor(&self, other: &AnyPath) -> Option<AnyPath> {
+        let segments = self.segments();
+        let common_len = segments
+            .iter()
+            .zip(other.segments())
+            .take_while(|(a, b)| *a == b)
+            .count();
+        let mut common_ancestor = self.clone();
+        for _ in common_len..segments.len() {
+            common_ancestor = common_ancestor.parent()?;
+        }
+        Some(common_ancestor)
+    }
+
+    /// Converts this `AnyPath` into its canonical string representation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(AnyPath::from(document_path).into_string(), "chatrooms/chatroom1");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_string(self) -> String {
+        match self {
+            AnyPath::Collection(collection_path) => collection_path.into_string(),
+            AnyPath::Document(document_path) => document_path.into_string(),
+        }
+    }
+
+    /// Returns the parent of this `AnyPath`.
+    ///
+    /// - The parent of a `DocumentPath` is a `CollectionPath`.
+    /// - The parent of a `CollectionPath` is a `DocumentPath` or `None` (root collection).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyPath,CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let any_path = AnyPath::from(CollectionPath::from_str("chatrooms/chatroom1/messages")?);
+    /// let any_path = any_path.parent().unwrap();
+    /// assert_eq!(
+    ///     any_path,
+    ///     AnyPath::from(DocumentPath::from_str("chatrooms/chatroom1")?)
+    /// );
+    /// let any_path = any_path.parent().unwrap();
+    /// assert_eq!(
+    ///     any_path,
+    ///     AnyPath::from(CollectionPath::from_str("chatrooms")?)
+    /// );
+    /// assert_eq!(any_path.parent(), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parent(&self) -> Option<AnyPath> {
+        match self {
+            AnyPath::Collection(collection_path) => {
+                collection_path.parent().cloned().map(AnyPath::Document)
+            }
+            AnyPath::Document(document_path) => {
+                Some(AnyPath::Collection(document_path.parent().clone()))
+            }
+        }
+    }
+
+    /// Truncates this `AnyPath` to its parent in place.
+    ///
+    /// Returns `false` and does nothing if this `AnyPath` has no parent (root collection).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyPath,CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let mut any_path = AnyPath::from(DocumentPath::from_str("chatrooms/chatroom1")?);
+    /// assert!(any_path.pop());
+    /// assert_eq!(any_path, AnyPath::from(CollectionPath::from_str("chatrooms")?));
+    /// assert!(!any_path.pop());
+    /// assert_eq!(any_path, AnyPath::from(CollectionPath::from_str("chatrooms")?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn pop(&mut self) -> bool {
+        match self.take() {
+            AnyPath::Collection(collection_path) => match collection_path.into_parts() {
+                (Some(document_path), _) => {
+                    *self = AnyPath::Document(document_path);
+                    true
+                }
+                (None, collection_id) => {
+                    *self = AnyPath::Collection(CollectionPath::new(None, collection_id));
+                    false
+                }
+            },
+            AnyPath::Document(document_path) => {
+                *self = AnyPath::Collection(document_path.into_parent());
+                true
+            }
+        }
+    }
+
+    /// Appends `collection_id` to this `AnyPath` (a `DocumentPath`) in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this `AnyPath` is a `CollectionPath` or `collection_id` is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let mut any_path = AnyPath::from(DocumentPath::from_str("chatrooms/chatroom1")?);
+    /// any_path.push_collection("messages")?;
+    /// any_path.push_doc("message1")?;
+    /// assert_eq!(
+    ///     any_path,
+    ///     AnyPath::from(DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?)
+    /// );
+    /// assert!(any_path.push_doc("message2").is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn push_collection<E, T>(&mut self, collection_id: T) -> Result<(), Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<CollectionId, Error = E>,
+    {
+        if let AnyPath::Collection(_) = self {
+            return Err(Error::from(ErrorKind::SegmentKindMismatch));
+        }
+        let collection_id = collection_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::CollectionIdConversion(e.to_string())))?;
+        if let AnyPath::Document(document_path) = self {
+            limits::check_depth(document_path.depth() + 1)?;
+        }
+        if let AnyPath::Document(document_path) = self.take() {
+            *self = AnyPath::Collection(CollectionPath::new(Some(document_path), collection_id));
+        }
+        Ok(())
+    }
+
+    /// Appends `document_id` to this `AnyPath` (a `CollectionPath`) in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this `AnyPath` is a `DocumentPath` or `document_id` is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyPath,CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let mut any_path = AnyPath::from(CollectionPath::from_str("chatrooms")?);
+    /// any_path.push_doc("chatroom1")?;
+    /// assert_eq!(
+    ///     any_path,
+    ///     AnyPath::from(DocumentPath::from_str("chatrooms/chatroom1")?)
+    /// );
+    /// assert!(any_path.push_doc("chatroom2").is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn push_doc<E, T>(&mut self, document_id: T) -> Result<(), Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DocumentId, Error = E>,
+    {
+        if let AnyPath::Document(_) = self {
+            return Err(Error::from(ErrorKind::SegmentKindMismatch));
+        }
+        let document_id = document_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::DocumentIdConversion(e.to_string())))?;
+        if let AnyPath::Collection(collection_path) = self.take() {
+            *self = AnyPath::Document(DocumentPath::new(collection_path, document_id));
+        }
+        Ok(())
+    }
+
+    /// Parses `s` from left to right, pushing one segment at a time onto the path built so far.
+    ///
+    /// The segments are borrowed from `s`, so no intermediate strings are allocated.
+    /// An error has the location of the segment that failed to parse.
+    pub(crate) fn parse(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        let mut segments = s.split('/').enumerate().scan(0, |start, (index, segment)| {
+            let item = (index, *start, segment);
+            *start += segment.len() + 1;
+            Some(item)
+        });
+        let mut document_path = None;
+        while let Some((index, start, collection_id)) = segments.next() {
+            let collection_id = (if options.length_limits {
+                limits::check_depth(index / 2 + 1)
+            } else {
+                Ok(())
+            })
+            .and_then(|_| CollectionId::from_str_with(collection_id, options))
+            .map_err(|e| e.at_segment(index, collection_id, start))?;
+            let collection_path = CollectionPath::new(document_path, collection_id);
+            let Some((index, start, document_id)) = segments.next() else {
+                return Ok(AnyPath::Collection(collection_path));
+            };
+            let document_id = DocumentId::from_str_with(document_id, options)
+                .map_err(|e| e.at_segment(index, document_id, start))?;
+            document_path = Some(DocumentPath::new(collection_path, document_id));
+        }
+        Ok(AnyPath::Document(
+            document_path.expect("split to yield at least one segment"),
+        ))
+    }
+
+    /// Checks `s` in the same order as `AnyPath::parse` without creating the path.
+    ///
+    /// Returns the number of the segments of `s`.
+    pub(crate) fn validate(s: &str) -> Result<usize, Error> {
+        let mut count = 0;
+        let mut start = 0;
+        for (index, segment) in s.split('/').enumerate() {
+            let result = if index % 2 == 0 {
+                limits::check_depth(index / 2 + 1)
+                    .and_then(|_| collection_id::validate(segment).map_err(Error::from))
+            } else {
+                document_id::validate(segment).map_err(Error::from)
+            };
+            result.map_err(|e| e.at_segment(index, segment, start))?;
+            count = index + 1;
+            start += segment.len() + 1;
+        }
+        Ok(count)
+    }
+
+    fn segments(&self) -> Vec<Segment<'_>> {
+        match self {
+            AnyPath::Collection(collection_path) => collection_path.segments().collect(),
+            AnyPath::Document(document_path) => document_path.segments().collect(),
+        }
+    }
+
+    fn take(&mut self) -> AnyPath {
+        std::mem::replace(
+            self,
+            AnyPath::Collection(CollectionPath::new(None, CollectionId::placeholder())),
+        )
+    }
+}
+
+impl std::cmp::Ord for AnyPath {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.segments().cmp(&other.segments())
+    }
+}
+
+impl std::cmp::PartialOrd for AnyPath {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::convert::From<CollectionPath> for AnyPath {
+    fn from(collection_path: CollectionPath) -> Self {
+        Self::Collection(collection_path)
+    }
+}
+
+impl std::convert::From<DocumentPath> for AnyPath {
+    fn from(document_path: DocumentPath) -> Self {
+        Self::Document(document_path)
+    }
+}
+
+impl std::fmt::Display for AnyPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnyPath::Collection(collection_path) => collection_path.fmt(f),
+            AnyPath::Document(document_path) => document_path.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let s = "chatrooms";
+        let any_path = AnyPath::from(CollectionPath::from_str(s)?);
+        assert_eq!(any_path.to_string(), s);
+
+        let s = "chatrooms/chatroom1";
+        let any_path = AnyPath::from(DocumentPath::from_str(s)?);
+        assert_eq!(any_path.to_string(), s);
+        Ok(())
+    }
+
+    #[test]
+    fn test_common_ancestor() -> anyhow::Result<()> {
+        fn any_path(s: &str) -> anyhow::Result<AnyPath> {
+            Ok(if s.split('/').count() % 2 == 1 {
+                AnyPath::from(CollectionPath::from_str(s)?)
+            } else {
+                AnyPath::from(DocumentPath::from_str(s)?)
+            })
+        }
+        for (a, b, expected) in [
+            ("c", "c", Some("c")),
+            ("c1", "c2", None),
+            ("c/d", "c", Some("c")),
+            ("c/d1", "c/d2", Some("c")),
+            ("c1/d", "c2/d", None),
+            ("c/d/c", "c/d", Some("c/d")),
+            ("c/d/c1/d", "c/d/c2/d", Some("c/d")),
+            ("c/d/c/d", "c/d/c/d", Some("c/d/c/d")),
+        ] {
+            let (a, b) = (any_path(a)?, any_path(b)?);
+            let expected = expected.map(any_path).transpose()?;
+            assert_eq!(a.common_ancestor(&b), expected);
+            assert_eq!(b.common_ancestor(&a), expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_collection_and_push_doc_and_pop() -> anyhow::Result<()> {
+        let mut any_path = AnyPath::from(CollectionPath::from_str("chatrooms")?);
+        assert!(any_path.push_collection("messages").is_err());
+        assert!(any_path.push_doc("chat/room1").is_err());
+        assert_eq!(any_path.to_string(), "chatrooms");
+
+        any_path.push_doc("chatroom1")?;
+        assert!(any_path.push_doc("chatroom2").is_err());
+        assert!(any_path.push_collection("").is_err());
+        assert_eq!(any_path.to_string(), "chatrooms/chatroom1");
+
+        any_path.push_collection("messages")?;
+        any_path.push_doc("message1")?;
+        assert_eq!(
+            any_path.to_string(),
+            "chatrooms/chatroom1/messages/message1"
+        );
+
+        let mut popped = vec![];
+        while any_path.pop() {
+            popped.push(any_path.to_string());
+        }
+        assert_eq!(
+            popped,
+            vec![
+                "chatrooms/chatroom1/messages",
+                "chatrooms/chatroom1",
+                "chatrooms"
+            ]
+        );
+        assert_eq!(any_path.to_string(), "chatrooms");
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_ord() -> anyhow::Result<()> {
+        let expected = vec![
+            "a", "a/b", "a/b/c", "a/b/c/d", "a/b/c2", "a/b2", "a-", "a-/b", "a2",
+        ];
+        let mut any_paths = expected
+            .iter()
+            .rev()
+            .map(|s| {
+                Ok(if s.split('/').count() % 2 == 1 {
+                    AnyPath::from(CollectionPath::from_str(s)?)
+                } else {
+                    AnyPath::from(DocumentPath::from_str(s)?)
+                })
+            })
+            .collect::<anyhow::Result<Vec<AnyPath>>>()?;
+        any_paths.sort();
+        assert_eq!(
+            any_paths
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parent() -> anyhow::Result<()> {
+        let any_path = AnyPath::from(DocumentPath::from_str(
+            "chatrooms/chatroom1/messages/message1",
+        )?);
+        let mut parents = vec![];
+        let mut current = any_path.parent();
+        while let Some(parent) = current {
+            current = parent.parent();
+            parents.push(parent.to_string());
+        }
+        assert_eq!(
+            parents,
+            vec![
+                "chatrooms/chatroom1/messages",
+                "chatrooms/chatroom1",
+                "chatrooms"
+            ]
+        );
+        Ok(())
+    }
+}
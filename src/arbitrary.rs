@@ -0,0 +1,251 @@
+// `arbitrary::Arbitrary` implementations, which only construct valid values.
+//
+// The ids are built from the fuzzer input and repaired to satisfy their limits, so that
+// most inputs produce a value. The names and paths are built from valid ids and have a depth
+// of 1 to 4. Inputs that would exceed the length limit of a name are rejected with
+// `arbitrary::Error::IncorrectFormat`.
+
+use ::arbitrary::{Arbitrary, Error, Result, Unstructured};
+
+use crate::{
+    limits, AnyName, AnyPath, CollectionId, CollectionName, CollectionPath, DatabaseId,
+    DatabaseName, DocumentId, DocumentName, DocumentPath, ProjectId, RootDocumentName,
+};
+
+const LOWERCASE_LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const LOWERCASE_LETTERS_AND_DIGITS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+const LOWERCASE_LETTERS_DIGITS_AND_HYPHEN: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789-";
+
+// Returns a string of `[a-z][a-z0-9-]*[a-z0-9]` with a length in `min_len..=max_len`.
+fn lowercase_id(u: &mut Unstructured<'_>, min_len: usize, max_len: usize) -> Result<String> {
+    let len = u.int_in_range(min_len..=max_len)?;
+    let mut s = String::with_capacity(len);
+    s.push(char::from(*u.choose(LOWERCASE_LETTERS)?));
+    for _ in 1..len - 1 {
+        s.push(char::from(*u.choose(LOWERCASE_LETTERS_DIGITS_AND_HYPHEN)?));
+    }
+    s.push(char::from(*u.choose(LOWERCASE_LETTERS_AND_DIGITS)?));
+    Ok(s)
+}
+
+// Returns a string that satisfies the limits of `CollectionId` and `DocumentId`.
+fn id_string(u: &mut Unstructured<'_>) -> Result<String> {
+    Ok(repair_id_string(String::arbitrary(u)?))
+}
+
+fn repair_id_string(s: String) -> String {
+    let mut s = s.replace('/', "");
+    // Leaves room for the character prepended below.
+    let mut len = s.len().min(limits::MAX_ID_BYTES - 1);
+    while !s.is_char_boundary(len) {
+        len -= 1;
+    }
+    s.truncate(len);
+    // Prepending a character fixes an empty id, `.`, `..` and `__.*__`.
+    if s.is_empty() || s == "." || s == ".." || (s.starts_with("__") && s.ends_with("__")) {
+        s.insert(0, 'x');
+    }
+    s
+}
+
+impl<'a> Arbitrary<'a> for AnyName {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => AnyName::from(CollectionName::arbitrary(u)?),
+            1 => AnyName::from(DocumentName::arbitrary(u)?),
+            _ => AnyName::from(RootDocumentName::arbitrary(u)?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for AnyPath {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(if bool::arbitrary(u)? {
+            AnyPath::from(CollectionPath::arbitrary(u)?)
+        } else {
+            AnyPath::from(DocumentPath::arbitrary(u)?)
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for CollectionId {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        CollectionId::try_from(id_string(u)?).map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a> Arbitrary<'a> for CollectionName {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        CollectionName::try_new(
+            RootDocumentName::arbitrary(u)?,
+            CollectionPath::arbitrary(u)?,
+        )
+        .map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a> Arbitrary<'a> for CollectionPath {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut document_path = None;
+        for _ in 0..u.int_in_range(0..=3)? {
+            document_path = Some(DocumentPath::new(
+                CollectionPath::new(document_path, CollectionId::arbitrary(u)?),
+                DocumentId::arbitrary(u)?,
+            ));
+        }
+        Ok(CollectionPath::new(
+            document_path,
+            CollectionId::arbitrary(u)?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for DatabaseId {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.ratio(1, 4)? {
+            return Ok(DatabaseId::default());
+        }
+        DatabaseId::try_from(lowercase_id(u, 4, 63)?).map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a> Arbitrary<'a> for DatabaseName {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(DatabaseName::new(
+            ProjectId::arbitrary(u)?,
+            DatabaseId::arbitrary(u)?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for DocumentId {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        DocumentId::try_from(id_string(u)?).map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a> Arbitrary<'a> for DocumentName {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        DocumentName::try_new(RootDocumentName::arbitrary(u)?, DocumentPath::arbitrary(u)?)
+            .map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a> Arbitrary<'a> for DocumentPath {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(DocumentPath::new(
+            CollectionPath::arbitrary(u)?,
+            DocumentId::arbitrary(u)?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for ProjectId {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // Rejects the ids that contain a reserved word (e.g. `google`).
+        ProjectId::try_from(lowercase_id(u, 6, 30)?).map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a> Arbitrary<'a> for RootDocumentName {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(RootDocumentName::new(DatabaseName::arbitrary(u)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    // Returns pseudo-random bytes (xorshift) as the fuzzer input.
+    fn inputs() -> impl Iterator<Item = Vec<u8>> {
+        let mut x = 0x2545_f491_4f6c_dd1d_u64;
+        (0..200).map(move |i| {
+            (0..i * 16)
+                .map(|_| {
+                    x ^= x << 13;
+                    x ^= x >> 7;
+                    x ^= x << 17;
+                    x as u8
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_ids() -> anyhow::Result<()> {
+        for data in inputs() {
+            let mut u = Unstructured::new(&data);
+            let collection_id = CollectionId::arbitrary(&mut u)?;
+            assert_eq!(
+                CollectionId::from_str(collection_id.as_ref())?,
+                collection_id
+            );
+            let document_id = DocumentId::arbitrary(&mut u)?;
+            assert_eq!(DocumentId::from_str(document_id.as_ref())?, document_id);
+            let database_id = DatabaseId::arbitrary(&mut u)?;
+            assert_eq!(DatabaseId::from_str(database_id.as_ref())?, database_id);
+            if let Ok(project_id) = ProjectId::arbitrary(&mut u) {
+                assert_eq!(ProjectId::from_str(project_id.as_ref())?, project_id);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_id_string() {
+        for (s, expected) in [
+            ("".to_string(), "x".to_string()),
+            (".".to_string(), "x.".to_string()),
+            ("..".to_string(), "x..".to_string()),
+            ("__x__".to_string(), "x__x__".to_string()),
+            ("chat/rooms".to_string(), "chatrooms".to_string()),
+            ("x".repeat(1_600), "x".repeat(1_499)),
+            ("\u{3042}".repeat(600), "\u{3042}".repeat(499)),
+        ] {
+            let repaired = repair_id_string(s);
+            assert_eq!(repaired, expected);
+            assert!(CollectionId::from_str(&repaired).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_names_and_paths() -> anyhow::Result<()> {
+        let mut count = 0;
+        for data in inputs() {
+            let mut u = Unstructured::new(&data);
+            let Ok(document_name) = DocumentName::arbitrary(&mut u) else {
+                continue;
+            };
+            assert_eq!(
+                DocumentName::from_str(document_name.as_str())?,
+                document_name
+            );
+            let Ok(any_name) = AnyName::arbitrary(&mut u) else {
+                continue;
+            };
+            let s = any_name.to_string();
+            match any_name {
+                AnyName::Collection(collection_name) => {
+                    assert_eq!(CollectionName::from_str(&s)?, collection_name)
+                }
+                AnyName::Document(document_name) => {
+                    assert_eq!(DocumentName::from_str(&s)?, document_name)
+                }
+                AnyName::RootDocument(root_document_name) => {
+                    assert_eq!(RootDocumentName::from_str(&s)?, root_document_name)
+                }
+            }
+            let any_path = AnyPath::arbitrary(&mut u)?;
+            assert_eq!(
+                AnyPath::parse(&any_path.to_string(), &Default::default())?,
+                any_path
+            );
+            count += 1;
+        }
+        assert!(count > 100);
+        Ok(())
+    }
+}
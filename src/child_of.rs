@@ -0,0 +1,140 @@
+use crate::{CollectionName, CollectionPath, DocumentName, DocumentPath, RootDocumentName};
+
+/// The relation between a name or path and the type of its direct parent.
+///
+/// `T: ChildOf<P>` means that a `T` can be a direct child of a `P`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{ChildOf,CollectionName,CollectionPath,DocumentName,DocumentPath};
+/// use std::str::FromStr;
+///
+/// fn children<'a, C, P>(items: &'a [C], parent: &'a P) -> impl Iterator<Item = &'a C>
+/// where
+///     C: ChildOf<P>,
+/// {
+///     items.iter().filter(move |item| item.is_child_of(parent))
+/// }
+///
+/// let parent = CollectionPath::from_str("chatrooms")?;
+/// let items = [
+///     DocumentPath::from_str("chatrooms/chatroom1")?,
+///     DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?,
+///     DocumentPath::from_str("chatrooms/chatroom2")?,
+/// ];
+/// assert_eq!(children(&items, &parent).count(), 2);
+///
+/// let parent = DocumentName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+/// )?;
+/// let items = [CollectionName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+/// )?];
+/// assert_eq!(children(&items, &parent).count(), 1);
+/// #     Ok(())
+/// # }
+/// ```
+pub trait ChildOf<P> {
+    /// Returns `true` if `parent` is the direct parent of this name or path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{ChildOf,CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert!(document_path.is_child_of(&CollectionPath::from_str("chatrooms")?));
+    /// assert!(!document_path.is_child_of(&CollectionPath::from_str("messages")?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    fn is_child_of(&self, parent: &P) -> bool;
+}
+
+impl ChildOf<DocumentName> for CollectionName {
+    fn is_child_of(&self, parent: &DocumentName) -> bool {
+        self.root_document_name() == parent.root_document_name()
+            && self.collection_path().is_child_of(parent.document_path())
+    }
+}
+
+impl ChildOf<RootDocumentName> for CollectionName {
+    fn is_child_of(&self, parent: &RootDocumentName) -> bool {
+        self.root_document_name() == parent && self.collection_path().parent().is_none()
+    }
+}
+
+impl ChildOf<DocumentPath> for CollectionPath {
+    fn is_child_of(&self, parent: &DocumentPath) -> bool {
+        CollectionPath::parent(self) == Some(parent)
+    }
+}
+
+impl ChildOf<CollectionName> for DocumentName {
+    fn is_child_of(&self, parent: &CollectionName) -> bool {
+        self.root_document_name() == parent.root_document_name()
+            && self.document_path().is_child_of(parent.collection_path())
+    }
+}
+
+impl ChildOf<CollectionPath> for DocumentPath {
+    fn is_child_of(&self, parent: &CollectionPath) -> bool {
+        DocumentPath::parent(self) == parent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_names() -> anyhow::Result<()> {
+        let root_document_name =
+            RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?;
+        let collection_name = CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms",
+        )?;
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        let child_collection_name = CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages",
+        )?;
+        let other_database_collection_name = CollectionName::from_str(
+            "projects/my-project/databases/other-database/documents/chatrooms",
+        )?;
+
+        assert!(collection_name.is_child_of(&root_document_name));
+        assert!(!child_collection_name.is_child_of(&root_document_name));
+        assert!(!other_database_collection_name.is_child_of(&root_document_name));
+
+        assert!(document_name.is_child_of(&collection_name));
+        assert!(!document_name.is_child_of(&other_database_collection_name));
+        assert!(!document_name.is_child_of(&child_collection_name));
+
+        assert!(child_collection_name.is_child_of(&document_name));
+        assert!(!collection_name.is_child_of(&document_name));
+        Ok(())
+    }
+
+    #[test]
+    fn test_paths() -> anyhow::Result<()> {
+        let collection_path = CollectionPath::from_str("chatrooms")?;
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+        let child_collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+        let child_document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+
+        assert!(document_path.is_child_of(&collection_path));
+        assert!(!child_document_path.is_child_of(&collection_path));
+        assert!(child_collection_path.is_child_of(&document_path));
+        assert!(!collection_path.is_child_of(&document_path));
+        assert!(child_document_path.is_child_of(&child_collection_path));
+        Ok(())
+    }
+}
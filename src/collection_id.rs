@@ -1,4 +1,8 @@
-use crate::{error::ErrorKind, Error};
+use crate::{
+    error::ErrorKind,
+    id_string::{self, IdString},
+    limits, Error, ParseOptions,
+};
 
 /// A collection id.
 ///
@@ -12,6 +16,16 @@ use crate::{error::ErrorKind, Error};
 /// > - Cannot solely consist of a single period (.) or double periods (..)
 /// > - Cannot match the regular expression __.*__
 ///
+/// # Sharing
+///
+/// The id is held in an `Arc<str>`, so clones (for example the ids in the names derived with
+/// `doc` and `collection`) share a single allocation.
+/// With the `intern` feature enabled, every `CollectionId` is additionally looked up in a
+/// process-wide interner when it is created, so each distinct id in use is stored only once.
+/// Ids that are no longer used by any `CollectionId` are dropped from the interner as it grows.
+/// With the `smol_str` feature enabled (and `intern` disabled), the id is held in a
+/// `smol_str::SmolStr` instead, which stores short ids inline and shares long ids.
+///
 /// # Examples
 ///
 /// ```rust
@@ -27,11 +41,196 @@ use crate::{error::ErrorKind, Error};
 /// ```
 ///
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct CollectionId(String);
+pub struct CollectionId(IdString<Inner>);
+
+impl CollectionId {
+    /// Creates a new `CollectionId` from a `'static` string.
+    ///
+    /// This is a `const fn`, so well-known ids can be defined as `const`s or `static`s without parsing at runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not a valid `CollectionId`. In a const context, this is a compile-time error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::CollectionId;
+    /// use std::str::FromStr;
+    ///
+    /// static ID: CollectionId = CollectionId::from_static("users");
+    /// assert_eq!(ID.as_ref(), "users");
+    /// assert_eq!(ID, CollectionId::from_str("users").unwrap());
+    /// ```
+    ///
+    /// ```rust,compile_fail
+    /// use firestore_path::CollectionId;
+    ///
+    /// static ID: CollectionId = CollectionId::from_static("chat/rooms");
+    /// ```
+    pub const fn from_static(s: &'static str) -> Self {
+        let result = validate(s);
+        let is_valid = result.is_ok();
+        // `ErrorKind` has a destructor, which cannot be run in a const context.
+        std::mem::forget(result);
+        assert!(is_valid, "invalid CollectionId");
+        Self(IdString::Static(s))
+    }
+
+    /// Creates a new `CollectionId` from `s` with `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid `CollectionId` under `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,ParseOptions};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     CollectionId::from_str_with("chatrooms", &ParseOptions::strict())?,
+    ///     CollectionId::from_str("chatrooms")?
+    /// );
+    /// assert!(CollectionId::from_str("__chatrooms__").is_err());
+    /// assert!(CollectionId::from_str_with("__chatrooms__", &ParseOptions::lenient()).is_ok());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        validate_with(s, options)?;
+        Ok(Self(IdString::Owned(intern(s))))
+    }
+
+    /// Returns `true` if `s` is a valid `CollectionId`.
+    ///
+    /// This is the same as `CollectionId::validate(s).is_ok()` and does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::CollectionId;
+    ///
+    /// assert!(CollectionId::is_valid("chatrooms"));
+    /// assert!(!CollectionId::is_valid("chat/rooms"));
+    /// ```
+    pub fn is_valid(s: &str) -> bool {
+        validate(s).is_ok()
+    }
+
+    /// Checks that `s` is a valid `CollectionId` without creating a `CollectionId`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that `CollectionId::from_str(s)` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::CollectionId;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(CollectionId::validate("chatrooms").is_ok());
+    /// assert_eq!(
+    ///     CollectionId::validate("chat/rooms").unwrap_err(),
+    ///     CollectionId::from_str("chat/rooms").unwrap_err()
+    /// );
+    /// ```
+    pub fn validate(s: &str) -> Result<(), Error> {
+        Ok(validate(s)?)
+    }
+
+    // An empty (invalid) `CollectionId` used only as a temporary value while moving out of `&mut`.
+    pub(crate) fn placeholder() -> Self {
+        Self(IdString::Static(""))
+    }
+}
+
+// Interned ids are shared through the interner, so `smol_str` only applies without `intern`.
+#[cfg(all(feature = "smol_str", not(feature = "intern")))]
+type Inner = smol_str::SmolStr;
+
+#[cfg(not(all(feature = "smol_str", not(feature = "intern"))))]
+type Inner = std::sync::Arc<str>;
+
+pub(crate) const fn validate(s: &str) -> Result<(), ErrorKind> {
+    validate_with(s, &ParseOptions::strict())
+}
+
+pub(crate) const fn validate_with(s: &str, options: &ParseOptions) -> Result<(), ErrorKind> {
+    // <https://firebase.google.com/docs/firestore/quotas#collections_documents_and_fields>
+    if s.is_empty() || (options.length_limits && s.len() > limits::MAX_ID_BYTES) {
+        return Err(ErrorKind::LengthOutOfBounds);
+    }
+    if id_string::contains(s, "/") {
+        return Err(ErrorKind::ContainsSlash);
+    }
+    if id_string::eq(s, ".") || id_string::eq(s, "..") {
+        return Err(ErrorKind::SinglePeriodOrDoublePeriods);
+    }
+    if options.reserved_ids && id_string::starts_with(s, "__") && id_string::ends_with(s, "__") {
+        return Err(ErrorKind::MatchesReservedIdPattern);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "intern")]
+fn intern(s: &str) -> Inner {
+    use std::sync::{Mutex, OnceLock, PoisonError};
+
+    static INTERNER: OnceLock<Mutex<Interner>> = OnceLock::new();
+    INTERNER
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .intern(s)
+}
+
+// A set of ids that drops the ids no longer used outside of it.
+//
+// The set is swept (each time its size doubles) instead of on every drop, so the ids that are not
+// used anymore are kept at most until the next sweep.
+#[cfg(feature = "intern")]
+#[derive(Default)]
+struct Interner {
+    ids: std::collections::HashSet<Inner>,
+    sweep_at: usize,
+}
+
+#[cfg(feature = "intern")]
+impl Interner {
+    const MIN_SWEEP_AT: usize = 1_024;
+
+    fn intern(&mut self, s: &str) -> Inner {
+        if let Some(interned) = self.ids.get(s) {
+            return Inner::clone(interned);
+        }
+        if self.ids.len() >= self.sweep_at {
+            self.sweep();
+        }
+        let interned = Inner::from(s);
+        self.ids.insert(Inner::clone(&interned));
+        interned
+    }
+
+    fn sweep(&mut self) {
+        // An id with a strong count of 1 is only held by this set. It cannot be cloned without the
+        // lock of the interner, so it is safe to drop.
+        self.ids.retain(|id| Inner::strong_count(id) > 1);
+        self.sweep_at = (self.ids.len() * 2).max(Self::MIN_SWEEP_AT);
+    }
+}
+
+#[cfg(not(feature = "intern"))]
+fn intern(s: &str) -> Inner {
+    Inner::from(s)
+}
 
 impl std::convert::AsRef<str> for CollectionId {
     fn as_ref(&self) -> &str {
-        self.0.as_ref()
+        self.0.as_str()
     }
 }
 
@@ -39,7 +238,8 @@ impl std::convert::TryFrom<&str> for CollectionId {
     type Error = Error;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        Self::try_from(s.to_string())
+        validate(s)?;
+        Ok(Self(IdString::Owned(intern(s))))
     }
 }
 
@@ -47,20 +247,7 @@ impl std::convert::TryFrom<String> for CollectionId {
     type Error = Error;
 
     fn try_from(s: String) -> Result<Self, Self::Error> {
-        // <https://firebase.google.com/docs/firestore/quotas#collections_documents_and_fields>
-        if !(1..=1500).contains(&s.len()) {
-            return Err(Error::from(ErrorKind::LengthOutOfBounds));
-        }
-        if s.contains('/') {
-            return Err(Error::from(ErrorKind::ContainsSlash));
-        }
-        if s == "." || s == ".." {
-            return Err(Error::from(ErrorKind::SinglePeriodOrDoublePeriods));
-        }
-        if s.starts_with("__") && s.ends_with("__") {
-            return Err(Error::from(ErrorKind::MatchesReservedIdPattern));
-        }
-        Ok(Self(s))
+        Self::try_from(s.as_str())
     }
 }
 
@@ -127,4 +314,104 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_clone_shares_allocation() -> anyhow::Result<()> {
+        let collection_id = CollectionId::from_str(&"x".repeat(32))?;
+        let cloned = collection_id.clone();
+        assert!(std::ptr::eq(collection_id.as_ref(), cloned.as_ref()));
+        Ok(())
+    }
+
+    #[cfg(feature = "intern")]
+    #[test]
+    fn test_intern() -> anyhow::Result<()> {
+        let collection_id1 = CollectionId::from_str("messages")?;
+        let collection_id2 = CollectionId::try_from("messages".to_string())?;
+        assert!(std::ptr::eq(
+            collection_id1.as_ref(),
+            collection_id2.as_ref()
+        ));
+
+        let collection_id3 = CollectionId::from_str("members")?;
+        assert!(!std::ptr::eq(
+            collection_id1.as_ref(),
+            collection_id3.as_ref()
+        ));
+        Ok(())
+    }
+
+    #[cfg(feature = "intern")]
+    #[test]
+    fn test_interner_sweep() {
+        let mut interner = Interner::default();
+        let used = interner.intern("messages");
+        drop(interner.intern("members"));
+        assert_eq!(interner.ids.len(), 2);
+        assert!(std::ptr::eq(
+            used.as_ref(),
+            interner.intern("messages").as_ref()
+        ));
+
+        interner.sweep();
+        assert_eq!(interner.ids.len(), 1);
+        assert!(interner.ids.contains("messages"));
+        assert!(!interner.ids.contains("members"));
+        assert_eq!(interner.sweep_at, Interner::MIN_SWEEP_AT);
+
+        for i in 0..Interner::MIN_SWEEP_AT * 2 {
+            drop(interner.intern(&format!("chatrooms{}", i)));
+        }
+        assert!(interner.ids.len() <= Interner::MIN_SWEEP_AT + 1);
+        assert!(interner.ids.contains("messages"));
+    }
+
+    #[test]
+    fn test_error_kind() -> anyhow::Result<()> {
+        for (s, expected) in [
+            ("".to_string(), ErrorKind::LengthOutOfBounds),
+            ("x".repeat(1501), ErrorKind::LengthOutOfBounds),
+            ("chat/rooms".to_string(), ErrorKind::ContainsSlash),
+            (".".to_string(), ErrorKind::SinglePeriodOrDoublePeriods),
+            ("..".to_string(), ErrorKind::SinglePeriodOrDoublePeriods),
+            (
+                "__chatrooms__".to_string(),
+                ErrorKind::MatchesReservedIdPattern,
+            ),
+        ] {
+            assert_eq!(CollectionId::from_str(&s).unwrap_err().kind(), &expected);
+            assert_eq!(CollectionId::try_from(s).unwrap_err().kind(), &expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_static() -> anyhow::Result<()> {
+        const ID: CollectionId = CollectionId::from_static("chatrooms");
+        assert_eq!(ID, CollectionId::from_str("chatrooms")?);
+        assert_eq!(ID.to_string(), "chatrooms");
+        assert!(std::panic::catch_unwind(|| CollectionId::from_static("__x__")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> anyhow::Result<()> {
+        for s in [
+            "chatrooms".to_string(),
+            "".to_string(),
+            "chat/rooms".to_string(),
+            "__x__".to_string(),
+            "x".repeat(1_501),
+        ] {
+            assert_eq!(
+                CollectionId::validate(&s).err(),
+                CollectionId::from_str(&s).err()
+            );
+            assert_eq!(
+                CollectionId::is_valid(&s),
+                CollectionId::from_str(&s).is_ok()
+            );
+        }
+        Ok(())
+    }
 }
@@ -1,8 +1,7 @@
-use std::str::FromStr;
-
 use crate::{
-    error::ErrorKind, CollectionId, CollectionPath, DatabaseName, DocumentId, DocumentName,
-    DocumentPath, Error, RootDocumentName,
+    error::ErrorKind, limits, stable_hash::StableHasher, AnyName, CollectionId, CollectionPath,
+    DatabaseId, DatabaseName, DocumentId, DocumentName, DocumentPath, Error, ParseOptions,
+    ProjectId, RootDocumentName, Segment,
 };
 
 /// A collection name.
@@ -11,6 +10,10 @@ use crate::{
 ///
 /// `{root_document_name}/{collection_path}`
 ///
+/// # Ordering
+///
+/// `CollectionName`s are ordered by the `RootDocumentName` and then segment by segment, which matches the ordering of references (`__name__`) in Firestore.
+///
 /// # Examples
 ///
 /// ```rust
@@ -39,10 +42,11 @@ use crate::{
 /// #     Ok(())
 /// # }
 /// ```
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct CollectionName {
     collection_path: CollectionPath,
     root_document_name: RootDocumentName,
+    string: String,
 }
 
 impl CollectionName {
@@ -77,10 +81,115 @@ impl CollectionName {
     where
         D: Into<RootDocumentName>,
     {
+        let root_document_name = root_document_name.into();
+        let string = format!("{}/{}", root_document_name, collection_path);
         Self {
             collection_path,
-            root_document_name: root_document_name.into(),
+            root_document_name,
+            string,
+        }
+    }
+
+    /// Creates a new `CollectionName`, checking the length of the name.
+    ///
+    /// `CollectionName::new` does not check the length, so use this method if `collection_path` is not known to be short enough.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is longer than `limits::MAX_DOCUMENT_NAME_BYTES` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionPath,DatabaseName,CollectionName};
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// assert_eq!(
+    ///     CollectionName::try_new(database_name.clone(), collection_path)?.to_string(),
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// );
+    ///
+    /// let collection_path = CollectionPath::from_str(&format!("{0}/x/{0}/x/{0}/x/{0}/x/{0}", "x".repeat(1_500)))?;
+    /// assert!(CollectionName::try_new(database_name, collection_path).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn try_new<D>(root_document_name: D, collection_path: CollectionPath) -> Result<Self, Error>
+    where
+        D: Into<RootDocumentName>,
+    {
+        let name = Self::new(root_document_name, collection_path);
+        if name.string.len() > limits::MAX_DOCUMENT_NAME_BYTES {
+            return Err(Error::from(ErrorKind::LengthOutOfBounds));
         }
+        Ok(name)
+    }
+
+    /// Returns an iterator over the ancestors of this `CollectionName`.
+    ///
+    /// The iterator yields the parent `DocumentName`, the parent `CollectionName`, ... and the `RootDocumentName` in that order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionName,DocumentName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.ancestors().collect::<Vec<AnyName>>(),
+    ///     vec![
+    ///         AnyName::from(DocumentName::from_str(
+    ///             "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///         )?),
+    ///         AnyName::from(CollectionName::from_str(
+    ///             "projects/my-project/databases/my-database/documents/chatrooms"
+    ///         )?),
+    ///         AnyName::from(RootDocumentName::from_str(
+    ///             "projects/my-project/databases/my-database/documents"
+    ///         )?),
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn ancestors(&self) -> impl Iterator<Item = AnyName> {
+        let parent = self
+            .parent()
+            .map(AnyName::from)
+            .unwrap_or_else(|| AnyName::from(self.root_document_name.clone()));
+        std::iter::successors(Some(parent), AnyName::parent)
+    }
+
+    /// Returns the canonical string representation of this `CollectionName`.
+    ///
+    /// The string is kept alongside the parsed components, so this does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.as_str(),
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.string
     }
 
     /// Returns the `CollectionId` of this `CollectionName`.
@@ -106,6 +215,56 @@ impl CollectionName {
         self.collection_path.collection_id()
     }
 
+    /// Returns the `CollectionId` at `index` (0-based, from the root) of this `CollectionName`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,CollectionName};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// assert_eq!(collection_name.collection_id_at(0), Some(&CollectionId::from_str("chatrooms")?));
+    /// assert_eq!(collection_name.collection_id_at(1), Some(&CollectionId::from_str("messages")?));
+    /// assert_eq!(collection_name.collection_id_at(2), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn collection_id_at(&self, index: usize) -> Option<&CollectionId> {
+        self.collection_ids().nth(index)
+    }
+
+    /// Returns an iterator over the `CollectionId`s of this `CollectionName` from the root to the leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,CollectionName};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.collection_ids().collect::<Vec<&CollectionId>>(),
+    ///     vec![
+    ///         &CollectionId::from_str("chatrooms")?,
+    ///         &CollectionId::from_str("messages")?
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn collection_ids(&self) -> impl Iterator<Item = &CollectionId> {
+        self.collection_path.collection_ids()
+    }
+
     /// Returns the `CollectionPath` of this `CollectionName`.
     ///
     /// # Examples
@@ -153,6 +312,31 @@ impl CollectionName {
         self.root_document_name.as_database_name()
     }
 
+    /// Returns the depth (the number of collections) of this `CollectionName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert_eq!(collection_name.depth(), 1);
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// assert_eq!(collection_name.depth(), 2);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn depth(&self) -> usize {
+        self.collection_path.depth()
+    }
+
     /// Creates a new `DocumentName` from this `CollectionName` and `document_id`.
     ///
     /// # Examples
@@ -188,177 +372,970 @@ impl CollectionName {
         self.clone().into_doc(document_id)
     }
 
-    /// Creates a new `DocumentName` by consuming the `CollectionName` with the provided `document_id`.
+    /// Creates a new `DocumentName` from this `CollectionName` and a random `DocumentId` (see `DocumentId::random`).
+    ///
+    /// This is like `doc()` without arguments of the client SDKs. The generated id is available from `document_id()` of the result.
+    ///
+    /// This method requires the `random` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new `DocumentName` is too long.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionId,CollectionName,DocumentName};
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str("projects/my-project/databases/my-database/documents/chatrooms")?;
+    /// let document = collection_name.doc_with_random_id()?;
+    /// assert_eq!(document.parent(), collection_name);
+    /// assert_eq!(document.document_id().as_ref().len(), 20);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "random")]
+    pub fn doc_with_random_id(&self) -> Result<DocumentName, Error> {
+        self.doc(DocumentId::random())
+    }
+
+    /// Creates new `DocumentName`s from this `CollectionName` and `document_ids`.
+    ///
+    /// This is the same as calling `doc` for each id.
+    ///
+    /// # Errors
+    ///
+    /// Each item is an error if the id cannot be converted into a `DocumentId` or the new `DocumentName` is too long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,DocumentName};
     /// use std::str::FromStr;
     ///
     /// let collection_name = CollectionName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms"
     /// )?;
+    /// let document_names = collection_name
+    ///     .docs(["chatroom1", "chatroom2"])
+    ///     .collect::<Result<Vec<DocumentName>, _>>()?;
     /// assert_eq!(
-    ///     collection_name.clone().into_doc("chatroom1")?,
-    ///     DocumentName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
-    ///     )?
-    /// );
-    /// assert_eq!(
-    ///     collection_name.into_doc("chatroom2")?,
-    ///     DocumentName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom2"
-    ///     )?
+    ///     document_names,
+    ///     [collection_name.doc("chatroom1")?, collection_name.doc("chatroom2")?]
     /// );
+    ///
+    /// let mut results = collection_name.docs(["chatroom1", "chat/room2"]);
+    /// assert!(results.next().unwrap().is_ok());
+    /// assert!(results.next().unwrap().is_err());
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn into_doc<E, T>(self, document_id: T) -> Result<DocumentName, Error>
+    pub fn docs<'a, E, I, T>(
+        &'a self,
+        document_ids: I,
+    ) -> impl Iterator<Item = Result<DocumentName, Error>> + 'a
     where
         E: std::fmt::Display,
+        I: IntoIterator<Item = T>,
+        I::IntoIter: 'a,
         T: TryInto<DocumentId, Error = E>,
     {
-        let document_id = document_id
-            .try_into()
-            .map_err(|e| Error::from(ErrorKind::DocumentIdConversion(e.to_string())))?;
-        let document_path = DocumentPath::new(self.collection_path, document_id);
-        let document_name = DocumentName::new(self.root_document_name, document_path);
-        Ok(document_name)
+        document_ids
+            .into_iter()
+            .map(move |document_id| self.doc(document_id))
     }
 
-    /// Consumes the `CollectionName`, returning the parent `DocumentName`.
+    /// Returns the range of the names of all documents in this `CollectionName` (including the documents in its subcollections).
+    ///
+    /// The range is `"{collection_name}/".."{collection_name}0"` (`'0'` is the successor of `'/'`),
+    /// which is suitable for `__name__ >= start AND __name__ < end` filters and for scans of key-value stores sorted by string.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionId,CollectionName,DocumentName};
+    /// use firestore_path::CollectionName;
     /// use std::str::FromStr;
     ///
     /// let collection_name = CollectionName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms"
     /// )?;
-    /// assert_eq!(collection_name.into_parent(), None);
+    /// let range = collection_name.document_name_range();
+    /// assert_eq!(range.start, "projects/my-project/databases/my-database/documents/chatrooms/");
+    /// assert_eq!(range.end, "projects/my-project/databases/my-database/documents/chatrooms0");
+    /// assert!(range.contains(&"projects/my-project/databases/my-database/documents/chatrooms/chatroom1".to_string()));
+    /// assert!(!range.contains(&"projects/my-project/databases/my-database/documents/chatrooms2/chatroom1".to_string()));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn document_name_range(&self) -> std::ops::Range<String> {
+        let prefix = self.prefix_str();
+        let end = format!("{}0", &prefix[..prefix.len() - 1]);
+        prefix..end
+    }
+
+    /// Returns the `DocumentId` at `index` (0-based, from the root) of this `CollectionName`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,DocumentId};
+    /// use std::str::FromStr;
     ///
     /// let collection_name = CollectionName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
     /// )?;
-    /// assert_eq!(
-    ///     collection_name.clone().into_parent(),
-    ///     Some(DocumentName::from_str(
-    ///       "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
-    ///     )?)
-    /// );
-    /// assert_eq!(
-    ///     collection_name.into_parent(),
-    ///     Some(DocumentName::from_str(
-    ///       "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
-    ///     )?)
-    /// );
+    /// assert_eq!(collection_name.document_id_at(0), Some(&DocumentId::from_str("chatroom1")?));
+    /// assert_eq!(collection_name.document_id_at(1), None);
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn into_parent(self) -> Option<DocumentName> {
-        Option::<DocumentPath>::from(self.collection_path).map(|document_path| {
-            DocumentName::new(DatabaseName::from(self.root_document_name), document_path)
-        })
+    pub fn document_id_at(&self, index: usize) -> Option<&DocumentId> {
+        self.document_ids().nth(index)
     }
 
-    /// Consumes the `CollectionName`, returning the `RootDocumentName`.
+    /// Returns an iterator over the `DocumentId`s of this `CollectionName` from the root to the leaf.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionName,RootDocumentName};
+    /// use firestore_path::{CollectionName,DocumentId};
     /// use std::str::FromStr;
     ///
     /// let collection_name = CollectionName::from_str(
-    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
     /// )?;
-    /// let root_document_name = collection_name.into_root_document_name();
     /// assert_eq!(
-    ///     root_document_name,
-    ///     RootDocumentName::from_str(
-    ///         "projects/my-project/databases/my-database/documents"
-    ///     )?
+    ///     collection_name.document_ids().collect::<Vec<&DocumentId>>(),
+    ///     vec![
+    ///         &DocumentId::from_str("chatroom1")?
+    ///     ]
     /// );
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn into_root_document_name(self) -> RootDocumentName {
-        self.root_document_name
+    pub fn document_ids(&self) -> impl Iterator<Item = &DocumentId> {
+        self.collection_path.document_ids()
     }
 
-    /// Returns the parent `DocumentName` of this `CollectionName`.
+    /// Returns the length in bytes of the canonical string representation of this `CollectionName`.
+    ///
+    /// This is the same as `to_string().len()` but does not allocate.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionId,CollectionName,DocumentName};
+    /// use firestore_path::CollectionName;
     /// use std::str::FromStr;
     ///
     /// let collection_name = CollectionName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms"
     /// )?;
-    /// assert_eq!(collection_name.parent(), None);
-    ///
-    /// let collection_name = CollectionName::from_str(
-    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
-    /// )?;
-    /// assert_eq!(
-    ///     collection_name.parent(),
-    ///     Some(DocumentName::from_str(
-    ///       "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
-    ///     )?)
-    /// );
-    /// assert_eq!(
-    ///     collection_name.parent(),
-    ///     Some(DocumentName::from_str(
-    ///       "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
-    ///     )?)
-    /// );
+    /// assert_eq!(collection_name.encoded_len(), collection_name.to_string().len());
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn parent(&self) -> Option<DocumentName> {
-        self.clone().into_parent()
+    pub fn encoded_len(&self) -> usize {
+        self.string.len()
     }
 
-    /// Returns the `RootDocumentName` of this `CollectionName`.
+    /// Returns `true` if this `CollectionName` and `other` have the same relative path, ignoring the `DatabaseName`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionName,RootDocumentName};
+    /// use firestore_path::CollectionName;
     /// use std::str::FromStr;
     ///
     /// let collection_name = CollectionName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms"
     /// )?;
-    /// let root_document_name = collection_name.root_document_name();
+    /// assert!(collection_name.eq_ignore_database(&CollectionName::from_str(
+    ///     "projects/my-project/databases/(default)/documents/chatrooms"
+    /// )?));
+    /// assert!(!collection_name.eq_ignore_database(&CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/messages"
+    /// )?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn eq_ignore_database(&self, other: &CollectionName) -> bool {
+        self.collection_path == other.collection_path
+    }
+
+    /// Creates a new `CollectionName` from `project_id`, `database_id` and `collection_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the parts cannot be converted or the name is too long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseId,CollectionPath,CollectionName,ProjectId};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_parts("my-project", "my-database", "chatrooms")?;
     /// assert_eq!(
-    ///     root_document_name,
-    ///     &RootDocumentName::from_str(
-    ///         "projects/my-project/databases/my-database/documents"
-    ///     )?
+    ///     collection_name,
+    ///     CollectionName::from_str("projects/my-project/databases/my-database/documents/chatrooms")?
+    /// );
+    /// assert_eq!(
+    ///     CollectionName::from_parts(
+    ///         ProjectId::from_str("my-project")?,
+    ///         DatabaseId::from_str("my-database")?,
+    ///         CollectionPath::from_str("chatrooms")?
+    ///     )?,
+    ///     collection_name
     /// );
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn root_document_name(&self) -> &RootDocumentName {
-        &self.root_document_name
+    pub fn from_parts<P, D, T>(
+        project_id: P,
+        database_id: D,
+        collection_path: T,
+    ) -> Result<Self, Error>
+    where
+        P: TryInto<ProjectId>,
+        P::Error: std::fmt::Display,
+        D: TryInto<DatabaseId>,
+        D::Error: std::fmt::Display,
+        T: TryInto<CollectionPath>,
+        T::Error: std::fmt::Display,
+    {
+        let project_id = project_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::ProjectIdConversion(e.to_string())))?;
+        let database_id = database_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::DatabaseIdConversion(e.to_string())))?;
+        DatabaseName::new(project_id, database_id).into_collection(collection_path)
     }
-}
 
-impl std::convert::From<CollectionName> for CollectionId {
-    fn from(collection_name: CollectionName) -> Self {
-        Self::from(collection_name.collection_path)
-    }
+    /// Creates a new `CollectionName` from `s` with `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid `CollectionName` under `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,ParseOptions};
+    /// use std::str::FromStr;
+    ///
+    /// let s = "projects/my-project/databases/my-database/documents/chatrooms";
+    /// assert_eq!(
+    ///     CollectionName::from_str_with(s, &ParseOptions::strict())?,
+    ///     CollectionName::from_str(s)?
+    /// );
+    ///
+    /// let s = "projects/my-project/databases/my-database/documents/__chatrooms__";
+    /// assert!(CollectionName::from_str(s).is_err());
+    /// assert!(CollectionName::from_str_with(s, &ParseOptions::lenient()).is_ok());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        options.parse_normalized(s, |s| Self::try_from_string_with(s.to_string(), options))
+    }
+
+    /// Creates a new `DocumentName` by consuming the `CollectionName` with the provided `document_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,CollectionName,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.clone().into_doc("chatroom1")?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?
+    /// );
+    /// assert_eq!(
+    ///     collection_name.into_doc("chatroom2")?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom2"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_doc<E, T>(self, document_id: T) -> Result<DocumentName, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DocumentId, Error = E>,
+    {
+        let document_id = document_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::DocumentIdConversion(e.to_string())))?;
+        let document_path = DocumentPath::new(self.collection_path, document_id);
+        DocumentName::try_new(self.root_document_name, document_path)
+    }
+
+    /// Consumes the `CollectionName`, returning the parent `DocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,CollectionName,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert_eq!(collection_name.into_parent(), None);
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.clone().into_parent(),
+    ///     Some(DocumentName::from_str(
+    ///       "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?)
+    /// );
+    /// assert_eq!(
+    ///     collection_name.into_parent(),
+    ///     Some(DocumentName::from_str(
+    ///       "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?)
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_parent(self) -> Option<DocumentName> {
+        Option::<DocumentPath>::from(self.collection_path).map(|document_path| {
+            DocumentName::new(DatabaseName::from(self.root_document_name), document_path)
+        })
+    }
+
+    /// Consumes the `CollectionName`, returning the `RootDocumentName` and the `CollectionPath`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,CollectionPath,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.into_parts(),
+    ///     (
+    ///         RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?,
+    ///         CollectionPath::from_str("chatrooms/chatroom1/messages")?
+    ///     )
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_parts(self) -> (RootDocumentName, CollectionPath) {
+        (self.root_document_name, self.collection_path)
+    }
+
+    /// Consumes the `CollectionName`, returning the `RootDocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// let root_document_name = collection_name.into_root_document_name();
+    /// assert_eq!(
+    ///     root_document_name,
+    ///     RootDocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_root_document_name(self) -> RootDocumentName {
+        self.root_document_name
+    }
+
+    /// Converts this `CollectionName` into its canonical string representation.
+    ///
+    /// The stored canonical string is returned as is, without formatting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.into_string(),
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_string(self) -> String {
+        self.string
+    }
+
+    /// Returns `true` if this `CollectionName` is in the default database `(default)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(CollectionName::from_str("projects/my-project/databases/(default)/documents/chatrooms")?.is_default_database());
+    /// assert!(!CollectionName::from_str("projects/my-project/databases/my-database/documents/chatrooms")?.is_default_database());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_default_database(&self) -> bool {
+        self.database_name().is_default_database()
+    }
+
+    /// Returns `true` if this `CollectionName` belongs to the collection group `collection_id`.
+    ///
+    /// That is, the `CollectionId` of this `CollectionName` or any of its ancestors is equal to `collection_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,CollectionName};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// assert!(collection_name.is_in_collection_group(&CollectionId::from_str("chatrooms")?));
+    /// assert!(collection_name.is_in_collection_group(&CollectionId::from_str("messages")?));
+    /// assert!(!collection_name.is_in_collection_group(&CollectionId::from_str("users")?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_in_collection_group(&self, collection_id: &CollectionId) -> bool {
+        self.collection_path.is_in_collection_group(collection_id)
+    }
+
+    /// Returns `true` if `s` is a valid `CollectionName`.
+    ///
+    /// This is the same as `CollectionName::validate(s).is_ok()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::CollectionName;
+    ///
+    /// assert!(CollectionName::is_valid("projects/my-project/databases/my-database/documents/chatrooms"));
+    /// assert!(!CollectionName::is_valid("projects/my-project/databases/my-database/documents/chatrooms/chatroom1"));
+    /// ```
+    pub fn is_valid(s: &str) -> bool {
+        Self::validate(s).is_ok()
+    }
+
+    /// Returns the parent `DocumentName` of this `CollectionName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,CollectionName,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert_eq!(collection_name.parent(), None);
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.parent(),
+    ///     Some(DocumentName::from_str(
+    ///       "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?)
+    /// );
+    /// assert_eq!(
+    ///     collection_name.parent(),
+    ///     Some(DocumentName::from_str(
+    ///       "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?)
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parent(&self) -> Option<DocumentName> {
+        self.clone().into_parent()
+    }
+
+    /// Returns the canonical prefix string (with a trailing slash) of the names of the descendants of this `CollectionName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// let prefix = collection_name.prefix_str();
+    /// assert_eq!(
+    ///     prefix,
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/"
+    /// );
+    /// assert!("projects/my-project/databases/my-database/documents/chatrooms/messages".starts_with(&prefix));
+    /// assert!(!"projects/my-project/databases/my-database/documents/chatrooms2/chatroom1".starts_with(&prefix));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn prefix_str(&self) -> String {
+        format!("{}/", self)
+    }
+
+    /// Returns the `RootDocumentName` of this `CollectionName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// let root_document_name = collection_name.root_document_name();
+    /// assert_eq!(
+    ///     root_document_name,
+    ///     &RootDocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn root_document_name(&self) -> &RootDocumentName {
+        &self.root_document_name
+    }
+
+    /// Returns the value of the `x-goog-request-params` header that routes requests to the database of this `CollectionName`.
+    ///
+    /// See [`DatabaseName::routing_header_value`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.routing_header_value(),
+    ///     "database=projects%2Fmy-project%2Fdatabases%2Fmy-database"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn routing_header_value(&self) -> String {
+        self.database_name().routing_header_value()
+    }
+
+    /// Returns `true` if this `CollectionName` and `other` are in the same database.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert!(collection_name.same_database(&CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?));
+    /// assert!(!collection_name.same_database(&CollectionName::from_str(
+    ///     "projects/my-project/databases/(default)/documents/chatrooms"
+    /// )?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn same_database(&self, other: &CollectionName) -> bool {
+        self.database_name() == other.database_name()
+    }
+
+    /// Returns `true` if this `CollectionName` and `other` are in the same project.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert!(collection_name.same_project(&CollectionName::from_str(
+    ///     "projects/my-project/databases/(default)/documents/chatrooms"
+    /// )?));
+    /// assert!(!collection_name.same_project(&CollectionName::from_str(
+    ///     "projects/my-project2/databases/my-database/documents/chatrooms"
+    /// )?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn same_project(&self, other: &CollectionName) -> bool {
+        self.database_name().project_id() == other.database_name().project_id()
+    }
+
+    /// Returns an iterator over the segments of the `CollectionPath` of this `CollectionName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name
+    ///         .segments()
+    ///         .map(|segment| segment.to_string())
+    ///         .collect::<Vec<String>>(),
+    ///     vec!["chatrooms", "chatroom1", "messages"]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn segments(&self) -> impl Iterator<Item = Segment<'_>> {
+        self.collection_path.segments()
+    }
+
+    /// Creates a new `CollectionName` with the same parent as this `CollectionName` and the provided `collection_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.sibling("members")?,
+    ///     CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/members"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn sibling<E, T>(&self, collection_id: T) -> Result<CollectionName, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<CollectionId, Error = E>,
+    {
+        CollectionName::try_new(
+            self.root_document_name.clone(),
+            self.collection_path.sibling(collection_id)?,
+        )
+    }
+
+    /// Returns a 64-bit hash of the canonical string representation of this `CollectionName`.
+    ///
+    /// Unlike `std::hash::Hash`, the value is stable across versions, platforms and processes.
+    /// It is the 64-bit FNV-1a hash of the UTF-8 bytes of `to_string()`, so it can be persisted or computed in other languages.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert_eq!(collection_name.stable_hash(), 0xb5979ac024204748);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn stable_hash(&self) -> u64 {
+        StableHasher::hash(|hasher| self.write_to(hasher))
+    }
+
+    /// Returns the `CollectionPath` relative to the ancestor `prefix`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `prefix` is in a different database or is not an ancestor of this `CollectionName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,CollectionPath,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.strip_prefix(&DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?)?,
+    ///     CollectionPath::from_str("messages")?
+    /// );
+    /// assert!(collection_name
+    ///     .strip_prefix(&DocumentName::from_str(
+    ///         "projects/my-project/databases/(default)/documents/chatrooms/chatroom1"
+    ///     )?)
+    ///     .is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn strip_prefix(&self, prefix: &DocumentName) -> Result<CollectionPath, Error> {
+        if self.database_name() != prefix.database_name() {
+            return Err(Error::from(ErrorKind::DatabaseNameMismatch));
+        }
+        self.collection_path.strip_prefix(prefix.document_path())
+    }
+
+    /// Creates a new `CollectionName` from the `RootDocumentName` (or `DatabaseName`) and the segments of the collection path.
+    ///
+    /// The segments must alternate between collection ids and document ids, starting and ending with a collection id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of segments is invalid, any of the segments is invalid, or the name is too long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,DatabaseName};
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let segments: &[&str] = &["chatrooms", "chatroom1", "messages"];
+    /// assert_eq!(
+    ///     CollectionName::try_from_segments(database_name.clone(), segments)?,
+    ///     CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    ///     )?
+    /// );
+    /// assert!(CollectionName::try_from_segments(database_name.clone(), ["chatrooms", "chatroom1"]).is_err());
+    /// assert!(CollectionName::try_from_segments(database_name, ["__x__"]).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn try_from_segments<D, I, S>(root_document_name: D, segments: I) -> Result<Self, Error>
+    where
+        D: Into<RootDocumentName>,
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::try_new(
+            root_document_name,
+            CollectionPath::try_from_segments(segments)?,
+        )
+    }
+
+    /// Checks that `s` is a valid `CollectionName` without creating a `CollectionName`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that `CollectionName::from_str(s)` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(CollectionName::validate("projects/my-project/databases/my-database/documents/chatrooms").is_ok());
+    ///
+    /// let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1";
+    /// assert_eq!(
+    ///     CollectionName::validate(s).unwrap_err(),
+    ///     CollectionName::from_str(s).unwrap_err()
+    /// );
+    /// ```
+    pub fn validate(s: &str) -> Result<(), Error> {
+        limits::check_name_length(s)?;
+
+        let number_of_parts = s.split('/').count();
+        if number_of_parts < 5 + 1 || (number_of_parts - 5) % 2 != 1 {
+            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+        }
+
+        let (index, _) = s
+            .match_indices('/')
+            .nth(4)
+            .expect("s to contain at least 5 slashes");
+        RootDocumentName::validate(&s[..index])?;
+        CollectionPath::validate(&s[index + 1..]).map_err(|e| e.offset(5, index + 1))
+    }
+
+    /// Creates a new `CollectionName` with the same `CollectionPath` in the provided `database_name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new `CollectionName` exceeds the length limit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,DatabaseName};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// let database_name = DatabaseName::from_str("projects/my-project2/databases/(default)")?;
+    /// assert_eq!(
+    ///     collection_name.with_database_name(&database_name)?,
+    ///     CollectionName::from_str(
+    ///         "projects/my-project2/databases/(default)/documents/chatrooms"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn with_database_name(
+        &self,
+        database_name: &DatabaseName,
+    ) -> Result<CollectionName, Error> {
+        CollectionName::try_new(database_name.clone(), self.collection_path.clone())
+    }
+
+    /// Writes the canonical string representation of this `CollectionName` into `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// let mut buffer = String::with_capacity(collection_name.encoded_len() + 1);
+    /// collection_name.write_to(&mut buffer)?;
+    /// buffer.push('\n');
+    /// assert_eq!(buffer, format!("{}\n", collection_name));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn write_to<W>(&self, writer: &mut W) -> std::fmt::Result
+    where
+        W: std::fmt::Write,
+    {
+        writer.write_str(&self.string)
+    }
+
+    fn try_from_string_with(s: String, options: &ParseOptions) -> Result<Self, Error> {
+        // <https://firebase.google.com/docs/firestore/quotas#collections_documents_and_fields>
+        if options.length_limits {
+            limits::check_name_length(&s)?;
+        }
+
+        let number_of_parts = s.split('/').count();
+        if number_of_parts < 5 + 1 || (number_of_parts - 5) % 2 != 1 {
+            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+        }
+
+        let (index, _) = s
+            .match_indices('/')
+            .nth(4)
+            .expect("s to contain at least 5 slashes");
+        let root_document_name = RootDocumentName::from_str_with(&s[..index], options)?;
+        let collection_path = CollectionPath::from_str_with(&s[index + 1..], options)
+            .map_err(|e| e.offset(5, index + 1))?;
+        Ok(Self {
+            collection_path,
+            root_document_name,
+            string: s,
+        })
+    }
+}
+
+impl std::cmp::Ord for CollectionName {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.root_document_name
+            .cmp(&other.root_document_name)
+            .then_with(|| self.collection_path.cmp(&other.collection_path))
+    }
+}
+
+impl std::cmp::PartialEq<str> for CollectionName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl std::cmp::PartialEq<&str> for CollectionName {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl std::cmp::PartialOrd for CollectionName {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::convert::From<CollectionName> for CollectionId {
+    fn from(collection_name: CollectionName) -> Self {
+        Self::from(collection_name.collection_path)
+    }
 }
 
 impl std::convert::From<CollectionName> for CollectionPath {
@@ -377,34 +1354,65 @@ impl std::convert::TryFrom<&str> for CollectionName {
     type Error = Error;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        // <https://firebase.google.com/docs/firestore/quotas#collections_documents_and_fields>
-        if !(1..=6_144).contains(&s.len()) {
-            return Err(Error::from(ErrorKind::LengthOutOfBounds));
-        }
-
-        let parts = s.split('/').collect::<Vec<&str>>();
-        if parts.len() < 5 + 1 || (parts.len() - 5) % 2 == 0 {
-            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
-        }
-
-        Ok(Self {
-            collection_path: CollectionPath::from_str(&parts[5..].join("/"))?,
-            root_document_name: RootDocumentName::from_str(&parts[0..5].join("/"))?,
-        })
+        Self::try_from(s.to_string())
     }
 }
 
 impl std::convert::TryFrom<String> for CollectionName {
     type Error = Error;
 
-    fn try_from(s: String) -> Result<Self, Self::Error> {
-        Self::try_from(s.as_str())
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::try_from_string_with(s, &ParseOptions::strict())
+    }
+}
+
+impl std::fmt::Display for CollectionName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.string)
+    }
+}
+
+/// Creates a new `DocumentName` by joining `rhs` to the `CollectionName`.
+///
+/// # Panics
+///
+/// Panics if `rhs` is not a valid `DocumentId`. Use [`CollectionName::into_doc`] to handle the error instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{CollectionName,DocumentName};
+/// use std::str::FromStr;
+///
+/// let collection_name = CollectionName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms"
+/// )?;
+/// assert_eq!(
+///     collection_name / "chatroom1",
+///     DocumentName::from_str(
+///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+///     )?
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+impl std::ops::Div<&str> for CollectionName {
+    type Output = DocumentName;
+
+    fn div(self, rhs: &str) -> Self::Output {
+        self.into_doc(rhs)
+            .unwrap_or_else(|e| panic!("CollectionName / &str: {e}"))
     }
 }
 
-impl std::fmt::Display for CollectionName {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}/{}", self.root_document_name, self.collection_path)
+/// Creates a new `DocumentName` by joining `rhs` to the `CollectionName`.
+impl std::ops::Div<DocumentId> for CollectionName {
+    type Output = DocumentName;
+
+    fn div(self, rhs: DocumentId) -> Self::Output {
+        self.into_doc(rhs)
+            .unwrap_or_else(|e| panic!("CollectionName / DocumentId: {e}"))
     }
 }
 
@@ -436,6 +1444,19 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_ancestors() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents/chatrooms";
+        let collection_name = CollectionName::from_str(s)?;
+        assert_eq!(
+            collection_name.ancestors().collect::<Vec<AnyName>>(),
+            vec![AnyName::from(RootDocumentName::from_str(
+                "projects/my-project/databases/my-database/documents"
+            )?)]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_collection_id() -> anyhow::Result<()> {
         let s = "projects/my-project/databases/my-database/documents/chatrooms";
@@ -600,6 +1621,52 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_sibling() -> anyhow::Result<()> {
+        let collection_name = CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms",
+        )?;
+        assert_eq!(
+            collection_name.sibling("users")?,
+            CollectionName::from_str("projects/my-project/databases/my-database/documents/users")?
+        );
+        assert!(collection_name.sibling("__users__").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_prefix() -> anyhow::Result<()> {
+        let collection_name = CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col",
+        )?;
+        for (prefix, expected) in [
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+                Some("messages/message1/col"),
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
+                Some("col"),
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom2",
+                None,
+            ),
+            (
+                "projects/my-project2/databases/my-database/documents/chatrooms/chatroom1",
+                None,
+            ),
+        ] {
+            assert_eq!(
+                collection_name
+                    .strip_prefix(&DocumentName::from_str(prefix)?)
+                    .ok(),
+                expected.map(CollectionPath::from_str).transpose()?
+            );
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_parent() -> anyhow::Result<()> {
         let s = "projects/my-project/databases/my-database/documents/chatrooms";
@@ -616,4 +1683,472 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_with_database_name() -> anyhow::Result<()> {
+        let collection_name = CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages",
+        )?;
+        let database_name = DatabaseName::from_str("projects/my-project/databases/(default)")?;
+        let collection_name = collection_name.with_database_name(&database_name)?;
+        assert_eq!(
+            collection_name.to_string(),
+            "projects/my-project/databases/(default)/documents/chatrooms/chatroom1/messages"
+        );
+        assert_eq!(collection_name.database_name(), &database_name);
+
+        // `{root}/{collection_path}` is exactly `MAX_DOCUMENT_NAME_BYTES` bytes.
+        let mut s = "projects/my-project/databases/my-database/documents".to_string();
+        while s.len() < limits::MAX_DOCUMENT_NAME_BYTES - "/c".len() {
+            let document_id_len =
+                (limits::MAX_DOCUMENT_NAME_BYTES - s.len() - "/c/".len() - "/c".len())
+                    .min(limits::MAX_ID_BYTES);
+            s.push_str(&format!("/c/{}", "x".repeat(document_id_len)));
+        }
+        s.push_str("/c");
+        let collection_name = CollectionName::from_str(&s)?;
+        assert_eq!(
+            collection_name.encoded_len(),
+            limits::MAX_DOCUMENT_NAME_BYTES
+        );
+        assert!(collection_name
+            .with_database_name(&DatabaseName::from_str(
+                "projects/my-project/databases/my-database"
+            )?)
+            .is_ok());
+        assert_eq!(
+            collection_name
+                .with_database_name(&DatabaseName::from_str(
+                    "projects/my-project2/databases/my-database"
+                )?)
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::LengthOutOfBounds
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_eq_ignore_database() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages";
+        for (other, expected) in [
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages",
+                true,
+            ),
+            (
+                "projects/my-project/databases/(default)/documents/chatrooms/chatroom1/messages",
+                true,
+            ),
+            (
+                "projects/my-project2/databases/my-database/documents/chatrooms/chatroom1/messages",
+                true,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom2/messages",
+                false,
+            ),
+            (
+                "projects/my-project2/databases/(default)/documents/chatrooms/chatroom2/messages",
+                false,
+            ),
+        ] {
+            assert_eq!(
+                CollectionName::from_str(s)?.eq_ignore_database(&CollectionName::from_str(other)?),
+                expected
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_same_database_and_same_project() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents/chatrooms";
+        for (other, same_database, same_project) in [
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms",
+                true,
+                true,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/messages",
+                true,
+                true,
+            ),
+            (
+                "projects/my-project/databases/(default)/documents/chatrooms",
+                false,
+                true,
+            ),
+            (
+                "projects/my-project2/databases/my-database/documents/chatrooms",
+                false,
+                false,
+            ),
+        ] {
+            let a = CollectionName::from_str(s)?;
+            let b = CollectionName::from_str(other)?;
+            assert_eq!(a.same_database(&b), same_database);
+            assert_eq!(a.same_project(&b), same_project);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_in_collection_group() -> anyhow::Result<()> {
+        let collection_name = CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages",
+        )?;
+        for (collection_id, expected) in [
+            ("chatrooms", true),
+            ("messages", true),
+            ("chatroom1", false),
+            ("users", false),
+        ] {
+            assert_eq!(
+                collection_name.is_in_collection_group(&CollectionId::from_str(collection_id)?),
+                expected
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_ids_and_document_ids() -> anyhow::Result<()> {
+        let collection_name = CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages",
+        )?;
+        assert_eq!(
+            collection_name
+                .collection_ids()
+                .map(CollectionId::as_ref)
+                .collect::<Vec<&str>>(),
+            vec!["chatrooms", "messages"]
+        );
+        assert_eq!(
+            collection_name
+                .document_ids()
+                .map(DocumentId::as_ref)
+                .collect::<Vec<&str>>(),
+            vec!["chatroom1"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_id_at_and_document_id_at() -> anyhow::Result<()> {
+        let collection_name = CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages",
+        )?;
+        for (index, expected) in [(0, Some("chatrooms")), (1, Some("messages")), (2, None)] {
+            assert_eq!(
+                collection_name
+                    .collection_id_at(index)
+                    .map(CollectionId::as_ref),
+                expected
+            );
+        }
+        for (index, expected) in [(0, Some("chatroom1")), (1, None)] {
+            assert_eq!(
+                collection_name
+                    .document_id_at(index)
+                    .map(DocumentId::as_ref),
+                expected
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_div() -> anyhow::Result<()> {
+        let collection_name = CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms",
+        )?;
+        assert_eq!(
+            (collection_name.clone() / "chatroom1").to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+        );
+        assert_eq!(
+            (collection_name / DocumentId::from_str("chatroom1")?).to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_impl_div_panics() {
+        let _ = CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms",
+        )
+        .unwrap()
+            / "chatroom1/messages";
+    }
+
+    #[test]
+    fn test_impl_ord() -> anyhow::Result<()> {
+        let expected = vec![
+            "projects/my-project/databases/my-database/documents/a",
+            "projects/my-project/databases/my-database/documents/a/b/c",
+            "projects/my-project/databases/my-database/documents/a/b/c/d/e",
+            "projects/my-project/databases/my-database/documents/a/b2/c",
+            "projects/my-project/databases/my-database/documents/a2",
+            "projects/my-project/databases/my-database/documents/z",
+        ];
+        let mut sorted = expected
+            .iter()
+            .rev()
+            .map(|s| CollectionName::from_str(s))
+            .collect::<Result<Vec<CollectionName>, Error>>()?;
+        sorted.sort();
+        assert_eq!(
+            sorted
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_document_name_range() -> anyhow::Result<()> {
+        let range =
+            CollectionName::from_str("projects/my-project/databases/my-database/documents/c")?
+                .document_name_range();
+        for (s, expected) in [
+            (
+                "projects/my-project/databases/my-database/documents/c/d",
+                true,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c/d/c/d",
+                true,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c-/d",
+                false,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c0/d",
+                false,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c1/d",
+                false,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/b/d",
+                false,
+            ),
+        ] {
+            assert_eq!(range.contains(&s.to_string()), expected, "{s}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefix_str() -> anyhow::Result<()> {
+        let collection_name = CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms",
+        )?;
+        assert_eq!(
+            collection_name.prefix_str(),
+            format!("{}/", collection_name)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_as_str() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents/chatrooms";
+        let collection_name = CollectionName::from_str(s)?;
+        assert_eq!(collection_name.as_str(), s);
+        assert_eq!(collection_name.as_str(), collection_name.to_string());
+
+        let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+        let collection_name =
+            CollectionName::new(database_name, CollectionPath::from_str("chatrooms")?);
+        assert_eq!(collection_name.as_str(), s);
+
+        let child = collection_name.doc("message1")?;
+        assert_eq!(child.as_str(), child.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_partial_eq_str() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents/chatrooms";
+        let collection_name = CollectionName::from_str(s)?;
+        assert_eq!(collection_name, s);
+        assert_eq!(&collection_name, s);
+        assert!(collection_name == *s);
+        assert_ne!(
+            collection_name,
+            "projects/my-project/databases/my-database/documents/chatrooms2"
+        );
+        assert_ne!(collection_name, "chatrooms");
+        Ok(())
+    }
+
+    #[test]
+    fn test_encoded_len_and_write_to() -> anyhow::Result<()> {
+        for s in [
+            "projects/my-project/databases/my-database/documents/chatrooms",
+            "projects/my-project/databases/my-database/documents/chatrooms/x/y",
+        ] {
+            let collection_name = CollectionName::from_str(s)?;
+            assert_eq!(collection_name.encoded_len(), s.len());
+            let mut buffer = String::from(">");
+            collection_name.write_to(&mut buffer)?;
+            assert_eq!(buffer, format!(">{s}"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_string() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents/chatrooms";
+        assert_eq!(CollectionName::from_str(s)?.into_string(), s);
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> anyhow::Result<()> {
+        for s in [
+            "projects/my-project/databases/my-database/documents/chatrooms".to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1".to_string(),
+            "projects/my-project/databases/my-database/documents".to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/__x__"
+                .to_string(),
+            "projects/my-project/databases/db/documents/chatrooms".to_string(),
+            format!(
+                "projects/my-project/databases/my-database/documents/{}",
+                "x".repeat(6_144)
+            ),
+        ] {
+            assert_eq!(
+                CollectionName::validate(&s).err(),
+                CollectionName::from_str(&s).err()
+            );
+            assert_eq!(
+                CollectionName::is_valid(&s),
+                CollectionName::from_str(&s).is_ok()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_parts() -> anyhow::Result<()> {
+        assert_eq!(
+            CollectionName::from_parts("my-project", "(default)", "chatrooms")?.to_string(),
+            "projects/my-project/databases/(default)/documents/chatrooms"
+        );
+        assert!(matches!(
+            CollectionName::from_parts("My-Project", "(default)", "chatrooms")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::ProjectIdConversion(_)
+        ));
+        assert!(matches!(
+            CollectionName::from_parts("my-project", "db", "chatrooms")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DatabaseIdConversion(_)
+        ));
+        assert!(matches!(
+            CollectionName::from_parts("my-project", "(default)", "chatrooms/x")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::CollectionPathConversion(_)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_docs() -> anyhow::Result<()> {
+        let collection_name = CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages",
+        )?;
+        let ids = (0..100)
+            .map(|i| format!("message{}", i))
+            .collect::<Vec<String>>();
+        let docs = collection_name
+            .docs(ids.iter().map(String::as_str))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(docs.len(), ids.len());
+        for (doc, id) in docs.iter().zip(ids.iter()) {
+            assert_eq!(doc, &collection_name.doc(id.as_str())?);
+            assert_eq!(doc.to_string(), format!("{}/{}", collection_name, id));
+        }
+        assert_eq!(collection_name.docs(Vec::<String>::new()).count(), 0);
+        assert!(matches!(
+            collection_name
+                .docs(["__x__"])
+                .next()
+                .expect("one item")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DocumentIdConversion(_)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_docs_max_length() -> anyhow::Result<()> {
+        let root = "projects/my-project/databases/my-database/documents";
+        let collection_id = "x".repeat(1_500);
+        let collection_name = CollectionName::from_str(&format!(
+            "{}/{}/d/{}/d/{}/d/{}",
+            root,
+            collection_id,
+            collection_id,
+            collection_id,
+            "x".repeat(1_400)
+        ))?;
+        let filler = limits::MAX_DOCUMENT_NAME_BYTES - collection_name.as_str().len() - 1;
+        for (id, ok) in [("x".repeat(filler), true), ("x".repeat(filler + 1), false)] {
+            assert_eq!(
+                collection_name
+                    .docs([id.as_str()])
+                    .next()
+                    .expect("one item")
+                    .is_ok(),
+                collection_name.doc(id.as_str()).is_ok()
+            );
+            assert_eq!(collection_name.doc(id.as_str()).is_ok(), ok);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_parts() -> anyhow::Result<()> {
+        let collection_name = CollectionName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages",
+        )?;
+        let (root_document_name, collection_path) = collection_name.clone().into_parts();
+        assert_eq!(
+            root_document_name.collection(collection_path)?,
+            collection_name
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_segments() -> anyhow::Result<()> {
+        let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+        assert_eq!(
+            CollectionName::try_from_segments(database_name.clone(), ["chatrooms"])?,
+            database_name.collection("chatrooms")?
+        );
+        for segments in [
+            vec![],
+            vec!["chatrooms", "chatroom1"],
+            vec!["chatrooms", "..", "messages"],
+        ] {
+            assert!(CollectionName::try_from_segments(database_name.clone(), segments).is_err());
+        }
+        Ok(())
+    }
 }
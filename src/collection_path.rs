@@ -1,6 +1,9 @@
 use std::str::FromStr;
 
-use crate::{error::ErrorKind, CollectionId, DocumentId, DocumentPath, Error};
+use crate::{
+    error::ErrorKind, limits, stable_hash::StableHasher, AnyPath, CollectionId, DocumentId,
+    DocumentPath, Error, ParseOptions, Segment,
+};
 
 /// A collection path.
 ///
@@ -9,6 +12,10 @@ use crate::{error::ErrorKind, CollectionId, DocumentId, DocumentPath, Error};
 /// - `{collection_id}`
 /// - `{document_path}/{collection_id}`
 ///
+/// # Ordering
+///
+/// `CollectionPath`s are ordered segment by segment (a path comes before its descendants), which matches the ordering of references (`__name__`) in Firestore.
+///
 /// # Examples
 ///
 /// ```rust
@@ -24,59 +31,683 @@ use crate::{error::ErrorKind, CollectionId, DocumentId, DocumentPath, Error};
 /// #     Ok(())
 /// # }
 /// ```
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct CollectionPath {
     document_path: Option<DocumentPath>,
     collection_id: CollectionId,
 }
 
-impl CollectionPath {
-    /// Create a new `CollectionPath`.
+impl CollectionPath {
+    /// Create a new `CollectionPath`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_id = CollectionId::from_str("chatrooms")?;
+    /// let collection_path = CollectionPath::new(None, collection_id);
+    /// assert_eq!(collection_path.to_string(), "chatrooms");
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// let collection_id = CollectionId::from_str("messages")?;
+    /// let collection_path = CollectionPath::new(Some(document_path), collection_id);
+    /// assert_eq!(collection_path.to_string(), "chatrooms/chatroom1/messages");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub const fn new(parent: Option<DocumentPath>, collection_id: CollectionId) -> Self {
+        Self {
+            document_path: parent,
+            collection_id,
+        }
+    }
+
+    /// Creates a new `AnyPath` by appending the relative path `segments` to this `CollectionPath`.
+    ///
+    /// The `segments` must start with a document id and alternate between document ids and collection ids.
+    /// The result is a `DocumentPath` if the number of `segments` is odd, otherwise a `CollectionPath`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the `segments` is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyPath,CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// assert_eq!(
+    ///     collection_path.clone().append(["chatroom1", "messages", "message1"])?,
+    ///     AnyPath::from(DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?)
+    /// );
+    /// assert_eq!(
+    ///     collection_path.clone().append(["chatroom1", "messages"])?,
+    ///     AnyPath::from(CollectionPath::from_str("chatrooms/chatroom1/messages")?)
+    /// );
+    /// assert!(collection_path.append(["chatroom1", "messages/message1"]).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn append<I, S>(self, segments: I) -> Result<AnyPath, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut any_path = AnyPath::from(self);
+        for (index, segment) in segments.into_iter().enumerate() {
+            if index % 2 == 1 {
+                any_path.push_collection(segment.as_ref())?;
+            } else {
+                any_path.push_doc(segment.as_ref())?;
+            }
+        }
+        Ok(any_path)
+    }
+
+    /// Returns an iterator over the ancestors of this `CollectionPath`.
+    ///
+    /// The iterator yields the parent `DocumentPath`, the parent `CollectionPath`, ... and the root `CollectionPath` in that order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyPath,CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// assert_eq!(collection_path.ancestors().count(), 0);
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert_eq!(
+    ///     collection_path.ancestors().collect::<Vec<AnyPath>>(),
+    ///     vec![
+    ///         AnyPath::from(DocumentPath::from_str("chatrooms/chatroom1")?),
+    ///         AnyPath::from(CollectionPath::from_str("chatrooms")?),
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn ancestors(&self) -> impl Iterator<Item = AnyPath> {
+        std::iter::successors(self.parent().cloned().map(AnyPath::from), AnyPath::parent)
+    }
+
+    /// Returns the `CollectionId` of this `CollectionPath`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// assert_eq!(collection_path.collection_id(), &CollectionId::from_str("chatrooms")?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn collection_id(&self) -> &CollectionId {
+        &self.collection_id
+    }
+
+    /// Returns the `CollectionId` at `index` (0-based, from the root) of this `CollectionPath`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,CollectionPath};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert_eq!(collection_path.collection_id_at(0), Some(&CollectionId::from_str("chatrooms")?));
+    /// assert_eq!(collection_path.collection_id_at(1), Some(&CollectionId::from_str("messages")?));
+    /// assert_eq!(collection_path.collection_id_at(2), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn collection_id_at(&self, index: usize) -> Option<&CollectionId> {
+        self.collection_ids().nth(index)
+    }
+
+    /// Returns an iterator over the `CollectionId`s of this `CollectionPath` from the root to the leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,CollectionPath};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert_eq!(
+    ///     collection_path.collection_ids().collect::<Vec<&CollectionId>>(),
+    ///     vec![
+    ///         &CollectionId::from_str("chatrooms")?,
+    ///         &CollectionId::from_str("messages")?
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn collection_ids(&self) -> impl Iterator<Item = &CollectionId> {
+        self.segments().filter_map(|segment| match segment {
+            Segment::Collection(collection_id) => Some(collection_id),
+            Segment::Document(_) => None,
+        })
+    }
+
+    /// Returns the depth (the number of collections) of this `CollectionPath`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionPath;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// assert_eq!(collection_path.depth(), 1);
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert_eq!(collection_path.depth(), 2);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn depth(&self) -> usize {
+        let mut depth = 1;
+        let mut collection_path = self;
+        while let Some(document_path) = collection_path.parent() {
+            depth += 1;
+            collection_path = document_path.parent();
+        }
+        depth
+    }
+
+    /// Create a new `DocumentPath` from this `CollectionPath` and `document_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// assert_eq!(
+    ///     collection_path.doc("chatroom1")?,
+    ///     DocumentPath::from_str("chatrooms/chatroom1")?
+    /// );
+    /// assert_eq!(
+    ///     collection_path.doc("chatroom2")?,
+    ///     DocumentPath::from_str("chatrooms/chatroom2")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn doc<E, T>(&self, document_id: T) -> Result<DocumentPath, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DocumentId, Error = E>,
+    {
+        self.clone().into_doc(document_id)
+    }
+
+    /// Creates a new `DocumentPath` from this `CollectionPath` and a random `DocumentId` (see `DocumentId::random`).
+    ///
+    /// This is like `doc()` without arguments of the client SDKs. The generated id is available from `document_id()` of the result.
+    ///
+    /// This method requires the `random` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new `DocumentPath` is too long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionPath;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// let document = collection_path.doc_with_random_id()?;
+    /// assert_eq!(document.parent(), &collection_path);
+    /// assert_eq!(document.document_id().as_ref().len(), 20);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "random")]
+    pub fn doc_with_random_id(&self) -> Result<DocumentPath, Error> {
+        self.doc(DocumentId::random())
+    }
+
+    /// Creates new `DocumentPath`s from this `CollectionPath` and `document_ids`.
+    ///
+    /// This is the same as calling `doc` for each id.
+    ///
+    /// # Errors
+    ///
+    /// Each item is an error if the id cannot be converted into a `DocumentId`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// let document_paths = collection_path
+    ///     .docs(["chatroom1", "chatroom2"])
+    ///     .collect::<Result<Vec<DocumentPath>, _>>()?;
+    /// assert_eq!(
+    ///     document_paths,
+    ///     [
+    ///         DocumentPath::from_str("chatrooms/chatroom1")?,
+    ///         DocumentPath::from_str("chatrooms/chatroom2")?
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn docs<'a, E, I, T>(
+        &'a self,
+        document_ids: I,
+    ) -> impl Iterator<Item = Result<DocumentPath, Error>> + 'a
+    where
+        E: std::fmt::Display,
+        I: IntoIterator<Item = T>,
+        I::IntoIter: 'a,
+        T: TryInto<DocumentId, Error = E>,
+    {
+        document_ids
+            .into_iter()
+            .map(move |document_id| self.doc(document_id))
+    }
+
+    /// Returns the `DocumentId` at `index` (0-based, from the root) of this `CollectionPath`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionPath,DocumentId};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert_eq!(collection_path.document_id_at(0), Some(&DocumentId::from_str("chatroom1")?));
+    /// assert_eq!(collection_path.document_id_at(1), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn document_id_at(&self, index: usize) -> Option<&DocumentId> {
+        self.document_ids().nth(index)
+    }
+
+    /// Returns an iterator over the `DocumentId`s of this `CollectionPath` from the root to the leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionPath,DocumentId};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert_eq!(
+    ///     collection_path.document_ids().collect::<Vec<&DocumentId>>(),
+    ///     vec![
+    ///         &DocumentId::from_str("chatroom1")?
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn document_ids(&self) -> impl Iterator<Item = &DocumentId> {
+        self.segments().filter_map(|segment| match segment {
+            Segment::Collection(_) => None,
+            Segment::Document(document_id) => Some(document_id),
+        })
+    }
+
+    /// Returns the length in bytes of the canonical string representation of this `CollectionPath`.
+    ///
+    /// This is the same as `to_string().len()` but does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionPath;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert_eq!(collection_path.encoded_len(), collection_path.to_string().len());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn encoded_len(&self) -> usize {
+        self.segments()
+            .map(|segment| segment.as_ref().len() + 1)
+            .sum::<usize>()
+            - 1
+    }
+
+    /// Creates a new `CollectionPath` from `s` with `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid `CollectionPath` under `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionPath,ParseOptions};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     CollectionPath::from_str_with("chatrooms/chatroom1/messages", &ParseOptions::strict())?,
+    ///     CollectionPath::from_str("chatrooms/chatroom1/messages")?
+    /// );
+    /// assert!(CollectionPath::from_str("__chatrooms__").is_err());
+    /// assert!(CollectionPath::from_str_with("__chatrooms__", &ParseOptions::lenient()).is_ok());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        options.parse_normalized(s, |s| match AnyPath::parse(s, options)? {
+            AnyPath::Collection(collection_path) => Ok(collection_path),
+            AnyPath::Document(_) => Err(Error::from(ErrorKind::InvalidNumberOfPathComponents)),
+        })
+    }
+
+    /// Create a new `DocumentPath` by consuming the `CollectionPath` with the provided `document_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// assert_eq!(
+    ///     collection_path.clone().into_doc("chatroom1")?,
+    ///     DocumentPath::from_str("chatrooms/chatroom1")?
+    /// );
+    /// assert_eq!(
+    ///     collection_path.into_doc("chatroom2")?,
+    ///     DocumentPath::from_str("chatrooms/chatroom2")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_doc<E, T>(self, document_id: T) -> Result<DocumentPath, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DocumentId, Error = E>,
+    {
+        let document_id = document_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::DocumentIdConversion(e.to_string())))?;
+        let document_path = DocumentPath::new(self, document_id);
+        Ok(document_path)
+    }
+
+    /// Consumes the `CollectionPath`, returning the parent `DocumentPath`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// assert_eq!(collection_path.into_parent(), None);
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert_eq!(collection_path.clone().into_parent(), Some(DocumentPath::from_str("chatrooms/chatroom1")?));
+    /// assert_eq!(collection_path.into_parent(), Some(DocumentPath::from_str("chatrooms/chatroom1")?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_parent(mut self) -> Option<DocumentPath> {
+        self.document_path.take()
+    }
+
+    /// Consumes the `CollectionPath`, returning the parent `DocumentPath` and the `CollectionId`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// assert_eq!(
+    ///     collection_path.into_parts(),
+    ///     (None, CollectionId::from_str("chatrooms")?)
+    /// );
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert_eq!(
+    ///     collection_path.into_parts(),
+    ///     (
+    ///         Some(DocumentPath::from_str("chatrooms/chatroom1")?),
+    ///         CollectionId::from_str("messages")?
+    ///     )
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_parts(mut self) -> (Option<DocumentPath>, CollectionId) {
+        (
+            self.document_path.take(),
+            std::mem::replace(&mut self.collection_id, CollectionId::placeholder()),
+        )
+    }
+
+    /// Converts this `CollectionPath` into its canonical string representation.
+    ///
+    /// The string is written into a buffer of exactly `encoded_len()` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionPath;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert_eq!(collection_path.into_string(), "chatrooms/chatroom1/messages");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_string(self) -> String {
+        let mut s = String::with_capacity(self.encoded_len());
+        self.write_to(&mut s)
+            .expect("writing to a String to succeed");
+        s
+    }
+
+    /// Returns `true` if this `CollectionPath` belongs to the collection group `collection_id`.
+    ///
+    /// That is, the `CollectionId` of this `CollectionPath` or any of its ancestors is equal to `collection_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,CollectionPath};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert!(collection_path.is_in_collection_group(&CollectionId::from_str("chatrooms")?));
+    /// assert!(collection_path.is_in_collection_group(&CollectionId::from_str("messages")?));
+    /// assert!(!collection_path.is_in_collection_group(&CollectionId::from_str("users")?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_in_collection_group(&self, collection_id: &CollectionId) -> bool {
+        self.segments()
+            .any(|segment| segment == Segment::Collection(collection_id))
+    }
+
+    /// Returns `true` if `s` is a valid `CollectionPath`.
+    ///
+    /// This is the same as `CollectionPath::validate(s).is_ok()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::CollectionPath;
+    ///
+    /// assert!(CollectionPath::is_valid("chatrooms/chatroom1/messages"));
+    /// assert!(!CollectionPath::is_valid("chatrooms/chatroom1"));
+    /// ```
+    pub fn is_valid(s: &str) -> bool {
+        Self::validate(s).is_ok()
+    }
+
+    /// Returns the parent `DocumentPath` of this `CollectionPath`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// assert_eq!(collection_path.parent(), None);
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert_eq!(collection_path.parent(), Some(&DocumentPath::from_str("chatrooms/chatroom1")?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parent(&self) -> Option<&DocumentPath> {
+        self.document_path.as_ref()
+    }
+
+    /// Returns an iterator over the segments of this `CollectionPath` from the root.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionPath,Segment};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert_eq!(
+    ///     collection_path
+    ///         .segments()
+    ///         .map(|segment| segment.to_string())
+    ///         .collect::<Vec<String>>(),
+    ///     vec!["chatrooms", "chatroom1", "messages"]
+    /// );
+    /// assert!(matches!(
+    ///     collection_path.segments().next(),
+    ///     Some(Segment::Collection(collection_id)) if collection_id.as_ref() == "chatrooms"
+    /// ));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn segments(&self) -> impl Iterator<Item = Segment<'_>> {
+        let mut segments = vec![];
+        let mut collection_path = self;
+        loop {
+            segments.push(Segment::Collection(&collection_path.collection_id));
+            match collection_path.document_path.as_ref() {
+                Some(document_path) => {
+                    segments.push(Segment::Document(document_path.document_id()));
+                    collection_path = document_path.parent();
+                }
+                None => break,
+            }
+        }
+        segments.into_iter().rev()
+    }
+
+    /// Creates a new `CollectionPath` with the same parent as this `CollectionPath` and the provided `collection_id`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionId,CollectionPath,DocumentPath};
+    /// use firestore_path::{CollectionId,CollectionPath};
     /// use std::str::FromStr;
     ///
-    /// let collection_id = CollectionId::from_str("chatrooms")?;
-    /// let collection_path = CollectionPath::new(None, collection_id);
-    /// assert_eq!(collection_path.to_string(), "chatrooms");
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// assert_eq!(
+    ///     collection_path.sibling("users")?,
+    ///     CollectionPath::from_str("users")?
+    /// );
     ///
-    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
-    /// let collection_id = CollectionId::from_str("messages")?;
-    /// let collection_path = CollectionPath::new(Some(document_path), collection_id);
-    /// assert_eq!(collection_path.to_string(), "chatrooms/chatroom1/messages");
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+    /// assert_eq!(
+    ///     collection_path.sibling(CollectionId::from_str("members")?)?,
+    ///     CollectionPath::from_str("chatrooms/chatroom1/members")?
+    /// );
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn new(parent: Option<DocumentPath>, collection_id: CollectionId) -> Self {
-        Self {
-            document_path: parent,
+    pub fn sibling<E, T>(&self, collection_id: T) -> Result<CollectionPath, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<CollectionId, Error = E>,
+    {
+        let collection_id = collection_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::CollectionIdConversion(e.to_string())))?;
+        Ok(CollectionPath::new(
+            self.document_path.clone(),
             collection_id,
-        }
+        ))
     }
 
-    /// Returns the `CollectionId` of this `CollectionPath`.
+    /// Returns a 64-bit hash of the canonical string representation of this `CollectionPath`.
+    ///
+    /// Unlike `std::hash::Hash`, the value is stable across versions, platforms and processes.
+    /// It is the 64-bit FNV-1a hash of the UTF-8 bytes of `to_string()`, so it can be persisted or computed in other languages.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionId,CollectionPath,DocumentPath};
+    /// use firestore_path::CollectionPath;
     /// use std::str::FromStr;
     ///
     /// let collection_path = CollectionPath::from_str("chatrooms")?;
-    /// assert_eq!(collection_path.collection_id(), &CollectionId::from_str("chatrooms")?);
+    /// assert_eq!(collection_path.stable_hash(), 0x08c0a48fb86dfb8d);
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn collection_id(&self) -> &CollectionId {
-        &self.collection_id
+    pub fn stable_hash(&self) -> u64 {
+        StableHasher::hash(|hasher| self.write_to(hasher))
     }
 
-    /// Create a new `DocumentPath` from this `CollectionPath` and `document_id`.
+    /// Returns the `CollectionPath` relative to the ancestor `prefix`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `prefix` is not an ancestor of this `CollectionPath`.
     ///
     /// # Examples
     ///
@@ -85,104 +716,131 @@ impl CollectionPath {
     /// use firestore_path::{CollectionPath,DocumentPath};
     /// use std::str::FromStr;
     ///
-    /// let collection_path = CollectionPath::from_str("chatrooms")?;
-    /// assert_eq!(
-    ///     collection_path.doc("chatroom1")?,
-    ///     DocumentPath::from_str("chatrooms/chatroom1")?
-    /// );
+    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages/message1/col")?;
     /// assert_eq!(
-    ///     collection_path.doc("chatroom2")?,
-    ///     DocumentPath::from_str("chatrooms/chatroom2")?
+    ///     collection_path.strip_prefix(&DocumentPath::from_str("chatrooms/chatroom1")?)?,
+    ///     CollectionPath::from_str("messages/message1/col")?
     /// );
+    /// assert!(collection_path.strip_prefix(&DocumentPath::from_str("chatrooms/chatroom2")?).is_err());
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn doc<E, T>(&self, document_id: T) -> Result<DocumentPath, Error>
-    where
-        E: std::fmt::Display,
-        T: TryInto<DocumentId, Error = E>,
-    {
-        self.clone().into_doc(document_id)
+    pub fn strip_prefix(&self, prefix: &DocumentPath) -> Result<CollectionPath, Error> {
+        let segments = self.segments().collect::<Vec<Segment>>();
+        let prefix = prefix.segments().collect::<Vec<Segment>>();
+        if prefix.len() >= segments.len() || !segments.starts_with(&prefix) {
+            return Err(Error::from(ErrorKind::PrefixMismatch));
+        }
+        CollectionPath::try_from_segments(&segments[prefix.len()..])
     }
 
-    /// Create a new `DocumentPath` by consuming the `CollectionPath` with the provided `document_id`.
+    /// Creates a new `CollectionPath` from the segments.
+    ///
+    /// The segments must alternate between collection ids and document ids, starting and ending with a collection id.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionPath,DocumentPath};
+    /// use firestore_path::CollectionPath;
     /// use std::str::FromStr;
     ///
-    /// let collection_path = CollectionPath::from_str("chatrooms")?;
     /// assert_eq!(
-    ///     collection_path.clone().into_doc("chatroom1")?,
-    ///     DocumentPath::from_str("chatrooms/chatroom1")?
+    ///     CollectionPath::try_from_segments(["chatrooms", "chatroom1", "messages"])?,
+    ///     CollectionPath::from_str("chatrooms/chatroom1/messages")?
     /// );
     /// assert_eq!(
-    ///     collection_path.into_doc("chatroom2")?,
-    ///     DocumentPath::from_str("chatrooms/chatroom2")?
+    ///     CollectionPath::try_from_segments(vec!["chatrooms".to_string()])?,
+    ///     CollectionPath::from_str("chatrooms")?
     /// );
+    /// assert!(CollectionPath::try_from_segments(["chatrooms", "chatroom1"]).is_err());
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn into_doc<E, T>(self, document_id: T) -> Result<DocumentPath, Error>
+    pub fn try_from_segments<I, S>(segments: I) -> Result<Self, Error>
     where
-        E: std::fmt::Display,
-        T: TryInto<DocumentId, Error = E>,
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
     {
-        let document_id = document_id
-            .try_into()
-            .map_err(|e| Error::from(ErrorKind::DocumentIdConversion(e.to_string())))?;
-        let document_path = DocumentPath::new(self, document_id);
-        Ok(document_path)
+        let segments = segments.into_iter().collect::<Vec<S>>();
+        let Some((collection_id, document_path)) = segments.split_last() else {
+            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+        };
+        if document_path.len() % 2 != 0 {
+            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+        }
+        limits::check_depth(document_path.len() / 2 + 1)?;
+        Ok(Self {
+            document_path: if document_path.is_empty() {
+                None
+            } else {
+                Some(DocumentPath::try_from_segments(document_path)?)
+            },
+            collection_id: CollectionId::from_str(collection_id.as_ref())?,
+        })
     }
 
-    /// Consumes the `CollectionPath`, returning the parent `DocumentPath`.
+    /// Checks that `s` is a valid `CollectionPath` without creating a `CollectionPath`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that `CollectionPath::from_str(s)` returns.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionPath,DocumentPath};
+    /// use firestore_path::CollectionPath;
     /// use std::str::FromStr;
     ///
-    /// let collection_path = CollectionPath::from_str("chatrooms")?;
-    /// assert_eq!(collection_path.into_parent(), None);
-    ///
-    /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
-    /// assert_eq!(collection_path.clone().into_parent(), Some(DocumentPath::from_str("chatrooms/chatroom1")?));
-    /// assert_eq!(collection_path.into_parent(), Some(DocumentPath::from_str("chatrooms/chatroom1")?));
-    /// #     Ok(())
-    /// # }
+    /// assert!(CollectionPath::validate("chatrooms/chatroom1/messages").is_ok());
+    /// assert_eq!(
+    ///     CollectionPath::validate("chatrooms/chatroom1").unwrap_err(),
+    ///     CollectionPath::from_str("chatrooms/chatroom1").unwrap_err()
+    /// );
     /// ```
-    pub fn into_parent(self) -> Option<DocumentPath> {
-        self.document_path
+    pub fn validate(s: &str) -> Result<(), Error> {
+        let count = AnyPath::validate(s)?;
+        if count % 2 == 1 {
+            Ok(())
+        } else {
+            Err(Error::from(ErrorKind::InvalidNumberOfPathComponents))
+        }
     }
 
-    /// Returns the parent `DocumentPath` of this `CollectionPath`.
+    /// Writes the canonical string representation of this `CollectionPath` into `writer`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionPath,DocumentPath};
+    /// use firestore_path::CollectionPath;
     /// use std::str::FromStr;
     ///
-    /// let collection_path = CollectionPath::from_str("chatrooms")?;
-    /// assert_eq!(collection_path.parent(), None);
-    ///
     /// let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
-    /// assert_eq!(collection_path.parent(), Some(&DocumentPath::from_str("chatrooms/chatroom1")?));
+    /// let mut buffer = String::with_capacity(collection_path.encoded_len() + 1);
+    /// collection_path.write_to(&mut buffer)?;
+    /// buffer.push('\n');
+    /// assert_eq!(buffer, format!("{}\n", collection_path));
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn parent(&self) -> Option<&DocumentPath> {
-        self.document_path.as_ref()
+    pub fn write_to<W>(&self, writer: &mut W) -> std::fmt::Result
+    where
+        W: std::fmt::Write,
+    {
+        write!(writer, "{self}")
+    }
+}
+
+impl std::cmp::Ord for CollectionPath {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.segments().cmp(other.segments())
     }
+}
 
-    pub(crate) fn into_tuple(self) -> (Option<DocumentPath>, CollectionId) {
-        (self.document_path, self.collection_id)
+impl std::cmp::PartialOrd for CollectionPath {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -194,13 +852,21 @@ impl std::convert::From<CollectionId> for CollectionPath {
 
 impl std::convert::From<CollectionPath> for CollectionId {
     fn from(collection_path: CollectionPath) -> Self {
-        collection_path.collection_id
+        collection_path.into_parts().1
     }
 }
 
 impl std::convert::From<CollectionPath> for Option<DocumentPath> {
     fn from(collection_path: CollectionPath) -> Self {
-        collection_path.document_path
+        collection_path.into_parent()
+    }
+}
+
+impl std::convert::TryFrom<&[&str]> for CollectionPath {
+    type Error = Error;
+
+    fn try_from(segments: &[&str]) -> Result<Self, Self::Error> {
+        Self::try_from_segments(segments)
     }
 }
 
@@ -208,7 +874,7 @@ impl std::convert::TryFrom<&str> for CollectionPath {
     type Error = Error;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        Self::try_from(s.to_string())
+        Self::from_str_with(s, &ParseOptions::strict())
     }
 }
 
@@ -216,24 +882,68 @@ impl std::convert::TryFrom<String> for CollectionPath {
     type Error = Error;
 
     fn try_from(s: String) -> Result<Self, Self::Error> {
-        Ok(match s.rsplit_once('/') {
-            Some((document_path, collection_id)) => Self {
-                document_path: Some(DocumentPath::from_str(document_path)?),
-                collection_id: CollectionId::from_str(collection_id)?,
-            },
-            None => Self {
-                document_path: None,
-                collection_id: CollectionId::try_from(s)?,
-            },
-        })
+        Self::try_from(s.as_str())
     }
 }
 
 impl std::fmt::Display for CollectionPath {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.document_path.as_ref() {
-            Some(document_path) => write!(f, "{}/{}", document_path, self.collection_id),
-            None => self.collection_id.fmt(f),
+        for (index, segment) in self.segments().enumerate() {
+            if index > 0 {
+                f.write_str("/")?;
+            }
+            f.write_str(segment.as_ref())?;
+        }
+        Ok(())
+    }
+}
+
+/// Creates a new `DocumentPath` by joining `rhs` to the `CollectionPath`.
+///
+/// # Panics
+///
+/// Panics if `rhs` is not a valid `DocumentId`. Use [`CollectionPath::into_doc`] to handle the error instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{CollectionPath,DocumentPath};
+/// use std::str::FromStr;
+///
+/// let collection_path = CollectionPath::from_str("chatrooms")?;
+/// assert_eq!(
+///     collection_path / "chatroom1" / "messages" / "message1",
+///     DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+impl std::ops::Div<&str> for CollectionPath {
+    type Output = DocumentPath;
+
+    fn div(self, rhs: &str) -> Self::Output {
+        self.into_doc(rhs)
+            .unwrap_or_else(|e| panic!("CollectionPath / &str: {e}"))
+    }
+}
+
+/// Creates a new `DocumentPath` by joining `rhs` to the `CollectionPath`.
+impl std::ops::Div<DocumentId> for CollectionPath {
+    type Output = DocumentPath;
+
+    fn div(self, rhs: DocumentId) -> Self::Output {
+        self.into_doc(rhs)
+            .unwrap_or_else(|e| panic!("CollectionPath / DocumentId: {e}"))
+    }
+}
+
+impl std::ops::Drop for CollectionPath {
+    fn drop(&mut self) {
+        // Unlink the ancestors one at a time so that dropping a deep path does not recurse.
+        let mut document_path = self.document_path.take();
+        while let Some(parent) = document_path {
+            document_path = parent.into_parent().into_parent();
         }
     }
 }
@@ -264,6 +974,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_ancestors() -> anyhow::Result<()> {
+        let collection_path = CollectionPath::from_str("chatrooms")?;
+        assert_eq!(
+            collection_path.ancestors().collect::<Vec<AnyPath>>(),
+            vec![]
+        );
+
+        let collection_path =
+            CollectionPath::from_str("chatrooms/chatroom1/messages/message1/col")?;
+        assert_eq!(
+            collection_path
+                .ancestors()
+                .map(|any_path| any_path.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "chatrooms/chatroom1/messages/message1",
+                "chatrooms/chatroom1/messages",
+                "chatrooms/chatroom1",
+                "chatrooms",
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_collection_id() -> anyhow::Result<()> {
         let collection_path = CollectionPath::from_str("chatrooms")?;
@@ -274,6 +1009,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_segments() -> anyhow::Result<()> {
+        let collection_path = CollectionPath::from_str("chatrooms")?;
+        assert_eq!(
+            collection_path.segments().collect::<Vec<Segment>>(),
+            vec![Segment::Collection(&CollectionId::from_str("chatrooms")?)]
+        );
+
+        let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+        assert_eq!(
+            collection_path.segments().collect::<Vec<Segment>>(),
+            vec![
+                Segment::Collection(&CollectionId::from_str("chatrooms")?),
+                Segment::Document(&DocumentId::from_str("chatroom1")?),
+                Segment::Collection(&CollectionId::from_str("messages")?),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_depth() -> anyhow::Result<()> {
+        for (s, expected) in [
+            ("chatrooms", 1),
+            ("chatrooms/chatroom1/messages", 2),
+            ("chatrooms/chatroom1/messages/message1/col", 3),
+        ] {
+            assert_eq!(CollectionPath::from_str(s)?.depth(), expected);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_doc() -> anyhow::Result<()> {
         let collection_path = CollectionPath::from_str("chatrooms")?;
@@ -341,7 +1108,14 @@ mod tests {
 
     #[test]
     fn test_impl_from_str_and_impl_try_from_string() -> anyhow::Result<()> {
-        for (s, expected) in [("chatrooms", true), ("chatrooms/chatroom1/messages", true)] {
+        for (s, expected) in [
+            ("", false),
+            ("chatrooms", true),
+            ("chatrooms/", false),
+            ("chatrooms/chatroom1", false),
+            ("chatrooms//messages", false),
+            ("chatrooms/chatroom1/messages", true),
+        ] {
             assert_eq!(CollectionPath::from_str(s).is_ok(), expected);
             assert_eq!(CollectionPath::try_from(s).is_ok(), expected);
             assert_eq!(CollectionPath::try_from(s.to_string()).is_ok(), expected);
@@ -357,6 +1131,60 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_sibling() -> anyhow::Result<()> {
+        let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+        assert_eq!(
+            collection_path.sibling("members")?,
+            CollectionPath::from_str("chatrooms/chatroom1/members")?
+        );
+        assert!(collection_path.sibling("mem/bers").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_prefix() -> anyhow::Result<()> {
+        let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+        for (prefix, expected) in [
+            ("chatrooms/chatroom1", Some("messages")),
+            ("chatrooms/chatroom2", None),
+            ("chatroom/chatroom1", None),
+            ("chatrooms/chatroom1/messages/message1", None),
+        ] {
+            assert_eq!(
+                collection_path
+                    .strip_prefix(&DocumentPath::from_str(prefix)?)
+                    .ok(),
+                expected.map(CollectionPath::from_str).transpose()?
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_try_from_segments() -> anyhow::Result<()> {
+        for (segments, expected) in [
+            (vec![], None),
+            (vec!["chatrooms"], Some("chatrooms")),
+            (vec!["chatrooms", "chatroom1"], None),
+            (
+                vec!["chatrooms", "chatroom1", "messages"],
+                Some("chatrooms/chatroom1/messages"),
+            ),
+            (vec!["chatrooms", "chatroom1", "messages", "message1"], None),
+            (vec!["chatrooms", "chat/room1", "messages"], None),
+            (vec!["chatrooms", "chatroom1", "__messages__"], None),
+        ] {
+            let expected = expected.map(CollectionPath::from_str).transpose()?;
+            assert_eq!(
+                CollectionPath::try_from_segments(segments.iter()).ok(),
+                expected
+            );
+            assert_eq!(CollectionPath::try_from(segments.as_slice()).ok(), expected);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_into_doc() -> anyhow::Result<()> {
         let collection_path = CollectionPath::from_str("chatrooms")?;
@@ -407,4 +1235,231 @@ mod tests {
     fn build_document_path() -> anyhow::Result<DocumentPath> {
         Ok(DocumentPath::from_str("chatrooms/chatroom1")?)
     }
+
+    #[test]
+    fn test_is_in_collection_group() -> anyhow::Result<()> {
+        let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+        for (collection_id, expected) in [
+            ("chatrooms", true),
+            ("messages", true),
+            ("chatroom1", false),
+            ("users", false),
+        ] {
+            assert_eq!(
+                collection_path.is_in_collection_group(&CollectionId::from_str(collection_id)?),
+                expected
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_ids_and_document_ids() -> anyhow::Result<()> {
+        let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+        assert_eq!(
+            collection_path
+                .collection_ids()
+                .map(CollectionId::as_ref)
+                .collect::<Vec<&str>>(),
+            vec!["chatrooms", "messages"]
+        );
+        assert_eq!(
+            collection_path
+                .document_ids()
+                .map(DocumentId::as_ref)
+                .collect::<Vec<&str>>(),
+            vec!["chatroom1"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_id_at_and_document_id_at() -> anyhow::Result<()> {
+        let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+        for (index, expected) in [(0, Some("chatrooms")), (1, Some("messages")), (2, None)] {
+            assert_eq!(
+                collection_path
+                    .collection_id_at(index)
+                    .map(CollectionId::as_ref),
+                expected
+            );
+        }
+        for (index, expected) in [(0, Some("chatroom1")), (1, None)] {
+            assert_eq!(
+                collection_path
+                    .document_id_at(index)
+                    .map(DocumentId::as_ref),
+                expected
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_div() -> anyhow::Result<()> {
+        let collection_path = CollectionPath::from_str("chatrooms")?;
+        assert_eq!(
+            (collection_path.clone() / "chatroom1").to_string(),
+            "chatrooms/chatroom1"
+        );
+        assert_eq!(
+            (collection_path / DocumentId::from_str("chatroom1")?).to_string(),
+            "chatrooms/chatroom1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_append() -> anyhow::Result<()> {
+        for (s, segments, expected) in [
+            ("c1", vec![], Some("c1")),
+            ("c1", vec!["d1"], Some("c1/d1")),
+            ("c1", vec!["d1", "c2"], Some("c1/d1/c2")),
+            (
+                "c1/d1/c2",
+                vec!["d2", "c3", "d3"],
+                Some("c1/d1/c2/d2/c3/d3"),
+            ),
+            ("c1", vec!["d1/c2"], None),
+            ("c1", vec!["d1", "__c2__"], None),
+        ] {
+            let collection_path = CollectionPath::from_str(s)?;
+            assert_eq!(
+                collection_path
+                    .append(segments)
+                    .ok()
+                    .map(|any_path| any_path.to_string()),
+                expected.map(ToString::to_string)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_ord() -> anyhow::Result<()> {
+        let expected = vec!["a", "a/b/c", "a/b/c/d/e", "a/b2/c", "a2", "z"];
+        let mut sorted = expected
+            .iter()
+            .rev()
+            .map(|s| CollectionPath::from_str(s))
+            .collect::<Result<Vec<CollectionPath>, Error>>()?;
+        sorted.sort();
+        assert_eq!(
+            sorted
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_deep_path() -> anyhow::Result<()> {
+        let depth = 100_000;
+        let s = format!("{}c", "c/d/".repeat(depth - 1));
+        assert_eq!(
+            CollectionPath::from_str(&s).unwrap_err().kind(),
+            &ErrorKind::DepthOutOfBounds
+        );
+
+        // A deep path can still be built with `new`, and it must not overflow the stack.
+        let mut collection_path = CollectionPath::from_str("c")?;
+        for _ in 1..depth {
+            collection_path = CollectionPath::new(
+                Some(DocumentPath::new(
+                    collection_path,
+                    DocumentId::from_str("d")?,
+                )),
+                CollectionId::from_str("c")?,
+            );
+        }
+        assert_eq!(collection_path.depth(), depth);
+        assert_eq!(collection_path.to_string(), s);
+        drop(collection_path);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encoded_len_and_write_to() -> anyhow::Result<()> {
+        for s in [
+            "chatrooms/chatroom1/messages",
+            "chatrooms/chatroom1/messages/x/y",
+        ] {
+            let collection_path = CollectionPath::from_str(s)?;
+            assert_eq!(collection_path.encoded_len(), s.len());
+            let mut buffer = String::from(">");
+            collection_path.write_to(&mut buffer)?;
+            assert_eq!(buffer, format!(">{s}"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_string() -> anyhow::Result<()> {
+        let s = "chatrooms/chatroom1/messages";
+        assert_eq!(CollectionPath::from_str(s)?.into_string(), s);
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_in_const_context() -> anyhow::Result<()> {
+        static COLLECTION_PATH: CollectionPath =
+            CollectionPath::new(None, CollectionId::from_static("chatrooms"));
+        assert_eq!(COLLECTION_PATH, CollectionPath::from_str("chatrooms")?);
+        assert_eq!(
+            COLLECTION_PATH.doc("chatroom1")?,
+            DocumentPath::from_str("chatrooms/chatroom1")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> anyhow::Result<()> {
+        for s in [
+            "chatrooms".to_string(),
+            "chatrooms/chatroom1/messages".to_string(),
+            "chatrooms/chatroom1".to_string(),
+            "chatrooms/../messages".to_string(),
+            "/chatrooms".to_string(),
+            "c/d/".repeat(100) + "c",
+        ] {
+            assert_eq!(
+                CollectionPath::validate(&s).err(),
+                CollectionPath::from_str(&s).err()
+            );
+            assert_eq!(
+                CollectionPath::is_valid(&s),
+                CollectionPath::from_str(&s).is_ok()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_docs() -> anyhow::Result<()> {
+        let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+        let ids = (0..100)
+            .map(|i| format!("message{}", i))
+            .collect::<Vec<String>>();
+        let docs = collection_path
+            .docs(ids.iter().map(String::as_str))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(docs.len(), ids.len());
+        for (doc, id) in docs.iter().zip(ids.iter()) {
+            assert_eq!(doc, &collection_path.doc(id.as_str())?);
+            assert_eq!(doc.to_string(), format!("{}/{}", collection_path, id));
+        }
+        assert_eq!(collection_path.docs(Vec::<String>::new()).count(), 0);
+        assert!(matches!(
+            collection_path
+                .docs(["__x__"])
+                .next()
+                .expect("one item")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DocumentIdConversion(_)
+        ));
+        Ok(())
+    }
 }
@@ -1,4 +1,8 @@
-use crate::{error::ErrorKind, Error};
+use crate::{
+    error::ErrorKind,
+    id_string::{self, IdString},
+    limits, Error, ParseOptions,
+};
 
 /// A database id.
 ///
@@ -6,7 +10,7 @@ use crate::{error::ErrorKind, Error};
 ///
 /// <https://firebase.google.com/docs/firestore/reference/rest/v1/projects.databases/create#query-parameters>
 ///
-/// > This value should be 4-63 characters. Valid characters are /[a-z][0-9]-/ with first character a letter and the last a letter or a number. Must not be UUID-like /[0-9a-f]{8}(-[0-9a-f]{4}){3}-[0-9a-f]{12}/.
+/// > This value should be 4-63 characters. Valid characters are `/[a-z][0-9]-/` with first character a letter and the last a letter or a number. Must not be UUID-like `/[0-9a-f]{8}(-[0-9a-f]{4}){3}-[0-9a-f]{12}/`.
 /// >
 /// > "(default)" database id is also valid.
 ///
@@ -33,11 +37,205 @@ use crate::{error::ErrorKind, Error};
 /// ```
 ///
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct DatabaseId(String);
+pub struct DatabaseId(IdString);
+
+impl DatabaseId {
+    /// The default database id `(default)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DatabaseId;
+    ///
+    /// assert_eq!(DatabaseId::DEFAULT.as_ref(), "(default)");
+    /// assert_eq!(DatabaseId::DEFAULT, DatabaseId::default());
+    /// ```
+    pub const DEFAULT: DatabaseId = DatabaseId(IdString::Static(DEFAULT));
+
+    /// Creates a new `DatabaseId` from a `'static` string.
+    ///
+    /// This is a `const fn`, so well-known ids can be defined as `const`s or `static`s without parsing at runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not a valid `DatabaseId`. In a const context, this is a compile-time error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DatabaseId;
+    /// use std::str::FromStr;
+    ///
+    /// static ID: DatabaseId = DatabaseId::from_static("my-database");
+    /// assert_eq!(ID.as_ref(), "my-database");
+    /// assert_eq!(ID, DatabaseId::from_str("my-database").unwrap());
+    /// ```
+    ///
+    /// ```rust,compile_fail
+    /// use firestore_path::DatabaseId;
+    ///
+    /// static ID: DatabaseId = DatabaseId::from_static("My-Database");
+    /// ```
+    pub const fn from_static(s: &'static str) -> Self {
+        let result = validate(s);
+        let is_valid = result.is_ok();
+        // `ErrorKind` has a destructor, which cannot be run in a const context.
+        std::mem::forget(result);
+        assert!(is_valid, "invalid DatabaseId");
+        Self(IdString::Static(s))
+    }
+
+    /// Creates a new `DatabaseId` from `s` with `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid `DatabaseId` under `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseId,ParseOptions};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     DatabaseId::from_str_with("my-database", &ParseOptions::strict())?,
+    ///     DatabaseId::from_str("my-database")?
+    /// );
+    /// assert!(DatabaseId::from_str("db").is_err());
+    /// assert!(DatabaseId::from_str_with("db", &ParseOptions::lenient()).is_ok());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        validate_with(s, options)?;
+        Ok(Self(IdString::from(s.to_string())))
+    }
+
+    /// Returns `true` if this is the default database id `(default)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DatabaseId;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(DatabaseId::from_str("(default)")?.is_default());
+    /// assert!(!DatabaseId::from_str("my-database")?.is_default());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_default(&self) -> bool {
+        self.0.as_str() == DEFAULT
+    }
+
+    /// Returns `true` if `s` is a valid `DatabaseId`.
+    ///
+    /// This is the same as `DatabaseId::validate(s).is_ok()` and does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DatabaseId;
+    ///
+    /// assert!(DatabaseId::is_valid("my-database"));
+    /// assert!(!DatabaseId::is_valid("db"));
+    /// ```
+    pub fn is_valid(s: &str) -> bool {
+        validate(s).is_ok()
+    }
+
+    /// Checks that `s` is a valid `DatabaseId` without creating a `DatabaseId`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that `DatabaseId::from_str(s)` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DatabaseId;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(DatabaseId::validate("my-database").is_ok());
+    /// assert_eq!(
+    ///     DatabaseId::validate("db").unwrap_err(),
+    ///     DatabaseId::from_str("db").unwrap_err()
+    /// );
+    /// ```
+    pub fn validate(s: &str) -> Result<(), Error> {
+        Ok(validate(s)?)
+    }
+}
+
+const DEFAULT: &str = "(default)";
+
+pub(crate) const fn validate(s: &str) -> Result<(), ErrorKind> {
+    validate_with(s, &ParseOptions::strict())
+}
+
+pub(crate) const fn validate_with(s: &str, options: &ParseOptions) -> Result<(), ErrorKind> {
+    // <https://firebase.google.com/docs/firestore/reference/rest/v1/projects.databases/create#query-parameters>
+    if id_string::eq(s, DEFAULT) {
+        return Ok(());
+    }
+
+    if s.is_empty()
+        || (options.length_limits
+            && (s.len() < limits::MIN_DATABASE_ID_LEN || s.len() > limits::MAX_DATABASE_ID_LEN))
+    {
+        return Err(ErrorKind::LengthOutOfBounds);
+    }
+
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if !(b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-') {
+            return Err(ErrorKind::ContainsInvalidCharacter);
+        }
+        i += 1;
+    }
+
+    if !bytes[0].is_ascii_lowercase() {
+        return Err(ErrorKind::StartsWithNonLetter);
+    }
+
+    if bytes[bytes.len() - 1] == b'-' {
+        return Err(ErrorKind::EndsWithHyphen);
+    }
+
+    if options.reserved_ids && is_uuid_like(bytes) {
+        return Err(ErrorKind::MatchesUuidPattern);
+    }
+
+    Ok(())
+}
+
+// Returns `true` if `bytes` matches `[0-9a-f]{8}(-[0-9a-f]{4}){3}-[0-9a-f]{12}`.
+const fn is_uuid_like(bytes: &[u8]) -> bool {
+    if bytes.len() != 36 {
+        return false;
+    }
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        let is_valid = match i {
+            8 | 13 | 18 | 23 => b == b'-',
+            _ => b.is_ascii_digit() || matches!(b, b'a'..=b'f'),
+        };
+        if !is_valid {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
 
 impl std::convert::AsRef<str> for DatabaseId {
     fn as_ref(&self) -> &str {
-        self.0.as_ref()
+        self.0.as_str()
     }
 }
 
@@ -53,32 +251,8 @@ impl std::convert::TryFrom<String> for DatabaseId {
     type Error = Error;
 
     fn try_from(s: String) -> Result<Self, Self::Error> {
-        // <https://firebase.google.com/docs/firestore/reference/rest/v1/projects.databases/create#query-parameters>
-        if s == "(default)" {
-            return Ok(Self(s.to_string()));
-        }
-
-        if !(4..=63).contains(&s.len()) {
-            return Err(Error::from(ErrorKind::LengthOutOfBounds));
-        }
-
-        if !s
-            .chars()
-            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
-        {
-            return Err(Error::from(ErrorKind::ContainsInvalidCharacter));
-        }
-
-        let first_char = s.chars().next().expect("already length checked");
-        if !first_char.is_ascii_lowercase() {
-            return Err(Error::from(ErrorKind::StartsWithNonLetter));
-        }
-
-        if s.ends_with('-') {
-            return Err(Error::from(ErrorKind::EndsWithHyphen));
-        }
-
-        Ok(Self(s))
+        validate(&s)?;
+        Ok(Self(IdString::from(s)))
     }
 }
 
@@ -92,7 +266,7 @@ impl std::default::Default for DatabaseId {
     /// assert_eq!(DatabaseId::default().to_string(), "(default)");
     /// ```
     fn default() -> Self {
-        Self("(default)".to_string())
+        Self::DEFAULT
     }
 }
 
@@ -146,6 +320,11 @@ mod tests {
             ("0xxx", false),
             ("xxx-", false),
             ("xxx0", true),
+            ("abcdef01-2345-6789-abcd-ef0123456789", false),
+            ("abcdef01-2345-6789-abcd-ef012345678", true),
+            ("abcdef01-2345-6789-abcd-ef0123456789a", true),
+            ("abcdef01-2345-6789-abcd0ef0123456789", true),
+            ("abcdefg1-2345-6789-abcd-ef0123456789", true),
         ] {
             assert_eq!(DatabaseId::from_str(s).is_ok(), expected);
             assert_eq!(DatabaseId::try_from(s).is_ok(), expected);
@@ -161,4 +340,63 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_uuid_like() -> anyhow::Result<()> {
+        assert_eq!(
+            DatabaseId::from_str("abcdef01-2345-6789-abcd-ef0123456789")
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::MatchesUuidPattern
+        );
+        // The pattern requires lowercase hex digits, which are rejected by the other rules anyway.
+        assert_eq!(
+            DatabaseId::from_str("0bcdef01-2345-6789-abcd-ef0123456789")
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::StartsWithNonLetter
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_static() -> anyhow::Result<()> {
+        const ID: DatabaseId = DatabaseId::from_static("(default)");
+        assert_eq!(ID, DatabaseId::from_str("(default)")?);
+        assert_eq!(ID.to_string(), "(default)");
+        assert!(std::panic::catch_unwind(|| DatabaseId::from_static("db")).is_err());
+        assert!(std::panic::catch_unwind(|| {
+            DatabaseId::from_static("abcdef01-2345-6789-abcd-ef0123456789")
+        })
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> anyhow::Result<()> {
+        for s in [
+            "my-database".to_string(),
+            "(default)".to_string(),
+            "db".to_string(),
+            "My-Database".to_string(),
+            "x".repeat(64),
+        ] {
+            assert_eq!(
+                DatabaseId::validate(&s).err(),
+                DatabaseId::from_str(&s).err()
+            );
+            assert_eq!(DatabaseId::is_valid(&s), DatabaseId::from_str(&s).is_ok());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_default() -> anyhow::Result<()> {
+        assert_eq!(DatabaseId::DEFAULT, DatabaseId::from_str("(default)")?);
+        assert_eq!(DatabaseId::DEFAULT, DatabaseId::default());
+        assert!(DatabaseId::DEFAULT.is_default());
+        assert!(DatabaseId::from_str("(default)")?.is_default());
+        assert!(!DatabaseId::from_str("default")?.is_default());
+        Ok(())
+    }
 }
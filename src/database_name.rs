@@ -1,8 +1,6 @@
-use std::str::FromStr;
-
 use crate::{
-    error::ErrorKind, CollectionName, CollectionPath, DatabaseId, DocumentName, DocumentPath,
-    Error, ProjectId, RootDocumentName,
+    database_id, error::ErrorKind, limits, project_id, CollectionName, CollectionPath, DatabaseId,
+    DocumentName, DocumentPath, Error, ParseOptions, ProjectId, RootDocumentName,
 };
 
 /// A database name.
@@ -84,7 +82,7 @@ impl DatabaseName {
     /// # }
     /// ```
     ///
-    pub fn new(project_id: ProjectId, database_id: DatabaseId) -> Self {
+    pub const fn new(project_id: ProjectId, database_id: DatabaseId) -> Self {
         Self {
             database_id,
             project_id,
@@ -122,6 +120,62 @@ impl DatabaseName {
         })
     }
 
+    /// Creates a new `DatabaseName` from `s` with `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid `DatabaseName` under `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseName,ParseOptions};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     DatabaseName::from_str_with(
+    ///         "projects/my-project/databases/my-database",
+    ///         &ParseOptions::strict()
+    ///     )?,
+    ///     DatabaseName::from_str("projects/my-project/databases/my-database")?
+    /// );
+    ///
+    /// let s = "projects/my-google-project/databases/my-database";
+    /// assert!(DatabaseName::from_str(s).is_err());
+    /// assert!(DatabaseName::from_str_with(s, &ParseOptions::lenient()).is_ok());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        options.parse_normalized(s, |s| {
+            if options.length_limits {
+                limits::check_name_length(s)?;
+            }
+
+            let mut parts = s.split('/');
+            let [Some(projects), Some(project_id), Some(databases), Some(database_id), None] =
+                std::array::from_fn(|_| parts.next())
+            else {
+                return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+            };
+            if projects != "projects" || databases != "databases" {
+                return Err(Error::from(ErrorKind::InvalidName));
+            }
+
+            let project_id_start = projects.len() + 1;
+            let database_id_start = project_id_start + project_id.len() + 1 + databases.len() + 1;
+            let project_id = ProjectId::from_str_with(project_id, options)
+                .map_err(|e| e.at_segment(1, project_id, project_id_start))?;
+            let database_id = DatabaseId::from_str_with(database_id, options)
+                .map_err(|e| e.at_segment(3, database_id, database_id_start))?;
+            Ok(Self {
+                database_id,
+                project_id,
+            })
+        })
+    }
+
     /// Creates a new `CollectionName` from this `DatabaseName` and `collection_path`.
     ///
     /// # Examples
@@ -220,7 +274,7 @@ impl DatabaseName {
         let collection_path = collection_path
             .try_into()
             .map_err(|e| Error::from(ErrorKind::CollectionPathConversion(e.to_string())))?;
-        Ok(CollectionName::new(self, collection_path))
+        CollectionName::try_new(self, collection_path)
     }
 
     /// Returns the `DatabaseId` of this `DatabaseName`.
@@ -342,7 +396,7 @@ impl DatabaseName {
         let document_path = document_path
             .try_into()
             .map_err(|e| Error::from(ErrorKind::DocumentPathConversion(e.to_string())))?;
-        Ok(DocumentName::new(self, document_path))
+        DocumentName::try_new(self, document_path)
     }
 
     /// Consumes the `DatabaseName`, returning the `RootDocumentName`.
@@ -366,6 +420,63 @@ impl DatabaseName {
         RootDocumentName::new(self)
     }
 
+    /// Converts this `DatabaseName` into its canonical string representation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DatabaseName;
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str(
+    ///     "projects/my-project/databases/my-database"
+    /// )?;
+    /// assert_eq!(
+    ///     database_name.into_string(),
+    ///     "projects/my-project/databases/my-database"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_string(self) -> String {
+        self.to_string()
+    }
+
+    /// Returns `true` if this `DatabaseName` is in the default database `(default)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DatabaseName;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(DatabaseName::from_str("projects/my-project/databases/(default)")?.is_default_database());
+    /// assert!(!DatabaseName::from_str("projects/my-project/databases/my-database")?.is_default_database());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_default_database(&self) -> bool {
+        self.database_id.is_default()
+    }
+
+    /// Returns `true` if `s` is a valid `DatabaseName`.
+    ///
+    /// This is the same as `DatabaseName::validate(s).is_ok()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DatabaseName;
+    ///
+    /// assert!(DatabaseName::is_valid("projects/my-project/databases/my-database"));
+    /// assert!(!DatabaseName::is_valid("projects/my-project/databases/db"));
+    /// ```
+    pub fn is_valid(s: &str) -> bool {
+        Self::validate(s).is_ok()
+    }
+
     /// Returns the `ProjectId` of this `DatabaseName`.
     ///
     /// # Examples
@@ -407,6 +518,203 @@ impl DatabaseName {
     pub fn root_document_name(&self) -> RootDocumentName {
         self.clone().into_root_document_name()
     }
+    /// Returns the value of the `x-goog-request-params` header that routes requests to this database.
+    ///
+    /// The database name is URL-encoded (`/` as `%2F`, `(` as `%28`, ...).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DatabaseName;
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// assert_eq!(
+    ///     database_name.routing_header_value(),
+    ///     "database=projects%2Fmy-project%2Fdatabases%2Fmy-database"
+    /// );
+    ///
+    /// let database_name = DatabaseName::from_project_id("my-project")?;
+    /// assert_eq!(
+    ///     database_name.routing_header_value(),
+    ///     "database=projects%2Fmy-project%2Fdatabases%2F%28default%29"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn routing_header_value(&self) -> String {
+        const HEX: &[u8; 16] = b"0123456789ABCDEF";
+        let name = self.to_string();
+        let mut s = String::with_capacity("database=".len() + name.len() * 3);
+        s.push_str("database=");
+        for b in name.bytes() {
+            if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+                s.push(char::from(b));
+            } else {
+                s.push('%');
+                s.push(char::from(HEX[usize::from(b >> 4)]));
+                s.push(char::from(HEX[usize::from(b & 0x0F)]));
+            }
+        }
+        s
+    }
+
+    /// Returns the value of the `x-goog-request-params` header as a `tonic` `MetadataValue`.
+    ///
+    /// This method requires the `tonic` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DatabaseName;
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let mut request = tonic::Request::new(());
+    /// request
+    ///     .metadata_mut()
+    ///     .insert("x-goog-request-params", database_name.routing_metadata_value());
+    /// assert_eq!(
+    ///     request.metadata().get("x-goog-request-params"),
+    ///     Some(&database_name.routing_metadata_value())
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tonic")]
+    pub fn routing_metadata_value(&self) -> tonic::metadata::AsciiMetadataValue {
+        tonic::metadata::AsciiMetadataValue::try_from(self.routing_header_value())
+            .expect("the routing header value to be URL-encoded ASCII")
+    }
+
+    /// Checks that `s` is a valid `DatabaseName` without creating a `DatabaseName`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that `DatabaseName::from_str(s)` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DatabaseName;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(DatabaseName::validate("projects/my-project/databases/my-database").is_ok());
+    ///
+    /// let s = "projects/my-project/databases/db";
+    /// assert_eq!(
+    ///     DatabaseName::validate(s).unwrap_err(),
+    ///     DatabaseName::from_str(s).unwrap_err()
+    /// );
+    /// ```
+    pub fn validate(s: &str) -> Result<(), Error> {
+        limits::check_name_length(s)?;
+
+        let mut parts = s.split('/');
+        let [Some(projects), Some(project_id), Some(databases), Some(database_id), None] =
+            std::array::from_fn(|_| parts.next())
+        else {
+            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+        };
+        if projects != "projects" || databases != "databases" {
+            return Err(Error::from(ErrorKind::InvalidName));
+        }
+
+        let project_id_start = projects.len() + 1;
+        let database_id_start = project_id_start + project_id.len() + 1 + databases.len() + 1;
+        project_id::validate(project_id)
+            .map_err(|e| Error::from(e).at_segment(1, project_id, project_id_start))?;
+        database_id::validate(database_id)
+            .map_err(|e| Error::from(e).at_segment(3, database_id, database_id_start))?;
+        Ok(())
+    }
+
+    /// Creates a new `DatabaseName` with the same `ProjectId` and the provided `database_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `database_id` cannot be converted into a `DatabaseId`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseId,DatabaseName};
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// assert_eq!(
+    ///     database_name.with_database_id("my-database2")?,
+    ///     DatabaseName::from_str("projects/my-project/databases/my-database2")?
+    /// );
+    /// assert_eq!(
+    ///     database_name.with_database_id(DatabaseId::default())?,
+    ///     DatabaseName::from_str("projects/my-project/databases/(default)")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn with_database_id<E, T>(&self, database_id: T) -> Result<DatabaseName, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DatabaseId, Error = E>,
+    {
+        Ok(Self::new(
+            self.project_id.clone(),
+            database_id
+                .try_into()
+                .map_err(|e| Error::from(ErrorKind::DatabaseIdConversion(e.to_string())))?,
+        ))
+    }
+
+    /// Creates a new `DatabaseName` with the same `DatabaseId` and the provided `project_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `project_id` cannot be converted into a `ProjectId`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{ProjectId,DatabaseName};
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// assert_eq!(
+    ///     database_name.with_project_id("my-project2")?,
+    ///     DatabaseName::from_str("projects/my-project2/databases/my-database")?
+    /// );
+    /// assert!(database_name.with_project_id("my-google-project").is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn with_project_id<E, T>(&self, project_id: T) -> Result<DatabaseName, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<ProjectId, Error = E>,
+    {
+        Ok(Self::new(
+            project_id
+                .try_into()
+                .map_err(|e| Error::from(ErrorKind::ProjectIdConversion(e.to_string())))?,
+            self.database_id.clone(),
+        ))
+    }
+}
+
+impl std::convert::From<(ProjectId, DatabaseId)> for DatabaseName {
+    fn from((project_id, database_id): (ProjectId, DatabaseId)) -> Self {
+        Self::new(project_id, database_id)
+    }
+}
+
+impl std::convert::From<DatabaseName> for (ProjectId, DatabaseId) {
+    fn from(database_name: DatabaseName) -> Self {
+        (database_name.project_id, database_name.database_id)
+    }
 }
 
 impl std::convert::From<DatabaseName> for DatabaseId {
@@ -425,24 +733,7 @@ impl std::convert::TryFrom<&str> for DatabaseName {
     type Error = Error;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        if !(1..=1_024 * 6).contains(&s.len()) {
-            return Err(Error::from(ErrorKind::LengthOutOfBounds));
-        }
-
-        let parts = s.split('/').collect::<Vec<&str>>();
-        if parts.len() != 4 {
-            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
-        }
-        if parts[0] != "projects" || parts[2] != "databases" {
-            return Err(Error::from(ErrorKind::InvalidName));
-        }
-
-        let project_id = ProjectId::from_str(parts[1])?;
-        let database_id = DatabaseId::from_str(parts[3])?;
-        Ok(Self {
-            database_id,
-            project_id,
-        })
+        Self::from_str_with(s, &ParseOptions::strict())
     }
 }
 
@@ -464,6 +755,58 @@ impl std::fmt::Display for DatabaseName {
     }
 }
 
+/// Creates a new `CollectionName` by joining `rhs` to the `DatabaseName`.
+///
+/// # Panics
+///
+/// Panics if `rhs` is not a valid `CollectionPath`. Use [`DatabaseName::into_collection`] to handle the error instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{DatabaseName,DocumentName};
+/// use std::str::FromStr;
+///
+/// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+/// assert_eq!(
+///     database_name / "chatrooms" / "chatroom1",
+///     DocumentName::from_str(
+///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+///     )?
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+impl std::ops::Div<&str> for DatabaseName {
+    type Output = CollectionName;
+
+    fn div(self, rhs: &str) -> Self::Output {
+        self.into_collection(rhs)
+            .unwrap_or_else(|e| panic!("DatabaseName / &str: {e}"))
+    }
+}
+
+/// Creates a new `CollectionName` by joining `rhs` to the `DatabaseName`.
+impl std::ops::Div<CollectionPath> for DatabaseName {
+    type Output = CollectionName;
+
+    fn div(self, rhs: CollectionPath) -> Self::Output {
+        self.into_collection(rhs)
+            .unwrap_or_else(|e| panic!("DatabaseName / CollectionPath: {e}"))
+    }
+}
+
+/// Creates a new `DocumentName` by joining `rhs` to the `DatabaseName`.
+impl std::ops::Div<DocumentPath> for DatabaseName {
+    type Output = DocumentName;
+
+    fn div(self, rhs: DocumentPath) -> Self::Output {
+        self.into_doc(rhs)
+            .unwrap_or_else(|e| panic!("DatabaseName / DocumentPath: {e}"))
+    }
+}
+
 impl std::str::FromStr for DatabaseName {
     type Err = Error;
 
@@ -531,4 +874,133 @@ mod tests {
     fn build_project_id() -> anyhow::Result<ProjectId> {
         Ok(ProjectId::from_str("my-project")?)
     }
+
+    #[test]
+    fn test_impl_div() -> anyhow::Result<()> {
+        let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+        assert_eq!(
+            (database_name.clone() / "chatrooms").to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms"
+        );
+        assert_eq!(
+            (database_name.clone() / CollectionPath::from_str("chatrooms/chatroom1/messages")?)
+                .to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+        );
+        assert_eq!(
+            (database_name / DocumentPath::from_str("chatrooms/chatroom1")?).to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_impl_div_panics() {
+        let _ = DatabaseName::from_str("projects/my-project/databases/my-database").unwrap()
+            / "chatrooms/chatroom1";
+    }
+
+    #[test]
+    fn test_into_string() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database";
+        assert_eq!(DatabaseName::from_str(s)?.into_string(), s);
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_in_const_context() -> anyhow::Result<()> {
+        static DATABASE_NAME: DatabaseName = DatabaseName::new(
+            ProjectId::from_static("my-project"),
+            DatabaseId::from_static("my-database"),
+        );
+        assert_eq!(
+            DATABASE_NAME,
+            DatabaseName::from_str("projects/my-project/databases/my-database")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_routing_header_value() -> anyhow::Result<()> {
+        for (s, expected) in [
+            (
+                "projects/my-project/databases/my-database",
+                "database=projects%2Fmy-project%2Fdatabases%2Fmy-database",
+            ),
+            (
+                "projects/my-project/databases/(default)",
+                "database=projects%2Fmy-project%2Fdatabases%2F%28default%29",
+            ),
+        ] {
+            assert_eq!(DatabaseName::from_str(s)?.routing_header_value(), expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> anyhow::Result<()> {
+        for s in [
+            "projects/my-project/databases/my-database".to_string(),
+            "projects/my-project/databases/db".to_string(),
+            "projects/my-project/database/my-database".to_string(),
+            "projects/my-project/databases".to_string(),
+            "projects/x/databases/my-database".to_string(),
+            "".to_string(),
+        ] {
+            assert_eq!(
+                DatabaseName::validate(&s).err(),
+                DatabaseName::from_str(&s).err()
+            );
+            assert_eq!(
+                DatabaseName::is_valid(&s),
+                DatabaseName::from_str(&s).is_ok()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_database_id_and_with_project_id() -> anyhow::Result<()> {
+        let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+        assert_eq!(
+            database_name.with_database_id("(default)")?.to_string(),
+            "projects/my-project/databases/(default)"
+        );
+        assert_eq!(
+            database_name
+                .with_project_id("my-project2")?
+                .with_database_id("my-database2")?
+                .to_string(),
+            "projects/my-project2/databases/my-database2"
+        );
+        assert_eq!(
+            database_name.with_database_id("db").unwrap_err().kind(),
+            &ErrorKind::DatabaseIdConversion("byte length exceeded".to_string())
+        );
+        assert!(matches!(
+            database_name
+                .with_project_id("My-Project")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::ProjectIdConversion(_)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_from_tuple() -> anyhow::Result<()> {
+        let project_id = ProjectId::from_str("my-project")?;
+        let database_id = DatabaseId::from_str("my-database")?;
+        let database_name = DatabaseName::from((project_id.clone(), database_id.clone()));
+        assert_eq!(
+            database_name,
+            DatabaseName::from_str("projects/my-project/databases/my-database")?
+        );
+        assert_eq!(
+            <(ProjectId, DatabaseId)>::from(database_name),
+            (project_id, database_id)
+        );
+        Ok(())
+    }
 }
@@ -1,4 +1,8 @@
-use crate::{error::ErrorKind, Error};
+use crate::{
+    error::ErrorKind,
+    id_string::{self, IdString},
+    limits, Error, ParseOptions,
+};
 
 /// A document id.
 ///
@@ -13,6 +17,8 @@ use crate::{error::ErrorKind, Error};
 /// > - Cannot match the regular expression __.*__
 /// > - If you import Datastore entities into a Firestore database, numeric entity IDs are exposed as __id[0-9]+__
 ///
+/// The numeric entity ids (`__id[0-9]+__`) match `__.*__`, so they are rejected and cannot collide with the imported documents.
+///
 /// # Examples
 ///
 /// ```rust
@@ -28,11 +34,258 @@ use crate::{error::ErrorKind, Error};
 /// ```
 ///
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct DocumentId(String);
+pub struct DocumentId(IdString);
+
+impl DocumentId {
+    /// Creates a new `DocumentId` from a `'static` string.
+    ///
+    /// This is a `const fn`, so well-known ids can be defined as `const`s or `static`s without parsing at runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not a valid `DocumentId`. In a const context, this is a compile-time error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DocumentId;
+    /// use std::str::FromStr;
+    ///
+    /// static ID: DocumentId = DocumentId::from_static("chatroom1");
+    /// assert_eq!(ID.as_ref(), "chatroom1");
+    /// assert_eq!(ID, DocumentId::from_str("chatroom1").unwrap());
+    /// ```
+    ///
+    /// ```rust,compile_fail
+    /// use firestore_path::DocumentId;
+    ///
+    /// static ID: DocumentId = DocumentId::from_static("chat/room1");
+    /// ```
+    pub const fn from_static(s: &'static str) -> Self {
+        let result = validate(s);
+        let is_valid = result.is_ok();
+        // `ErrorKind` has a destructor, which cannot be run in a const context.
+        std::mem::forget(result);
+        assert!(is_valid, "invalid DocumentId");
+        Self(IdString::Static(s))
+    }
+
+    /// Creates a new `DocumentId` from `s` with `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid `DocumentId` under `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentId,ParseOptions};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     DocumentId::from_str_with("chatroom1", &ParseOptions::strict())?,
+    ///     DocumentId::from_str("chatroom1")?
+    /// );
+    /// assert!(DocumentId::from_str("__chatroom1__").is_err());
+    /// assert!(DocumentId::from_str_with("__chatroom1__", &ParseOptions::lenient()).is_ok());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        validate_with(s, options)?;
+        Ok(Self(IdString::from(s.to_string())))
+    }
+
+    /// Creates a new `DocumentId` from a `u64`.
+    ///
+    /// The value is encoded as a 20-digit zero-padded decimal string, so the lexicographic order of the ids matches the numeric order of the values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DocumentId;
+    ///
+    /// let document_id = DocumentId::from_u64(123);
+    /// assert_eq!(document_id.as_ref(), "00000000000000000123");
+    /// assert_eq!(document_id.to_u64(), Some(123));
+    ///
+    /// assert!(DocumentId::from_u64(9) < DocumentId::from_u64(10));
+    /// ```
+    pub fn from_u64(value: u64) -> Self {
+        Self(IdString::from(format!("{:020}", value)))
+    }
+
+    /// Creates a new `DocumentId` from an `i64`.
+    ///
+    /// The value is offset by `2^63` and encoded in the same way as [`DocumentId::from_u64`], so the lexicographic order of the ids matches the numeric order of the values (negative values included).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DocumentId;
+    ///
+    /// let document_id = DocumentId::from_i64(-1);
+    /// assert_eq!(document_id.as_ref(), "09223372036854775807");
+    /// assert_eq!(document_id.to_i64(), Some(-1));
+    ///
+    /// assert!(DocumentId::from_i64(-1) < DocumentId::from_i64(0));
+    /// ```
+    pub fn from_i64(value: i64) -> Self {
+        Self::from_u64((value as u64) ^ (1 << 63))
+    }
+
+    /// Creates a new random `DocumentId`.
+    ///
+    /// The id is 20 alphanumeric characters like the auto-ids of the client SDKs (e.g. `add()` or `doc()` without arguments).
+    ///
+    /// This method requires the `random` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the random number generator of the OS is not available.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DocumentId;
+    ///
+    /// let document_id = DocumentId::random();
+    /// assert_eq!(document_id.as_ref().len(), 20);
+    /// assert!(document_id.as_ref().bytes().all(|b| b.is_ascii_alphanumeric()));
+    /// assert_ne!(document_id, DocumentId::random());
+    /// ```
+    #[cfg(feature = "random")]
+    pub fn random() -> Self {
+        const CHARS: &[u8; 62] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+        // The bytes greater than or equal to 248 (= 62 * 4) are discarded to avoid the modulo bias.
+        let mut s = String::with_capacity(20);
+        let mut buf = [0_u8; 40];
+        while s.len() < 20 {
+            getrandom::fill(&mut buf).expect("the random number generator to be available");
+            s.extend(
+                buf.iter()
+                    .filter(|b| **b < 248)
+                    .map(|b| CHARS[usize::from(*b) % CHARS.len()] as char)
+                    .take(20 - s.len()),
+            );
+        }
+        Self(IdString::from(s))
+    }
+
+    /// Returns the `u64` value of the `DocumentId` created by [`DocumentId::from_u64`].
+    ///
+    /// Returns `None` if the `DocumentId` is not a 20-digit zero-padded decimal string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentId;
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(DocumentId::from_u64(123).to_u64(), Some(123));
+    /// assert_eq!(DocumentId::from_str("123")?.to_u64(), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_u64(&self) -> Option<u64> {
+        let s = self.0.as_str();
+        if s.len() != 20 || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        s.parse::<u64>().ok()
+    }
+
+    /// Returns the `i64` value of the `DocumentId` created by [`DocumentId::from_i64`].
+    ///
+    /// Returns `None` if the `DocumentId` is not a 20-digit zero-padded decimal string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentId;
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(DocumentId::from_i64(-123).to_i64(), Some(-123));
+    /// assert_eq!(DocumentId::from_str("-123")?.to_i64(), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_i64(&self) -> Option<i64> {
+        self.to_u64().map(|value| (value ^ (1 << 63)) as i64)
+    }
+
+    /// Returns `true` if `s` is a valid `DocumentId`.
+    ///
+    /// This is the same as `DocumentId::validate(s).is_ok()` and does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DocumentId;
+    ///
+    /// assert!(DocumentId::is_valid("chatroom1"));
+    /// assert!(!DocumentId::is_valid(".."));
+    /// ```
+    pub fn is_valid(s: &str) -> bool {
+        validate(s).is_ok()
+    }
+
+    /// Checks that `s` is a valid `DocumentId` without creating a `DocumentId`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that `DocumentId::from_str(s)` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DocumentId;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(DocumentId::validate("chatroom1").is_ok());
+    /// assert_eq!(
+    ///     DocumentId::validate("..").unwrap_err(),
+    ///     DocumentId::from_str("..").unwrap_err()
+    /// );
+    /// ```
+    pub fn validate(s: &str) -> Result<(), Error> {
+        Ok(validate(s)?)
+    }
+}
+
+pub(crate) const fn validate(s: &str) -> Result<(), ErrorKind> {
+    validate_with(s, &ParseOptions::strict())
+}
+
+pub(crate) const fn validate_with(s: &str, options: &ParseOptions) -> Result<(), ErrorKind> {
+    // <https://firebase.google.com/docs/firestore/quotas#collections_documents_and_fields>
+    if s.is_empty() || (options.length_limits && s.len() > limits::MAX_ID_BYTES) {
+        return Err(ErrorKind::LengthOutOfBounds);
+    }
+
+    if id_string::contains(s, "/") {
+        return Err(ErrorKind::ContainsSlash);
+    }
+
+    if id_string::eq(s, ".") || id_string::eq(s, "..") {
+        return Err(ErrorKind::SinglePeriodOrDoublePeriods);
+    }
+
+    if options.reserved_ids && id_string::starts_with(s, "__") && id_string::ends_with(s, "__") {
+        return Err(ErrorKind::MatchesReservedIdPattern);
+    }
+
+    // The ids of the Datastore entities (`__id[0-9]+__`) also match `__.*__`, so they are rejected above.
+
+    Ok(())
+}
 
 impl std::convert::AsRef<str> for DocumentId {
     fn as_ref(&self) -> &str {
-        self.0.as_ref()
+        self.0.as_str()
     }
 }
 
@@ -48,26 +301,8 @@ impl std::convert::TryFrom<String> for DocumentId {
     type Error = Error;
 
     fn try_from(s: String) -> Result<Self, Self::Error> {
-        // <https://firebase.google.com/docs/firestore/quotas#collections_documents_and_fields>
-        if !(1..=1500).contains(&s.len()) {
-            return Err(Error::from(ErrorKind::LengthOutOfBounds));
-        }
-
-        if s.contains('/') {
-            return Err(Error::from(ErrorKind::ContainsSlash));
-        }
-
-        if s == "." || s == ".." {
-            return Err(Error::from(ErrorKind::SinglePeriodOrDoublePeriods));
-        }
-
-        if s.starts_with("__") && s.ends_with("__") {
-            return Err(Error::from(ErrorKind::MatchesReservedIdPattern));
-        }
-
-        // TODO: Datastore entities
-
-        Ok(Self(s))
+        validate(&s)?;
+        Ok(Self(IdString::from(s)))
     }
 }
 
@@ -101,6 +336,67 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_from_i64_and_to_i64() -> anyhow::Result<()> {
+        for (value, s) in [
+            (i64::MIN, "00000000000000000000"),
+            (-1, "09223372036854775807"),
+            (0, "09223372036854775808"),
+            (1, "09223372036854775809"),
+            (i64::MAX, "18446744073709551615"),
+        ] {
+            let document_id = DocumentId::from_i64(value);
+            assert_eq!(document_id.as_ref(), s);
+            assert_eq!(document_id.to_i64(), Some(value));
+            assert_eq!(DocumentId::from_str(s)?, document_id);
+        }
+
+        let mut values = vec![i64::MAX, 10, -10, 0, -1, 9, i64::MIN];
+        let mut document_ids = values
+            .iter()
+            .copied()
+            .map(DocumentId::from_i64)
+            .collect::<Vec<DocumentId>>();
+        values.sort();
+        document_ids.sort();
+        assert_eq!(
+            document_ids
+                .iter()
+                .map(|document_id| document_id.to_i64())
+                .collect::<Vec<Option<i64>>>(),
+            values.into_iter().map(Some).collect::<Vec<Option<i64>>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_u64_and_to_u64() -> anyhow::Result<()> {
+        for (value, s) in [
+            (u64::MIN, "00000000000000000000"),
+            (1, "00000000000000000001"),
+            (10, "00000000000000000010"),
+            (u64::MAX, "18446744073709551615"),
+        ] {
+            let document_id = DocumentId::from_u64(value);
+            assert_eq!(document_id.as_ref(), s);
+            assert_eq!(document_id.to_u64(), Some(value));
+            assert_eq!(DocumentId::from_str(s)?, document_id);
+        }
+
+        assert!(DocumentId::from_u64(9) < DocumentId::from_u64(10));
+        assert!(DocumentId::from_u64(10) < DocumentId::from_u64(u64::MAX));
+
+        for s in [
+            "1",
+            "0000000000000000000x",
+            "-0000000000000000001",
+            "99999999999999999999",
+        ] {
+            assert_eq!(DocumentId::from_str(s)?.to_u64(), None);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_impl_from_str_and_impl_try_from_string() -> anyhow::Result<()> {
         for (s, expected) in [
@@ -117,6 +413,9 @@ mod tests {
             ("__x__", false),
             ("__x", true),
             ("x__", true),
+            ("__id1__", false),
+            ("__id1234567890__", false),
+            ("id1__", true),
         ] {
             assert_eq!(DocumentId::from_str(s).is_ok(), expected);
             assert_eq!(DocumentId::try_from(s.to_string()).is_ok(), expected);
@@ -130,4 +429,47 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_from_static() -> anyhow::Result<()> {
+        const ID: DocumentId = DocumentId::from_static("chatroom1");
+        assert_eq!(ID, DocumentId::from_str("chatroom1")?);
+        assert_eq!(ID.to_string(), "chatroom1");
+        assert!(std::panic::catch_unwind(|| DocumentId::from_static("..")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> anyhow::Result<()> {
+        for s in [
+            "chatroom1".to_string(),
+            "".to_string(),
+            "..".to_string(),
+            "__x__".to_string(),
+            "x".repeat(1_501),
+        ] {
+            assert_eq!(
+                DocumentId::validate(&s).err(),
+                DocumentId::from_str(&s).err()
+            );
+            assert_eq!(DocumentId::is_valid(&s), DocumentId::from_str(&s).is_ok());
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn test_random() -> anyhow::Result<()> {
+        let ids = (0..1_000).map(|_| DocumentId::random()).collect::<Vec<_>>();
+        for id in &ids {
+            assert_eq!(id.as_ref().len(), 20);
+            assert!(id.as_ref().bytes().all(|b| b.is_ascii_alphanumeric()));
+            assert_eq!(&DocumentId::from_str(id.as_ref())?, id);
+        }
+        assert_eq!(
+            ids.iter().collect::<std::collections::HashSet<_>>().len(),
+            ids.len()
+        );
+        Ok(())
+    }
 }
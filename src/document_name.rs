@@ -1,8 +1,7 @@
-use std::str::FromStr;
-
 use crate::{
-    error::ErrorKind, CollectionId, CollectionName, CollectionPath, DatabaseName, DocumentId,
-    DocumentPath, Error, RootDocumentName,
+    error::ErrorKind, limits, stable_hash::StableHasher, AnyName, AnyPath, CollectionId,
+    CollectionName, CollectionPath, DatabaseId, DatabaseName, DocumentId, DocumentPath, Error,
+    ParseOptions, ProjectId, RootDocumentName, Segment,
 };
 
 /// A document name.
@@ -11,6 +10,10 @@ use crate::{
 ///
 /// `{database_name}/{document_path}`
 ///
+/// # Ordering
+///
+/// `DocumentName`s are ordered by the `RootDocumentName` and then segment by segment, which matches the ordering of references (`__name__`) in Firestore.
+///
 /// # Examples
 ///
 /// ```rust
@@ -54,10 +57,11 @@ use crate::{
 /// # }
 /// ```
 ///
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct DocumentName {
     document_path: DocumentPath,
     root_document_name: RootDocumentName,
+    string: String,
 }
 
 impl DocumentName {
@@ -93,10 +97,116 @@ impl DocumentName {
     where
         D: Into<RootDocumentName>,
     {
+        let root_document_name = root_document_name.into();
+        let string = format!("{}/{}", root_document_name, document_path);
         Self {
             document_path,
-            root_document_name: root_document_name.into(),
+            root_document_name,
+            string,
+        }
+    }
+
+    /// Creates a new `DocumentName`, checking the length of the name.
+    ///
+    /// `DocumentName::new` does not check the length, so use this method if `document_path` is not known to be short enough.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is longer than `limits::MAX_DOCUMENT_NAME_BYTES` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentPath,DatabaseName,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(
+    ///     DocumentName::try_new(database_name.clone(), document_path)?.to_string(),
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// );
+    ///
+    /// let document_path = DocumentPath::from_str(&format!("{0}/x/{0}/x/{0}/x/{0}/x/{0}/x", "x".repeat(1_500)))?;
+    /// assert!(DocumentName::try_new(database_name, document_path).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn try_new<D>(root_document_name: D, document_path: DocumentPath) -> Result<Self, Error>
+    where
+        D: Into<RootDocumentName>,
+    {
+        let name = Self::new(root_document_name, document_path);
+        if name.string.len() > limits::MAX_DOCUMENT_NAME_BYTES {
+            return Err(Error::from(ErrorKind::LengthOutOfBounds));
         }
+        Ok(name)
+    }
+
+    /// Returns an iterator over the ancestors of this `DocumentName`.
+    ///
+    /// The iterator yields the parent `CollectionName`, the parent `DocumentName`, ... and the `RootDocumentName` in that order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionName,DocumentName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.ancestors().collect::<Vec<AnyName>>(),
+    ///     vec![
+    ///         AnyName::from(CollectionName::from_str(
+    ///             "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    ///         )?),
+    ///         AnyName::from(DocumentName::from_str(
+    ///             "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///         )?),
+    ///         AnyName::from(CollectionName::from_str(
+    ///             "projects/my-project/databases/my-database/documents/chatrooms"
+    ///         )?),
+    ///         AnyName::from(RootDocumentName::from_str(
+    ///             "projects/my-project/databases/my-database/documents"
+    ///         )?),
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn ancestors(&self) -> impl Iterator<Item = AnyName> {
+        std::iter::successors(Some(AnyName::from(self.parent())), AnyName::parent)
+    }
+
+    /// Returns the canonical string representation of this `DocumentName`.
+    ///
+    /// The string is kept alongside the parsed components, so this does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.as_str(),
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn as_str(&self) -> &str {
+        &self.string
     }
 
     /// Creates a new `CollectionName` from this `DocumentName` and `collection_path`.
@@ -170,175 +280,186 @@ impl DocumentName {
         self.document_path.collection_id()
     }
 
-    /// Returns the `DatabaseName` of this `DocumentName`.
+    /// Returns the `CollectionId` at `index` (0-based, from the root) of this `DocumentName`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{DatabaseName,DocumentName,DocumentPath};
+    /// use firestore_path::{CollectionId,DocumentName};
     /// use std::str::FromStr;
     ///
     /// let document_name = DocumentName::from_str(
-    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    /// )?;
+    /// assert_eq!(document_name.collection_id_at(0), Some(&CollectionId::from_str("chatrooms")?));
+    /// assert_eq!(document_name.collection_id_at(1), Some(&CollectionId::from_str("messages")?));
+    /// assert_eq!(document_name.collection_id_at(2), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn collection_id_at(&self, index: usize) -> Option<&CollectionId> {
+        self.collection_ids().nth(index)
+    }
+
+    /// Returns an iterator over the `CollectionId`s of this `DocumentName` from the root to the leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
     /// )?;
     /// assert_eq!(
-    ///     document_name.database_name(),
-    ///     &DatabaseName::from_str("projects/my-project/databases/my-database")?
+    ///     document_name.collection_ids().collect::<Vec<&CollectionId>>(),
+    ///     vec![
+    ///         &CollectionId::from_str("chatrooms")?,
+    ///         &CollectionId::from_str("messages")?
+    ///     ]
     /// );
     /// #     Ok(())
     /// # }
     /// ```
-    ///
-    pub fn database_name(&self) -> &DatabaseName {
-        self.root_document_name.as_database_name()
+    pub fn collection_ids(&self) -> impl Iterator<Item = &CollectionId> {
+        self.document_path.collection_ids()
     }
 
-    /// Creates a new `DocumentName` from this `DocumentName` and `document_path`.
+    /// Returns the `DatabaseName` of this `DocumentName`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionPath,DocumentName,DocumentPath};
+    /// use firestore_path::{DatabaseName,DocumentName,DocumentPath};
     /// use std::str::FromStr;
     ///
     /// let document_name = DocumentName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
     /// )?;
     /// assert_eq!(
-    ///     document_name.doc("messages/message1")?,
-    ///     DocumentName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
-    ///     )?
-    /// );
-    /// assert_eq!(
-    ///     document_name.doc("messages/message1/col/doc")?,
-    ///     DocumentName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col/doc"
-    ///     )?
-    /// );
-    /// assert_eq!(
-    ///     document_name.doc(DocumentPath::from_str("messages/message1")?)?,
-    ///     DocumentName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
-    ///     )?
-    /// );
-    /// assert_eq!(
-    ///     document_name.doc(DocumentPath::from_str("messages/message1/col/doc")?)?,
-    ///     DocumentName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col/doc"
-    ///     )?
+    ///     document_name.database_name(),
+    ///     &DatabaseName::from_str("projects/my-project/databases/my-database")?
     /// );
     /// #     Ok(())
     /// # }
     /// ```
     ///
-    pub fn doc<E, T>(&self, document_path: T) -> Result<DocumentName, Error>
-    where
-        E: std::fmt::Display,
-        T: TryInto<DocumentPath, Error = E>,
-    {
-        self.clone().into_doc(document_path)
+    pub fn database_name(&self) -> &DatabaseName {
+        self.root_document_name.as_database_name()
     }
 
-    /// Returns the `DocumentId` of this `DocumentName`.
+    /// Returns the depth (the number of collections) of this `DocumentName`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{DocumentId,DocumentName};
+    /// use firestore_path::DocumentName;
     /// use std::str::FromStr;
     ///
     /// let document_name = DocumentName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
     /// )?;
-    /// assert_eq!(document_name.document_id(), &DocumentId::from_str("chatroom1")?);
+    /// assert_eq!(document_name.depth(), 1);
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    /// )?;
+    /// assert_eq!(document_name.depth(), 2);
     /// #     Ok(())
     /// # }
     /// ```
-    ///
-    pub fn document_id(&self) -> &DocumentId {
-        self.document_path.document_id()
+    pub fn depth(&self) -> usize {
+        self.document_path.depth()
     }
 
-    /// Returns the `DocumentPath` of this `DocumentName`.
+    /// Creates a new `DocumentName` by appending `(collection_id, document_id)` pairs to this `DocumentName`.
+    ///
+    /// Returns a clone of this `DocumentName` if `pairs` is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the ids cannot be converted or the new `DocumentName` is too deep or too long.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{DocumentName,DocumentPath};
+    /// use firestore_path::DocumentName;
     /// use std::str::FromStr;
     ///
     /// let document_name = DocumentName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
     /// )?;
     /// assert_eq!(
-    ///     document_name.document_path(),
-    ///     &DocumentPath::from_str("chatrooms/chatroom1")?
+    ///     document_name.descend([("messages", "message1")])?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    ///     )?
     /// );
     /// #     Ok(())
     /// # }
     /// ```
-    ///
-    pub fn document_path(&self) -> &DocumentPath {
-        &self.document_path
+    pub fn descend<C, D, I>(&self, pairs: I) -> Result<DocumentName, Error>
+    where
+        C: TryInto<CollectionId>,
+        C::Error: std::fmt::Display,
+        D: TryInto<DocumentId>,
+        D::Error: std::fmt::Display,
+        I: IntoIterator<Item = (C, D)>,
+    {
+        DocumentName::try_new(
+            self.root_document_name.clone(),
+            self.document_path.descend(pairs)?,
+        )
     }
 
-    /// Creates a new `CollectionName` from this `DocumentName` and `collection_path`.
+    /// Returns the range of the names of all descendants of this `DocumentName`.
+    ///
+    /// The range is `"{document_name}/".."{document_name}0"` (`'0'` is the successor of `'/'`),
+    /// which is suitable for `__name__ >= start AND __name__ < end` filters and for scans of key-value stores sorted by string.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionId,CollectionName,CollectionPath,DocumentName};
+    /// use firestore_path::DocumentName;
     /// use std::str::FromStr;
     ///
     /// let document_name = DocumentName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
     /// )?;
+    /// let range = document_name.descendants_range();
     /// assert_eq!(
-    ///     document_name.clone().into_collection("messages")?,
-    ///     CollectionName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
-    ///     )?
-    /// );
-    /// assert_eq!(
-    ///     document_name.clone().into_collection("messages/message1/col")?,
-    ///     CollectionName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col"
-    ///     )?
-    /// );
-    /// assert_eq!(
-    ///     document_name.clone().into_collection(CollectionId::from_str("messages")?)?,
-    ///     CollectionName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
-    ///     )?
+    ///     range.start,
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/"
     /// );
     /// assert_eq!(
-    ///     document_name.into_collection(CollectionPath::from_str("messages/message1/col")?)?,
-    ///     CollectionName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col"
-    ///     )?
+    ///     range.end,
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom10"
     /// );
+    /// assert!(range.contains(
+    ///     &"projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1".to_string()
+    /// ));
+    /// assert!(!range.contains(
+    ///     &"projects/my-project/databases/my-database/documents/chatrooms/chatroom10/messages/message1".to_string()
+    /// ));
     /// #     Ok(())
     /// # }
     /// ```
-    ///
-    pub fn into_collection<E, T>(self, collection_path: T) -> Result<CollectionName, Error>
-    where
-        E: std::fmt::Display,
-        T: TryInto<CollectionPath, Error = E>,
-    {
-        Ok(CollectionName::new(
-            self.root_document_name,
-            self.document_path.into_collection(collection_path)?,
-        ))
+    pub fn descendants_range(&self) -> std::ops::Range<String> {
+        let prefix = self.prefix_str();
+        let end = format!("{}0", &prefix[..prefix.len() - 1]);
+        prefix..end
     }
 
-    /// Creates a new `DocumentName` by consuming the `DocumentName` with the provided `document_path`.
+    /// Creates a new `DocumentName` from this `DocumentName` and `document_path`.
     ///
     /// # Examples
     ///
@@ -351,25 +472,25 @@ impl DocumentName {
     ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
     /// )?;
     /// assert_eq!(
-    ///     document_name.clone().into_doc("messages/message1")?,
+    ///     document_name.doc("messages/message1")?,
     ///     DocumentName::from_str(
     ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
     ///     )?
     /// );
     /// assert_eq!(
-    ///     document_name.clone().into_doc("messages/message1/col/doc")?,
+    ///     document_name.doc("messages/message1/col/doc")?,
     ///     DocumentName::from_str(
     ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col/doc"
     ///     )?
     /// );
     /// assert_eq!(
-    ///     document_name.clone().into_doc(DocumentPath::from_str("messages/message1")?)?,
+    ///     document_name.doc(DocumentPath::from_str("messages/message1")?)?,
     ///     DocumentName::from_str(
     ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
     ///     )?
     /// );
     /// assert_eq!(
-    ///     document_name.into_doc(DocumentPath::from_str("messages/message1/col/doc")?)?,
+    ///     document_name.doc(DocumentPath::from_str("messages/message1/col/doc")?)?,
     ///     DocumentName::from_str(
     ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col/doc"
     ///     )?
@@ -378,429 +499,2498 @@ impl DocumentName {
     /// # }
     /// ```
     ///
-    pub fn into_doc<E, T>(self, document_path: T) -> Result<DocumentName, Error>
+    pub fn doc<E, T>(&self, document_path: T) -> Result<DocumentName, Error>
     where
         E: std::fmt::Display,
         T: TryInto<DocumentPath, Error = E>,
     {
-        Ok(DocumentName::new(
-            self.root_document_name,
-            self.document_path.into_doc(document_path)?,
-        ))
+        self.clone().into_doc(document_path)
     }
 
-    /// Consumes the `DocumentName`, returning the parent `CollectionName`.
+    /// Returns the `DocumentId` of this `DocumentName`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionName,DocumentName};
+    /// use firestore_path::{DocumentId,DocumentName};
     /// use std::str::FromStr;
     ///
     /// let document_name = DocumentName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
     /// )?;
-    /// assert_eq!(
-    ///     document_name.into_parent(),
-    ///     CollectionName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms"
-    ///     )?
-    /// );
+    /// assert_eq!(document_name.document_id(), &DocumentId::from_str("chatroom1")?);
     /// #     Ok(())
     /// # }
     /// ```
     ///
-    pub fn into_parent(self) -> CollectionName {
-        CollectionName::new(
-            self.root_document_name,
-            CollectionPath::from(self.document_path),
-        )
+    pub fn document_id(&self) -> &DocumentId {
+        self.document_path.document_id()
     }
 
-    /// Consumes the `DocumentName`, returning the parent `DocumentName`.
+    /// Returns the `DocumentId` at `index` (0-based, from the root) of this `DocumentName`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionName,DocumentName};
+    /// use firestore_path::{DocumentId,DocumentName};
     /// use std::str::FromStr;
     ///
     /// let document_name = DocumentName::from_str(
-    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
-    /// )?;
-    /// assert_eq!(document_name.into_parent_document_name(), None);
-    /// let document_name = DocumentName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
     /// )?;
-    /// assert_eq!(
-    ///     document_name.into_parent_document_name(),
-    ///     Some(DocumentName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
-    ///     )?)
-    /// );
+    /// assert_eq!(document_name.document_id_at(0), Some(&DocumentId::from_str("chatroom1")?));
+    /// assert_eq!(document_name.document_id_at(1), Some(&DocumentId::from_str("message1")?));
+    /// assert_eq!(document_name.document_id_at(2), None);
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn into_parent_document_name(self) -> Option<DocumentName> {
-        self.document_path
-            .into_parent()
-            .into_parent()
-            .map(|document_path| DocumentName::new(self.root_document_name, document_path))
+    pub fn document_id_at(&self, index: usize) -> Option<&DocumentId> {
+        self.document_ids().nth(index)
     }
 
-    /// Consumes the `DocumentName`, returning the `RootDocumentName`.
+    /// Returns an iterator over the `DocumentId`s of this `DocumentName` from the root to the leaf.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{DocumentName,RootDocumentName};
+    /// use firestore_path::{DocumentId,DocumentName};
     /// use std::str::FromStr;
     ///
     /// let document_name = DocumentName::from_str(
-    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
     /// )?;
-    /// let root_document_name = document_name.into_root_document_name();
     /// assert_eq!(
-    ///     root_document_name,
-    ///     RootDocumentName::from_str(
-    ///         "projects/my-project/databases/my-database/documents"
-    ///     )?
+    ///     document_name.document_ids().collect::<Vec<&DocumentId>>(),
+    ///     vec![
+    ///         &DocumentId::from_str("chatroom1")?,
+    ///         &DocumentId::from_str("message1")?
+    ///     ]
     /// );
     /// #     Ok(())
     /// # }
-    pub fn into_root_document_name(self) -> RootDocumentName {
-        self.root_document_name
+    /// ```
+    pub fn document_ids(&self) -> impl Iterator<Item = &DocumentId> {
+        self.document_path.document_ids()
     }
 
-    /// Returns the parent `CollectionName` of this `DocumentName`.
+    /// Returns the `DocumentPath` of this `DocumentName`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionName,DocumentName};
+    /// use firestore_path::{DocumentName,DocumentPath};
     /// use std::str::FromStr;
     ///
     /// let document_name = DocumentName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
     /// )?;
     /// assert_eq!(
-    ///     document_name.parent(),
+    ///     document_name.document_path(),
+    ///     &DocumentPath::from_str("chatrooms/chatroom1")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn document_path(&self) -> &DocumentPath {
+        &self.document_path
+    }
+
+    /// Returns the length in bytes of the canonical string representation of this `DocumentName`.
+    ///
+    /// This is the same as `to_string().len()` but does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(document_name.encoded_len(), document_name.to_string().len());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn encoded_len(&self) -> usize {
+        self.string.len()
+    }
+
+    /// Returns `true` if this `DocumentName` and `other` have the same relative path, ignoring the `DatabaseName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert!(document_name.eq_ignore_database(&DocumentName::from_str(
+    ///     "projects/my-project/databases/(default)/documents/chatrooms/chatroom1"
+    /// )?));
+    /// assert!(!document_name.eq_ignore_database(&DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom2"
+    /// )?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn eq_ignore_database(&self, other: &DocumentName) -> bool {
+        self.document_path == other.document_path
+    }
+
+    /// Creates a new `DocumentName` from `root_document_name` and `(CollectionId, DocumentId)` pairs ordered from the root to the leaf.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pairs` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,DatabaseName,DocumentId,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let document_name = DocumentName::from_pairs(
+    ///     database_name,
+    ///     vec![
+    ///         (CollectionId::from_str("chatrooms")?, DocumentId::from_str("chatroom1")?),
+    ///         (CollectionId::from_str("messages")?, DocumentId::from_str("message1")?),
+    ///     ],
+    /// )?;
+    /// assert_eq!(
+    ///     document_name,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_pairs<D, I>(root_document_name: D, pairs: I) -> Result<Self, Error>
+    where
+        D: Into<RootDocumentName>,
+        I: IntoIterator<Item = (CollectionId, DocumentId)>,
+    {
+        Self::try_new(root_document_name, DocumentPath::from_pairs(pairs)?)
+    }
+
+    /// Creates a new `DocumentName` from `project_id`, `database_id` and `document_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the parts cannot be converted or the name is too long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseId,DocumentPath,DocumentName,ProjectId};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_parts("my-project", "my-database", "chatrooms/chatroom1")?;
+    /// assert_eq!(
+    ///     document_name,
+    ///     DocumentName::from_str("projects/my-project/databases/my-database/documents/chatrooms/chatroom1")?
+    /// );
+    /// assert_eq!(
+    ///     DocumentName::from_parts(
+    ///         ProjectId::from_str("my-project")?,
+    ///         DatabaseId::from_str("my-database")?,
+    ///         DocumentPath::from_str("chatrooms/chatroom1")?
+    ///     )?,
+    ///     document_name
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_parts<P, D, T>(
+        project_id: P,
+        database_id: D,
+        document_path: T,
+    ) -> Result<Self, Error>
+    where
+        P: TryInto<ProjectId>,
+        P::Error: std::fmt::Display,
+        D: TryInto<DatabaseId>,
+        D::Error: std::fmt::Display,
+        T: TryInto<DocumentPath>,
+        T::Error: std::fmt::Display,
+    {
+        let project_id = project_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::ProjectIdConversion(e.to_string())))?;
+        let database_id = database_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::DatabaseIdConversion(e.to_string())))?;
+        DatabaseName::new(project_id, database_id).into_doc(document_path)
+    }
+
+    /// Creates a new `DocumentName` from `s` with `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid `DocumentName` under `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,ParseOptions};
+    /// use std::str::FromStr;
+    ///
+    /// let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1";
+    /// assert_eq!(
+    ///     DocumentName::from_str_with(s, &ParseOptions::strict())?,
+    ///     DocumentName::from_str(s)?
+    /// );
+    ///
+    /// let s = "projects/my-project/databases/my-database/documents/__chatrooms__/chatroom1";
+    /// assert!(DocumentName::from_str(s).is_err());
+    /// assert!(DocumentName::from_str_with(s, &ParseOptions::lenient()).is_ok());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        options.parse_normalized(s, |s| Self::try_from_string_with(s.to_string(), options))
+    }
+
+    /// Creates a new `CollectionName` from this `DocumentName` and `collection_path`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,CollectionName,CollectionPath,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.clone().into_collection("messages")?,
     ///     CollectionName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms"
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
     ///     )?
     /// );
     /// assert_eq!(
-    ///     document_name.parent(),
+    ///     document_name.clone().into_collection("messages/message1/col")?,
     ///     CollectionName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms"
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col"
+    ///     )?
+    /// );
+    /// assert_eq!(
+    ///     document_name.clone().into_collection(CollectionId::from_str("messages")?)?,
+    ///     CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    ///     )?
+    /// );
+    /// assert_eq!(
+    ///     document_name.into_collection(CollectionPath::from_str("messages/message1/col")?)?,
+    ///     CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col"
     ///     )?
     /// );
     /// #     Ok(())
     /// # }
     /// ```
     ///
-    pub fn parent(&self) -> CollectionName {
-        self.clone().into_parent()
+    pub fn into_collection<E, T>(self, collection_path: T) -> Result<CollectionName, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<CollectionPath, Error = E>,
+    {
+        CollectionName::try_new(
+            self.root_document_name,
+            self.document_path.into_collection(collection_path)?,
+        )
     }
 
-    /// Returns the parent `DocumentName` of this `DocumentName`.
+    /// Creates a new `DocumentName` by consuming the `DocumentName` with the provided `document_path`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionName,DocumentName};
+    /// use firestore_path::{CollectionPath,DocumentName,DocumentPath};
     /// use std::str::FromStr;
     ///
     /// let document_name = DocumentName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
     /// )?;
-    /// assert_eq!(document_name.parent_document_name(), None);
+    /// assert_eq!(
+    ///     document_name.clone().into_doc("messages/message1")?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    ///     )?
+    /// );
+    /// assert_eq!(
+    ///     document_name.clone().into_doc("messages/message1/col/doc")?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col/doc"
+    ///     )?
+    /// );
+    /// assert_eq!(
+    ///     document_name.clone().into_doc(DocumentPath::from_str("messages/message1")?)?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    ///     )?
+    /// );
+    /// assert_eq!(
+    ///     document_name.into_doc(DocumentPath::from_str("messages/message1/col/doc")?)?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col/doc"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn into_doc<E, T>(self, document_path: T) -> Result<DocumentName, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DocumentPath, Error = E>,
+    {
+        DocumentName::try_new(
+            self.root_document_name,
+            self.document_path.into_doc(document_path)?,
+        )
+    }
+
+    /// Consumes the `DocumentName`, returning the `(CollectionId, DocumentId)` pairs ordered from the root to the leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,DocumentId,DocumentName};
+    /// use std::str::FromStr;
+    ///
     /// let document_name = DocumentName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
     /// )?;
     /// assert_eq!(
-    ///     document_name.parent_document_name(),
-    ///     Some(DocumentName::from_str(
-    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
-    ///     )?)
+    ///     document_name.into_pairs(),
+    ///     vec![
+    ///         (CollectionId::from_str("chatrooms")?, DocumentId::from_str("chatroom1")?),
+    ///         (CollectionId::from_str("messages")?, DocumentId::from_str("message1")?),
+    ///     ]
     /// );
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn parent_document_name(&self) -> Option<DocumentName> {
-        self.clone().into_parent_document_name()
+    pub fn into_pairs(self) -> Vec<(CollectionId, DocumentId)> {
+        self.document_path.into_pairs()
     }
 
-    /// Returns the `RootDocumentName` of this `DocumentName`.
+    /// Consumes the `DocumentName`, returning the parent `CollectionName`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{DocumentName,RootDocumentName};
+    /// use firestore_path::{CollectionName,DocumentName};
     /// use std::str::FromStr;
     ///
     /// let document_name = DocumentName::from_str(
     ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
     /// )?;
-    /// let root_document_name = document_name.root_document_name();
     /// assert_eq!(
-    ///     root_document_name,
-    ///     &RootDocumentName::from_str(
-    ///         "projects/my-project/databases/my-database/documents"
+    ///     document_name.into_parent(),
+    ///     CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms"
     ///     )?
     /// );
     /// #     Ok(())
     /// # }
-    pub fn root_document_name(&self) -> &RootDocumentName {
-        &self.root_document_name
-    }
-}
-
-impl std::convert::From<DocumentName> for DatabaseName {
-    fn from(document_name: DocumentName) -> Self {
-        Self::from(document_name.root_document_name)
+    /// ```
+    ///
+    pub fn into_parent(self) -> CollectionName {
+        CollectionName::new(
+            self.root_document_name,
+            CollectionPath::from(self.document_path),
+        )
     }
-}
 
-impl std::convert::From<DocumentName> for DocumentId {
-    fn from(document_name: DocumentName) -> Self {
-        Self::from(document_name.document_path)
+    /// Consumes the `DocumentName`, returning the parent `CollectionName` and the `DocumentId`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,DocumentId,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.into_parent_and_id(),
+    ///     (
+    ///         CollectionName::from_str(
+    ///             "projects/my-project/databases/my-database/documents/chatrooms"
+    ///         )?,
+    ///         DocumentId::from_str("chatroom1")?
+    ///     )
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_parent_and_id(self) -> (CollectionName, DocumentId) {
+        let (collection_path, document_id) = self.document_path.into_parent_and_id();
+        (
+            CollectionName::new(self.root_document_name, collection_path),
+            document_id,
+        )
     }
-}
 
-impl std::convert::From<DocumentName> for DocumentPath {
-    fn from(document_name: DocumentName) -> Self {
-        document_name.document_path
-    }
-}
+    /// Consumes the `DocumentName`, returning the parent `DocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(document_name.into_parent_document_name(), None);
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.into_parent_document_name(),
+    ///     Some(DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?)
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_parent_document_name(self) -> Option<DocumentName> {
+        self.document_path
+            .into_parent()
+            .into_parent()
+            .map(|document_path| DocumentName::new(self.root_document_name, document_path))
+    }
+
+    /// Consumes the `DocumentName`, returning the `RootDocumentName` and the `DocumentPath`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,DocumentPath,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.into_parts(),
+    ///     (
+    ///         RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?,
+    ///         DocumentPath::from_str("chatrooms/chatroom1")?
+    ///     )
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_parts(self) -> (RootDocumentName, DocumentPath) {
+        (self.root_document_name, self.document_path)
+    }
+
+    /// Consumes the `DocumentName`, returning the `RootDocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// let root_document_name = document_name.into_root_document_name();
+    /// assert_eq!(
+    ///     root_document_name,
+    ///     RootDocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    pub fn into_root_document_name(self) -> RootDocumentName {
+        self.root_document_name
+    }
+
+    /// Converts this `DocumentName` into its canonical string representation.
+    ///
+    /// The stored canonical string is returned as is, without formatting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.into_string(),
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn into_string(self) -> String {
+        self.string
+    }
+
+    /// Returns `true` if this `DocumentName` is in the default database `(default)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(DocumentName::from_str("projects/my-project/databases/(default)/documents/chatrooms/chatroom1")?.is_default_database());
+    /// assert!(!DocumentName::from_str("projects/my-project/databases/my-database/documents/chatrooms/chatroom1")?.is_default_database());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_default_database(&self) -> bool {
+        self.database_name().is_default_database()
+    }
+
+    /// Returns `true` if this `DocumentName` belongs to the collection group `collection_id`.
+    ///
+    /// That is, the `CollectionId` of this `DocumentName` or any of its ancestors is equal to `collection_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    /// )?;
+    /// assert!(document_name.is_in_collection_group(&CollectionId::from_str("chatrooms")?));
+    /// assert!(document_name.is_in_collection_group(&CollectionId::from_str("messages")?));
+    /// assert!(!document_name.is_in_collection_group(&CollectionId::from_str("users")?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_in_collection_group(&self, collection_id: &CollectionId) -> bool {
+        self.document_path.is_in_collection_group(collection_id)
+    }
+
+    /// Returns `true` if `s` is a valid `DocumentName`.
+    ///
+    /// This is the same as `DocumentName::validate(s).is_ok()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DocumentName;
+    ///
+    /// assert!(DocumentName::is_valid("projects/my-project/databases/my-database/documents/chatrooms/chatroom1"));
+    /// assert!(!DocumentName::is_valid("projects/my-project/databases/my-database/documents/chatrooms"));
+    /// ```
+    pub fn is_valid(s: &str) -> bool {
+        Self::validate(s).is_ok()
+    }
+
+    /// Creates a new `DocumentName` by consuming the `DocumentName` and transforming the `DocumentId` with `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1-v1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.map_document_id(|document_id| document_id.as_ref().replace("-v1", "-v2"))?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1-v2"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn map_document_id<E, F, T>(self, f: F) -> Result<DocumentName, Error>
+    where
+        E: std::fmt::Display,
+        F: FnOnce(DocumentId) -> T,
+        T: TryInto<DocumentId, Error = E>,
+    {
+        DocumentName::try_new(
+            self.root_document_name,
+            self.document_path.map_document_id(f)?,
+        )
+    }
+
+    /// Returns the parent `CollectionName` of this `DocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.parent(),
+    ///     CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms"
+    ///     )?
+    /// );
+    /// assert_eq!(
+    ///     document_name.parent(),
+    ///     CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn parent(&self) -> CollectionName {
+        self.clone().into_parent()
+    }
+
+    /// Returns the ancestor `n` levels above this `DocumentName`.
+    ///
+    /// `parent_at(0)` returns this `DocumentName` itself and `parent_at(1)` returns the parent `CollectionName`.
+    /// Returns `None` if `n` is greater than the number of ancestors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionName,DocumentName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.parent_at(2),
+    ///     Some(AnyName::from(DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?))
+    /// );
+    /// assert_eq!(
+    ///     document_name.parent_at(3),
+    ///     Some(AnyName::from(CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms"
+    ///     )?))
+    /// );
+    /// assert_eq!(
+    ///     document_name.parent_at(4),
+    ///     Some(AnyName::from(RootDocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents"
+    ///     )?))
+    /// );
+    /// assert_eq!(document_name.parent_at(5), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parent_at(&self, n: usize) -> Option<AnyName> {
+        if n == self.depth() * 2 {
+            return Some(AnyName::from(self.root_document_name.clone()));
+        }
+        self.document_path
+            .parent_at(n)
+            .map(|any_path| match any_path {
+                AnyPath::Collection(collection_path) => AnyName::from(CollectionName::new(
+                    self.root_document_name.clone(),
+                    collection_path,
+                )),
+                AnyPath::Document(document_path) => AnyName::from(DocumentName::new(
+                    self.root_document_name.clone(),
+                    document_path,
+                )),
+            })
+    }
+
+    /// Returns the parent `DocumentName` of this `DocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(document_name.parent_document_name(), None);
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.parent_document_name(),
+    ///     Some(DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?)
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parent_document_name(&self) -> Option<DocumentName> {
+        self.clone().into_parent_document_name()
+    }
+
+    /// Returns the canonical prefix string (with a trailing slash) of the names of the descendants of this `DocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// let prefix = document_name.prefix_str();
+    /// assert_eq!(
+    ///     prefix,
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/"
+    /// );
+    /// assert!("projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages".starts_with(&prefix));
+    /// assert!(!"projects/my-project/databases/my-database/documents/chatrooms/chatroom10/messages/message1".starts_with(&prefix));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn prefix_str(&self) -> String {
+        format!("{}/", self)
+    }
+
+    /// Creates a new `DocumentName` by replacing the ancestor `from` of this `DocumentName` with `to`.
+    ///
+    /// `from` and `to` may belong to different databases.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `from` is not an ancestor of this `DocumentName` or the resulting name is too long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/tenants/tenant1/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.rebase(
+    ///         &DocumentName::from_str(
+    ///             "projects/my-project/databases/my-database/documents/tenants/tenant1"
+    ///         )?,
+    ///         &DocumentName::from_str(
+    ///             "projects/my-project/databases/(default)/documents/tenants/tenant2"
+    ///         )?
+    ///     )?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/(default)/documents/tenants/tenant2/chatrooms/chatroom1"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn rebase(&self, from: &DocumentName, to: &DocumentName) -> Result<DocumentName, Error> {
+        let document_path = self.strip_prefix(from)?;
+        to.doc(document_path)
+    }
+
+    /// Returns the `RootDocumentName` of this `DocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// let root_document_name = document_name.root_document_name();
+    /// assert_eq!(
+    ///     root_document_name,
+    ///     &RootDocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    pub fn root_document_name(&self) -> &RootDocumentName {
+        &self.root_document_name
+    }
+
+    /// Returns the value of the `x-goog-request-params` header that routes requests to the database of this `DocumentName`.
+    ///
+    /// See [`DatabaseName::routing_header_value`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.routing_header_value(),
+    ///     "database=projects%2Fmy-project%2Fdatabases%2Fmy-database"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn routing_header_value(&self) -> String {
+        self.database_name().routing_header_value()
+    }
+
+    /// Returns `true` if this `DocumentName` and `other` are in the same database.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert!(document_name.same_database(&DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?));
+    /// assert!(!document_name.same_database(&DocumentName::from_str(
+    ///     "projects/my-project/databases/(default)/documents/chatrooms/chatroom1"
+    /// )?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn same_database(&self, other: &DocumentName) -> bool {
+        self.database_name() == other.database_name()
+    }
+
+    /// Returns `true` if this `DocumentName` and `other` are in the same project.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert!(document_name.same_project(&DocumentName::from_str(
+    ///     "projects/my-project/databases/(default)/documents/chatrooms/chatroom1"
+    /// )?));
+    /// assert!(!document_name.same_project(&DocumentName::from_str(
+    ///     "projects/my-project2/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn same_project(&self, other: &DocumentName) -> bool {
+        self.database_name().project_id() == other.database_name().project_id()
+    }
+
+    /// Returns an iterator over the segments of the `DocumentPath` of this `DocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name
+    ///         .segments()
+    ///         .map(|segment| segment.to_string())
+    ///         .collect::<Vec<String>>(),
+    ///     vec!["chatrooms", "chatroom1"]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn segments(&self) -> impl Iterator<Item = Segment<'_>> {
+        self.document_path.segments()
+    }
+
+    /// Creates a new shard `DocumentName` (`{document_name}/shards/{shard_index}`) of the distributed counter.
+    ///
+    /// <https://firebase.google.com/docs/firestore/solutions/counters>
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `shard_index` is not less than `num_shards`, or if the shard `DocumentName` exceeds the depth or length limits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/counters/counter1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.shard(2, 10)?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/counters/counter1/shards/2"
+    ///     )?
+    /// );
+    /// assert!(document_name.shard(10, 10).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn shard(&self, shard_index: usize, num_shards: usize) -> Result<DocumentName, Error> {
+        if shard_index >= num_shards {
+            return Err(Error::from(ErrorKind::ShardIndexOutOfBounds));
+        }
+        self.collection("shards")?.into_doc(shard_index.to_string())
+    }
+
+    /// Returns an iterator over all shard `DocumentName`s (`{document_name}/shards/{0..num_shards}`) of the distributed counter.
+    ///
+    /// <https://firebase.google.com/docs/firestore/solutions/counters>
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any shard `DocumentName` exceeds the depth or length limits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/counters/counter1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.shards(2)?.collect::<Vec<DocumentName>>(),
+    ///     vec![
+    ///         DocumentName::from_str(
+    ///             "projects/my-project/databases/my-database/documents/counters/counter1/shards/0"
+    ///         )?,
+    ///         DocumentName::from_str(
+    ///             "projects/my-project/databases/my-database/documents/counters/counter1/shards/1"
+    ///         )?,
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn shards(&self, num_shards: usize) -> Result<impl Iterator<Item = DocumentName>, Error> {
+        let collection_name = self.collection("shards")?;
+        // The last shard has the longest name, so checking it is enough for all shards.
+        if let Some(last_shard_index) = num_shards.checked_sub(1) {
+            collection_name.doc(last_shard_index.to_string())?;
+        }
+        Ok((0..num_shards).map(move |shard_index| {
+            collection_name
+                .doc(shard_index.to_string())
+                .expect("shard index to be checked")
+        }))
+    }
+
+    /// Creates a new `DocumentName` with the same parent as this `DocumentName` and the provided `document_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentId,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.sibling("chatroom2")?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom2"
+    ///     )?
+    /// );
+    /// assert_eq!(
+    ///     document_name.sibling(DocumentId::from_str("chatroom3")?)?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom3"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn sibling<E, T>(&self, document_id: T) -> Result<DocumentName, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DocumentId, Error = E>,
+    {
+        self.parent().into_doc(document_id)
+    }
+
+    /// Returns a 64-bit hash of the canonical string representation of this `DocumentName`.
+    ///
+    /// Unlike `std::hash::Hash`, the value is stable across versions, platforms and processes.
+    /// It is the 64-bit FNV-1a hash of the UTF-8 bytes of `to_string()`, so it can be persisted or computed in other languages.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(document_name.stable_hash(), 0x15159496b290bdc7);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn stable_hash(&self) -> u64 {
+        StableHasher::hash(|hasher| self.write_to(hasher))
+    }
+
+    /// Returns the `DocumentPath` relative to the ancestor `prefix`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `prefix` is in a different database or is not an ancestor of this `DocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.strip_prefix(&DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?)?,
+    ///     DocumentPath::from_str("messages/message1")?
+    /// );
+    /// assert!(document_name
+    ///     .strip_prefix(&DocumentName::from_str(
+    ///         "projects/my-project/databases/(default)/documents/chatrooms/chatroom1"
+    ///     )?)
+    ///     .is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn strip_prefix(&self, prefix: &DocumentName) -> Result<DocumentPath, Error> {
+        if self.database_name() != prefix.database_name() {
+            return Err(Error::from(ErrorKind::DatabaseNameMismatch));
+        }
+        self.document_path.strip_prefix(prefix.document_path())
+    }
+
+    /// Returns the `(parent, collection_id, document_id)` of the `CreateDocumentRequest` that creates this document.
+    ///
+    /// The `parent` is the parent document name or, for a document in a top-level collection, the root document name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionId,DocumentId,DocumentName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.to_create_document_parts(),
+    ///     (
+    ///         AnyName::from(RootDocumentName::from_str(
+    ///             "projects/my-project/databases/my-database/documents"
+    ///         )?),
+    ///         CollectionId::from_str("chatrooms")?,
+    ///         DocumentId::from_str("chatroom1")?
+    ///     )
+    /// );
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.to_create_document_parts(),
+    ///     (
+    ///         AnyName::from(DocumentName::from_str(
+    ///             "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///         )?),
+    ///         CollectionId::from_str("messages")?,
+    ///         DocumentId::from_str("message1")?
+    ///     )
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_create_document_parts(&self) -> (AnyName, CollectionId, DocumentId) {
+        (
+            self.parent_document_name()
+                .map(AnyName::from)
+                .unwrap_or_else(|| AnyName::from(self.root_document_name().clone())),
+            self.collection_id().clone(),
+            self.document_id().clone(),
+        )
+    }
+
+    /// Creates a new `DocumentName` from the `RootDocumentName` (or `DatabaseName`) and the segments of the document path.
+    ///
+    /// The segments must alternate between collection ids and document ids, starting with a collection id and ending with a document id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of segments is invalid, any of the segments is invalid, or the name is too long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseName,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let segments: &[&str] = &["chatrooms", "chatroom1", "messages", "message1"];
+    /// assert_eq!(
+    ///     DocumentName::try_from_segments(database_name.clone(), segments)?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    ///     )?
+    /// );
+    /// assert!(DocumentName::try_from_segments(database_name.clone(), ["chatrooms"]).is_err());
+    /// assert!(DocumentName::try_from_segments(database_name, ["chatrooms", "chat/room1"]).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn try_from_segments<D, I, S>(root_document_name: D, segments: I) -> Result<Self, Error>
+    where
+        D: Into<RootDocumentName>,
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::try_new(
+            root_document_name,
+            DocumentPath::try_from_segments(segments)?,
+        )
+    }
+
+    /// Checks that `s` is a valid `DocumentName` without creating a `DocumentName`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that `DocumentName::from_str(s)` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(DocumentName::validate("projects/my-project/databases/my-database/documents/chatrooms/chatroom1").is_ok());
+    ///
+    /// let s = "projects/my-project/databases/my-database/documents/chatrooms";
+    /// assert_eq!(
+    ///     DocumentName::validate(s).unwrap_err(),
+    ///     DocumentName::from_str(s).unwrap_err()
+    /// );
+    /// ```
+    pub fn validate(s: &str) -> Result<(), Error> {
+        limits::check_name_length(s)?;
+
+        let number_of_parts = s.split('/').count();
+        if number_of_parts < 5 + 2 || (number_of_parts - 5) % 2 == 1 {
+            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+        }
+
+        let (index, _) = s
+            .match_indices('/')
+            .nth(4)
+            .expect("s to contain at least 5 slashes");
+        RootDocumentName::validate(&s[..index])?;
+        DocumentPath::validate(&s[index + 1..]).map_err(|e| e.offset(5, index + 1))
+    }
+
+    /// Creates a new `DocumentName` with the same `DocumentPath` in the provided `database_name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new `DocumentName` exceeds the length limit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseName,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// let database_name = DatabaseName::from_str("projects/my-project2/databases/(default)")?;
+    /// assert_eq!(
+    ///     document_name.with_database_name(&database_name)?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project2/databases/(default)/documents/chatrooms/chatroom1"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn with_database_name(&self, database_name: &DatabaseName) -> Result<DocumentName, Error> {
+        DocumentName::try_new(database_name.clone(), self.document_path.clone())
+    }
+
+    /// Creates a new `DocumentName` by consuming the `DocumentName` and replacing the `DocumentId` with `document_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentId,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.clone().with_document_id("chatroom2")?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom2"
+    ///     )?
+    /// );
+    /// assert_eq!(
+    ///     document_name.with_document_id(DocumentId::from_str("chatroom3")?)?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom3"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn with_document_id<E, T>(self, document_id: T) -> Result<DocumentName, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DocumentId, Error = E>,
+    {
+        self.map_document_id(|_| document_id)
+    }
+
+    /// Writes the canonical string representation of this `DocumentName` into `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// let mut buffer = String::with_capacity(document_name.encoded_len() + 1);
+    /// document_name.write_to(&mut buffer)?;
+    /// buffer.push('\n');
+    /// assert_eq!(buffer, format!("{}\n", document_name));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn write_to<W>(&self, writer: &mut W) -> std::fmt::Result
+    where
+        W: std::fmt::Write,
+    {
+        writer.write_str(&self.string)
+    }
+
+    fn try_from_string_with(s: String, options: &ParseOptions) -> Result<Self, Error> {
+        // <https://firebase.google.com/docs/firestore/quotas#collections_documents_and_fields>
+        if options.length_limits {
+            limits::check_name_length(&s)?;
+        }
+
+        let number_of_parts = s.split('/').count();
+        if number_of_parts < 5 + 2 || (number_of_parts - 5) % 2 == 1 {
+            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+        }
+
+        let (index, _) = s
+            .match_indices('/')
+            .nth(4)
+            .expect("s to contain at least 5 slashes");
+        let root_document_name = RootDocumentName::from_str_with(&s[..index], options)?;
+        let document_path = DocumentPath::from_str_with(&s[index + 1..], options)
+            .map_err(|e| e.offset(5, index + 1))?;
+        Ok(Self {
+            document_path,
+            root_document_name,
+            string: s,
+        })
+    }
+}
+
+impl std::cmp::Ord for DocumentName {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.root_document_name
+            .cmp(&other.root_document_name)
+            .then_with(|| self.document_path.cmp(&other.document_path))
+    }
+}
+
+impl std::cmp::PartialEq<str> for DocumentName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl std::cmp::PartialEq<&str> for DocumentName {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl std::cmp::PartialOrd for DocumentName {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::convert::From<DocumentName> for DatabaseName {
+    fn from(document_name: DocumentName) -> Self {
+        Self::from(document_name.root_document_name)
+    }
+}
+
+impl std::convert::From<DocumentName> for DocumentId {
+    fn from(document_name: DocumentName) -> Self {
+        Self::from(document_name.document_path)
+    }
+}
+
+impl std::convert::From<DocumentName> for DocumentPath {
+    fn from(document_name: DocumentName) -> Self {
+        document_name.document_path
+    }
+}
+
+impl std::convert::TryFrom<&str> for DocumentName {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_from(s.to_string())
+    }
+}
+
+impl std::convert::TryFrom<String> for DocumentName {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::try_from_string_with(s, &ParseOptions::strict())
+    }
+}
+
+impl std::fmt::Display for DocumentName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.string)
+    }
+}
+
+/// Creates a new `CollectionName` by joining `rhs` to the `DocumentName`.
+///
+/// # Panics
+///
+/// Panics if `rhs` is not a valid `CollectionPath`. Use [`DocumentName::into_collection`] to handle the error instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{CollectionName,DocumentName};
+/// use std::str::FromStr;
+///
+/// let document_name = DocumentName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+/// )?;
+/// assert_eq!(
+///     document_name / "messages",
+///     CollectionName::from_str(
+///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+///     )?
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+impl std::ops::Div<&str> for DocumentName {
+    type Output = CollectionName;
+
+    fn div(self, rhs: &str) -> Self::Output {
+        self.into_collection(rhs)
+            .unwrap_or_else(|e| panic!("DocumentName / &str: {e}"))
+    }
+}
+
+/// Creates a new `CollectionName` by joining `rhs` to the `DocumentName`.
+impl std::ops::Div<CollectionPath> for DocumentName {
+    type Output = CollectionName;
+
+    fn div(self, rhs: CollectionPath) -> Self::Output {
+        self.into_collection(rhs)
+            .unwrap_or_else(|e| panic!("DocumentName / CollectionPath: {e}"))
+    }
+}
+
+/// Creates a new `DocumentName` by joining `rhs` to the `DocumentName`.
+impl std::ops::Div<DocumentPath> for DocumentName {
+    type Output = DocumentName;
+
+    fn div(self, rhs: DocumentPath) -> Self::Output {
+        self.into_doc(rhs)
+            .unwrap_or_else(|e| panic!("DocumentName / DocumentPath: {e}"))
+    }
+}
+
+impl std::str::FromStr for DocumentName {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{CollectionPath, DocumentId};
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1";
+        let document_name = DocumentName::from_str(s)?;
+        assert_eq!(document_name.to_string(), s);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ancestors() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        assert_eq!(
+            document_name
+                .ancestors()
+                .map(|any_name| any_name.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "projects/my-project/databases/my-database/documents/chatrooms",
+                "projects/my-project/databases/my-database/documents",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        let collection_name = document_name.into_collection("messages")?;
+        assert_eq!(
+            collection_name,
+            CollectionName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+            )?
+        );
+
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
+        )?;
+        let collection_name = document_name.into_collection("col")?;
+        assert_eq!(
+            collection_name,
+            CollectionName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col"
+            )?
+        );
+
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        let collection_id = CollectionId::from_str("messages")?;
+        let collection_name = document_name.into_collection(collection_id)?;
+        assert_eq!(
+            collection_name,
+            CollectionName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+            )?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_with_colleciton_path() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        let collection_name = document_name.into_collection("messages/message1/col")?;
+        assert_eq!(
+            collection_name,
+            CollectionName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col"
+            )?
+        );
+
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        let collection_path = CollectionPath::from_str("messages/message1/col")?;
+        let collection_name = document_name.into_collection(collection_path)?;
+        assert_eq!(
+            collection_name,
+            CollectionName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col"
+            )?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_document_id() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        assert_eq!(
+            document_name.document_id(),
+            &DocumentId::from_str("chatroom1")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_from_database_name_for_document_id() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        assert_eq!(
+            DatabaseName::from(document_name),
+            DatabaseName::from_str("projects/my-project/databases/my-database")?
+        );
+        Ok(())
+    }
 
-impl std::convert::TryFrom<&str> for DocumentName {
-    type Error = Error;
+    #[test]
+    fn test_impl_from_document_name_for_document_id() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        assert_eq!(
+            DocumentId::from(document_name),
+            DocumentId::from_str("chatroom1")?
+        );
+        Ok(())
+    }
 
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
-        // <https://firebase.google.com/docs/firestore/quotas#collections_documents_and_fields>
-        if !(1..=6_144).contains(&s.len()) {
-            return Err(Error::from(ErrorKind::LengthOutOfBounds));
+    #[test]
+    fn test_impl_from_str_and_impl_try_from_string() -> anyhow::Result<()> {
+        let b = "projects/my-project/databases/my-database/documents";
+        let c1 = "x".repeat(1500);
+        let d1 = "x".repeat(1500);
+        let c2 = "y".repeat(1500);
+        let d2 = "y".repeat(1500);
+        let c3 = "z".repeat(80);
+        let d3_ok = "z".repeat(7);
+        let d3_err = "z".repeat(7 + 1);
+        let s1 = format!("{}/{}/{}/{}/{}/{}/{}", b, c1, d1, c2, d2, c3, d3_ok);
+        assert_eq!(s1.len(), 6_144);
+        let s2 = format!("{}/{}/{}/{}/{}/{}/{}", b, c1, d1, c2, d2, c3, d3_err);
+        assert_eq!(s2.len(), 6_145);
+
+        for (s, expected) in [
+            ("projects/my-project/databases/my-database/documents", false),
+            (
+                "projects/my-project/databases/my-database/documents/c",
+                false,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c/d",
+                true,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c/d/c",
+                false,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c/d/c/d",
+                true,
+            ),
+            (s1.as_ref(), true),
+            (s2.as_ref(), false),
+        ] {
+            assert_eq!(DocumentName::from_str(s).is_ok(), expected);
+            assert_eq!(DocumentName::try_from(s).is_ok(), expected);
+            assert_eq!(DocumentName::try_from(s.to_string()).is_ok(), expected);
+            if expected {
+                assert_eq!(DocumentName::from_str(s)?, DocumentName::try_from(s)?);
+                assert_eq!(
+                    DocumentName::from_str(s)?,
+                    DocumentName::try_from(s.to_string())?
+                );
+                assert_eq!(DocumentName::from_str(s)?.to_string(), s);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_document_id() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        assert_eq!(
+            document_name
+                .clone()
+                .map_document_id(|document_id| format!("{}-v2", document_id))?,
+            DocumentName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1-v2"
+            )?
+        );
+        assert!(document_name
+            .map_document_id(|document_id| format!("__{}__", document_id))
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parent() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        assert_eq!(
+            document_name.into_parent(),
+            CollectionName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms",
+            )?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sibling() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
+        )?;
+        assert_eq!(
+            document_name.sibling("message2")?,
+            DocumentName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message2"
+            )?
+        );
+        assert!(document_name.sibling("..").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_prefix() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
+        )?;
+        for (prefix, expected) in [
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+                Some("messages/message1"),
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
+                None,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom2",
+                None,
+            ),
+            (
+                "projects/my-project/databases/my-database2/documents/chatrooms/chatroom1",
+                None,
+            ),
+        ] {
+            assert_eq!(
+                document_name
+                    .strip_prefix(&DocumentName::from_str(prefix)?)
+                    .ok(),
+                expected.map(DocumentPath::from_str).transpose()?
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_rebase() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/tenants/tenant1/chatrooms/chatroom1",
+        )?;
+        let from = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/tenants/tenant1",
+        )?;
+        let to = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/tenants/tenant2",
+        )?;
+        assert_eq!(
+            document_name.rebase(&from, &to)?,
+            DocumentName::from_str(
+                "projects/my-project/databases/my-database/documents/tenants/tenant2/chatrooms/chatroom1"
+            )?
+        );
+        assert!(document_name.rebase(&to, &from).is_err());
+
+        let to = DocumentName::from_str(&format!(
+            "projects/my-project/databases/my-database/documents/{}/{}/{}/{}/{}/{}",
+            "x".repeat(1_500),
+            "x".repeat(1_500),
+            "x".repeat(1_500),
+            "x".repeat(1_500),
+            "x".repeat(40),
+            "x".repeat(40)
+        ))?;
+        assert_eq!(to.to_string().len(), 6_137);
+        assert!(document_name.rebase(&from, &to).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_shard() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/counters/counter1",
+        )?;
+        assert_eq!(
+            document_name.shard(0, 1)?,
+            DocumentName::from_str(
+                "projects/my-project/databases/my-database/documents/counters/counter1/shards/0"
+            )?
+        );
+        assert_eq!(
+            document_name.shard(9, 10)?,
+            DocumentName::from_str(
+                "projects/my-project/databases/my-database/documents/counters/counter1/shards/9"
+            )?
+        );
+        assert!(document_name.shard(1, 1).is_err());
+        assert!(document_name.shard(0, 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_shards() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/counters/counter1",
+        )?;
+        assert_eq!(document_name.shards(0)?.count(), 0);
+        let shards = document_name.shards(10)?.collect::<Vec<DocumentName>>();
+        assert_eq!(shards.len(), 10);
+        for (shard_index, shard) in shards.into_iter().enumerate() {
+            assert_eq!(shard, document_name.shard(shard_index, 10)?);
+        }
+
+        let document_name = DocumentName::from_str(&format!(
+            "projects/my-project/databases/my-database/documents{}",
+            "/c/d".repeat(limits::MAX_DEPTH)
+        ))?;
+        assert_eq!(
+            document_name.shards(1).err().map(|e| e.kind().clone()),
+            Some(ErrorKind::DepthOutOfBounds)
+        );
+        assert_eq!(
+            document_name.shard(0, 1).unwrap_err().kind(),
+            &ErrorKind::DepthOutOfBounds
+        );
+
+        // `{document_name}/shards/1` is exactly `MAX_DOCUMENT_NAME_BYTES` bytes.
+        let mut s = "projects/my-project/databases/my-database/documents".to_string();
+        let len = limits::MAX_DOCUMENT_NAME_BYTES - "/shards/1".len();
+        while s.len() < len {
+            let document_id_len = (len - s.len() - "/c/".len()).min(limits::MAX_ID_BYTES);
+            s.push_str(&format!("/c/{}", "x".repeat(document_id_len)));
+        }
+        let document_name = DocumentName::from_str(&s)?;
+        assert_eq!(document_name.shards(10)?.count(), 10);
+        assert_eq!(
+            document_name.shards(11).err().map(|e| e.kind().clone()),
+            Some(ErrorKind::LengthOutOfBounds)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_database_name() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
+        )?;
+        let database_name = DatabaseName::from_str("projects/my-project/databases/(default)")?;
+        let document_name = document_name.with_database_name(&database_name)?;
+        assert_eq!(
+            document_name.to_string(),
+            "projects/my-project/databases/(default)/documents/chatrooms/chatroom1/messages/message1"
+        );
+        assert_eq!(document_name.database_name(), &database_name);
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_document_id() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        assert_eq!(
+            document_name.clone().with_document_id("chatroom2")?,
+            DocumentName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom2"
+            )?
+        );
+        assert!(document_name.with_document_id("").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_eq_ignore_database() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1";
+        for (other, expected) in [
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+                true,
+            ),
+            (
+                "projects/my-project/databases/(default)/documents/chatrooms/chatroom1",
+                true,
+            ),
+            (
+                "projects/my-project2/databases/my-database/documents/chatrooms/chatroom1",
+                true,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom2",
+                false,
+            ),
+            (
+                "projects/my-project2/databases/(default)/documents/chatrooms/chatroom2",
+                false,
+            ),
+        ] {
+            assert_eq!(
+                DocumentName::from_str(s)?.eq_ignore_database(&DocumentName::from_str(other)?),
+                expected
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_same_database_and_same_project() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1";
+        for (other, same_database, same_project) in [
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+                true,
+                true,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom2",
+                true,
+                true,
+            ),
+            (
+                "projects/my-project/databases/(default)/documents/chatrooms/chatroom1",
+                false,
+                true,
+            ),
+            (
+                "projects/my-project2/databases/my-database/documents/chatrooms/chatroom1",
+                false,
+                false,
+            ),
+        ] {
+            let a = DocumentName::from_str(s)?;
+            let b = DocumentName::from_str(other)?;
+            assert_eq!(a.same_database(&b), same_database);
+            assert_eq!(a.same_project(&b), same_project);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_in_collection_group() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str("projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1")?;
+        for (collection_id, expected) in [
+            ("chatrooms", true),
+            ("messages", true),
+            ("chatroom1", false),
+            ("users", false),
+        ] {
+            assert_eq!(
+                document_name.is_in_collection_group(&CollectionId::from_str(collection_id)?),
+                expected
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_ids_and_document_ids() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str("projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1")?;
+        assert_eq!(
+            document_name
+                .collection_ids()
+                .map(CollectionId::as_ref)
+                .collect::<Vec<&str>>(),
+            vec!["chatrooms", "messages"]
+        );
+        assert_eq!(
+            document_name
+                .document_ids()
+                .map(DocumentId::as_ref)
+                .collect::<Vec<&str>>(),
+            vec!["chatroom1", "message1"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_id_at_and_document_id_at() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str("projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1")?;
+        for (index, expected) in [(0, Some("chatrooms")), (1, Some("messages")), (2, None)] {
+            assert_eq!(
+                document_name
+                    .collection_id_at(index)
+                    .map(CollectionId::as_ref),
+                expected
+            );
+        }
+        for (index, expected) in [(0, Some("chatroom1")), (1, Some("message1")), (2, None)] {
+            assert_eq!(
+                document_name.document_id_at(index).map(DocumentId::as_ref),
+                expected
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parent_at() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
+        )?;
+        for (n, expected) in [
+            (0, Some("projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1")),
+            (1, Some("projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages")),
+            (2, Some("projects/my-project/databases/my-database/documents/chatrooms/chatroom1")),
+            (3, Some("projects/my-project/databases/my-database/documents/chatrooms")),
+            (4, Some("projects/my-project/databases/my-database/documents")),
+            (5, None),
+        ] {
+            assert_eq!(
+                document_name.parent_at(n).map(|any_name| any_name.to_string()),
+                expected.map(ToString::to_string)
+            );
+            if n > 0 {
+                assert_eq!(document_name.parent_at(n), document_name.ancestors().nth(n - 1));
+            }
         }
+        Ok(())
+    }
 
-        let parts = s.split('/').collect::<Vec<&str>>();
-        if parts.len() < 5 + 2 || (parts.len() - 5) % 2 != 0 {
-            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+    #[test]
+    fn test_from_pairs_and_into_pairs() -> anyhow::Result<()> {
+        let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+        for s in [
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
+        ] {
+            let document_name = DocumentName::from_str(s)?;
+            let pairs = document_name.clone().into_pairs();
+            assert_eq!(
+                DocumentName::from_pairs(database_name.clone(), pairs)?,
+                document_name
+            );
         }
-
-        Ok(Self {
-            root_document_name: RootDocumentName::from_str(&parts[0..5].join("/"))?,
-            document_path: DocumentPath::from_str(&parts[5..].join("/"))?,
-        })
+        assert!(DocumentName::from_pairs(database_name, vec![]).is_err());
+        Ok(())
     }
-}
-
-impl std::convert::TryFrom<String> for DocumentName {
-    type Error = Error;
 
-    fn try_from(s: String) -> Result<Self, Self::Error> {
-        Self::try_from(s.as_str())
+    #[test]
+    fn test_impl_div() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        assert_eq!(
+            (document_name.clone() / "messages").to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+        );
+        assert_eq!(
+            (document_name.clone() / CollectionPath::from_str("messages")?).to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+        );
+        assert_eq!(
+            (document_name / DocumentPath::from_str("messages/message1")?).to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+        );
+        Ok(())
     }
-}
 
-impl std::fmt::Display for DocumentName {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}/{}", self.root_document_name, self.document_path)
+    #[test]
+    fn test_impl_ord() -> anyhow::Result<()> {
+        let expected = vec![
+            "projects/my-project/databases/my-database/documents/a/b",
+            "projects/my-project/databases/my-database/documents/a/b/c/d",
+            "projects/my-project/databases/my-database/documents/a/b/c/d/e/f",
+            "projects/my-project/databases/my-database/documents/a/b/c2/d",
+            "projects/my-project/databases/my-database/documents/a/b2",
+            "projects/my-project/databases/my-database/documents/a2/b",
+            "projects/my-project/databases/my-database/documents/z/a",
+        ];
+        let mut sorted = expected
+            .iter()
+            .rev()
+            .map(|s| DocumentName::from_str(s))
+            .collect::<Result<Vec<DocumentName>, Error>>()?;
+        sorted.sort();
+        assert_eq!(
+            sorted
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            expected
+        );
+        Ok(())
     }
-}
-
-impl std::str::FromStr for DocumentName {
-    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Self::try_from(s)
+    #[test]
+    fn test_descendants_range() -> anyhow::Result<()> {
+        let range =
+            DocumentName::from_str("projects/my-project/databases/my-database/documents/c/d")?
+                .descendants_range();
+        for (s, expected) in [
+            (
+                "projects/my-project/databases/my-database/documents/c/d",
+                false,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c/d/c",
+                true,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c/d/c/d",
+                true,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c/d/c/d/c/d",
+                true,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c/d-/c/d",
+                false,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c/d0/c/d",
+                false,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c/d1/c/d",
+                false,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/c/e/c/d",
+                false,
+            ),
+        ] {
+            assert_eq!(range.contains(&s.to_string()), expected, "{s}");
+        }
+        Ok(())
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use std::str::FromStr;
-
-    use crate::{CollectionPath, DocumentId};
 
-    use super::*;
+    #[test]
+    fn test_prefix_str() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+        )?;
+        assert_eq!(document_name.prefix_str(), format!("{}/", document_name));
+        Ok(())
+    }
 
     #[test]
-    fn test() -> anyhow::Result<()> {
+    fn test_as_str() -> anyhow::Result<()> {
         let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1";
         let document_name = DocumentName::from_str(s)?;
-        assert_eq!(document_name.to_string(), s);
+        assert_eq!(document_name.as_str(), s);
+        assert_eq!(document_name.as_str(), document_name.to_string());
+
+        let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+        let document_name = DocumentName::new(
+            database_name,
+            DocumentPath::from_str("chatrooms/chatroom1")?,
+        );
+        assert_eq!(document_name.as_str(), s);
+
+        let child = document_name.collection("messages")?;
+        assert_eq!(child.as_str(), child.to_string());
         Ok(())
     }
 
     #[test]
-    fn test_collection() -> anyhow::Result<()> {
-        let document_name = DocumentName::from_str(
-            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
-        )?;
-        let collection_name = document_name.into_collection("messages")?;
+    fn test_max_length() -> anyhow::Result<()> {
+        // The deepest name (100 levels) whose last 4 document ids fill the rest of 6,144 bytes.
+        let root = "projects/my-project/databases/my-database/documents";
+        let s = format!("{}{}", root, "/c/d".repeat(96));
+        let s = format!("{}{}", s, format!("/c/{}", "d".repeat(1_424)).repeat(3));
+        let s = format!("{}/c/{}", s, "d".repeat(6_144 - s.len() - 3));
+        assert_eq!(s.len(), 6_144);
+        let document_name = DocumentName::from_str(&s)?;
+        assert_eq!(document_name.depth(), 100);
+        assert_eq!(document_name.to_string(), s);
         assert_eq!(
-            collection_name,
-            CollectionName::from_str(
-                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
-            )?
+            document_name.document_path().to_string(),
+            &s[root.len() + 1..]
         );
+        drop(document_name);
 
-        let document_name = DocumentName::from_str(
-            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
-        )?;
-        let collection_name = document_name.into_collection("col")?;
+        assert!(DocumentName::from_str(&format!("{}x", s)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_depth() -> anyhow::Result<()> {
+        let root = "projects/my-project/databases/my-database/documents";
+        let s = format!("{}{}", root, "/c/d".repeat(100));
+        let document_name = DocumentName::from_str(&s)?;
+        assert_eq!(document_name.depth(), 100);
+
+        let error = DocumentName::from_str(&format!("{}/c/d", s)).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::DepthOutOfBounds);
+        assert_eq!(error.segment_index(), Some(5 + 200));
+        assert_eq!(error.byte_range(), Some(s.len() + 1..s.len() + 2));
         assert_eq!(
-            collection_name,
-            CollectionName::from_str(
-                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col"
-            )?
+            CollectionName::from_str(&format!("{}/c", s))
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::DepthOutOfBounds
         );
 
-        let document_name = DocumentName::from_str(
-            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
-        )?;
-        let collection_id = CollectionId::from_str("messages")?;
-        let collection_name = document_name.into_collection(collection_id)?;
+        assert!(document_name.collection("c").is_err());
+        assert!(document_name.doc("c/d").is_err());
+        assert!(document_name.parent().sibling("c").is_ok());
+        let parent = document_name.parent().into_parent().expect("parent");
+        assert!(parent.doc("c/d").is_ok());
+        assert!(parent.doc("c/d/c/d").is_err());
+
+        let pairs = document_name.clone().into_pairs();
+        assert!(DocumentName::from_pairs(RootDocumentName::from_str(root)?, pairs.clone()).is_ok());
+        let mut pairs = pairs;
+        pairs.push((CollectionId::from_str("c")?, DocumentId::from_str("d")?));
         assert_eq!(
-            collection_name,
-            CollectionName::from_str(
-                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
-            )?
+            DocumentName::from_pairs(RootDocumentName::from_str(root)?, pairs)
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::DepthOutOfBounds
         );
+
+        let segments = s[root.len() + 1..].split('/').collect::<Vec<&str>>();
+        assert!(DocumentPath::try_from_segments(&segments).is_ok());
+        assert!(DocumentPath::try_from_segments([&segments[..], &["c", "d"]].concat()).is_err());
+        assert!(CollectionPath::try_from_segments([&segments[..], &["c"]].concat()).is_err());
+
+        let mut any_name = AnyName::from(document_name);
+        assert!(any_name.push_collection("c").is_err());
         Ok(())
     }
 
     #[test]
-    fn test_collection_with_colleciton_path() -> anyhow::Result<()> {
-        let document_name = DocumentName::from_str(
-            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
-        )?;
-        let collection_name = document_name.into_collection("messages/message1/col")?;
+    fn test_max_length_when_composing() -> anyhow::Result<()> {
+        let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+        let root = database_name.root_document_name();
+        let long_id = "x".repeat(1_500);
+        let collection_name = root.collection(format!("{0}/x/{0}/x/{0}/x/{0}", long_id))?;
+        let rest = 6_144 - collection_name.encoded_len() - 1;
+        let document_name = collection_name.doc("d".repeat(rest))?;
+        assert_eq!(document_name.encoded_len(), 6_144);
         assert_eq!(
-            collection_name,
-            CollectionName::from_str(
-                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col"
-            )?
+            collection_name
+                .doc("d".repeat(rest + 1))
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::LengthOutOfBounds
         );
-
-        let document_name = DocumentName::from_str(
-            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
-        )?;
-        let collection_path = CollectionPath::from_str("messages/message1/col")?;
-        let collection_name = document_name.into_collection(collection_path)?;
+        assert!(document_name.collection("c").is_err());
+        assert!(document_name.parent().sibling("c").is_ok());
+        assert!(document_name.sibling("d".repeat(rest + 1)).is_err());
+        assert!(document_name
+            .clone()
+            .map_document_id(|_| "d".repeat(rest + 1))
+            .is_err());
+        assert!(database_name
+            .doc(format!("{}/d", collection_name.collection_path()))
+            .is_ok());
+        assert!(database_name
+            .doc(format!(
+                "{}/{}",
+                collection_name.collection_path(),
+                "d".repeat(rest + 1)
+            ))
+            .is_err());
+        assert!(DocumentName::try_new(
+            database_name.clone(),
+            DocumentPath::from_str(&format!("{}/c/d", document_name.document_path()))?
+        )
+        .is_err());
+        assert!(
+            DocumentName::try_new(database_name, document_name.document_path().clone()).is_ok()
+        );
+        assert!(document_name
+            .with_database_name(&DatabaseName::from_str(
+                "projects/my-project/databases/my-database"
+            )?)
+            .is_ok());
         assert_eq!(
-            collection_name,
-            CollectionName::from_str(
-                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1/col"
-            )?
+            document_name
+                .with_database_name(&DatabaseName::from_str(
+                    "projects/my-project2/databases/my-database2"
+                )?)
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::LengthOutOfBounds
         );
+
+        let from =
+            DocumentName::from_str("projects/my-project/databases/my-database/documents/c/d")?;
+        let to = document_name.parent().doc("d")?;
+        let d = from.doc(format!("c/{}", "d".repeat(rest)))?;
+        assert!(d.rebase(&from, &to).is_err());
         Ok(())
     }
 
     #[test]
-    fn test_document_id() -> anyhow::Result<()> {
-        let document_name = DocumentName::from_str(
+    fn test_impl_partial_eq_str() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1";
+        let document_name = DocumentName::from_str(s)?;
+        assert_eq!(document_name, s);
+        assert_eq!(&document_name, s);
+        assert!(document_name == *s);
+        assert_ne!(
+            document_name,
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom12"
+        );
+        assert_ne!(document_name, "chatrooms/chatroom1");
+        Ok(())
+    }
+
+    #[test]
+    fn test_encoded_len_and_write_to() -> anyhow::Result<()> {
+        for s in [
             "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
-        )?;
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/x/y",
+        ] {
+            let document_name = DocumentName::from_str(s)?;
+            assert_eq!(document_name.encoded_len(), s.len());
+            let mut buffer = String::from(">");
+            document_name.write_to(&mut buffer)?;
+            assert_eq!(buffer, format!(">{s}"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_string() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1";
+        assert_eq!(DocumentName::from_str(s)?.into_string(), s);
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> anyhow::Result<()> {
+        for s in [
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1".to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms".to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms/..".to_string(),
+            "projects/my-project/databases/db/documents/chatrooms/chatroom1".to_string(),
+            "projects/my-project/databases/my-database/document/chatrooms/chatroom1".to_string(),
+        ] {
+            assert_eq!(
+                DocumentName::validate(&s).err(),
+                DocumentName::from_str(&s).err()
+            );
+            assert_eq!(
+                DocumentName::is_valid(&s),
+                DocumentName::from_str(&s).is_ok()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_parts() -> anyhow::Result<()> {
         assert_eq!(
-            document_name.document_id(),
-            &DocumentId::from_str("chatroom1")?
+            DocumentName::from_parts("my-project", "(default)", "chatrooms/chatroom1")?.to_string(),
+            "projects/my-project/databases/(default)/documents/chatrooms/chatroom1"
         );
+        assert!(matches!(
+            DocumentName::from_parts("My-Project", "(default)", "chatrooms/chatroom1")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::ProjectIdConversion(_)
+        ));
+        assert!(matches!(
+            DocumentName::from_parts("my-project", "db", "chatrooms/chatroom1")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DatabaseIdConversion(_)
+        ));
+        assert!(matches!(
+            DocumentName::from_parts("my-project", "(default)", "chatrooms/chatroom1/x")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DocumentPathConversion(_)
+        ));
         Ok(())
     }
 
     #[test]
-    fn test_impl_from_database_name_for_document_id() -> anyhow::Result<()> {
+    fn test_into_parent_and_id() -> anyhow::Result<()> {
         let document_name = DocumentName::from_str(
-            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
         )?;
-        assert_eq!(
-            DatabaseName::from(document_name),
-            DatabaseName::from_str("projects/my-project/databases/my-database")?
-        );
+        let (collection_name, document_id) = document_name.clone().into_parent_and_id();
+        assert_eq!(collection_name, document_name.parent());
+        assert_eq!(&document_id, document_name.document_id());
+        assert_eq!(collection_name.doc(document_id)?, document_name);
         Ok(())
     }
 
     #[test]
-    fn test_impl_from_document_name_for_document_id() -> anyhow::Result<()> {
+    fn test_into_parts() -> anyhow::Result<()> {
         let document_name = DocumentName::from_str(
-            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
         )?;
+        let (root_document_name, document_path) = document_name.clone().into_parts();
         assert_eq!(
-            DocumentId::from(document_name),
-            DocumentId::from_str("chatroom1")?
+            root_document_name.doc(document_path.clone())?,
+            document_name
         );
+        let (collection_path, document_id) = document_path.into_parts();
+        let (parent, collection_id) = collection_path.into_parts();
+        assert_eq!(parent, Some(DocumentPath::from_str("chatrooms/chatroom1")?));
+        assert_eq!(collection_id.as_ref(), "messages");
+        assert_eq!(document_id.as_ref(), "message1");
         Ok(())
     }
 
     #[test]
-    fn test_impl_from_str_and_impl_try_from_string() -> anyhow::Result<()> {
-        let b = "projects/my-project/databases/my-database/documents";
-        let c1 = "x".repeat(1500);
-        let d1 = "x".repeat(1500);
-        let c2 = "y".repeat(1500);
-        let d2 = "y".repeat(1500);
-        let c3 = "z".repeat(80);
-        let d3_ok = "z".repeat(7);
-        let d3_err = "z".repeat(7 + 1);
-        let s1 = format!("{}/{}/{}/{}/{}/{}/{}", b, c1, d1, c2, d2, c3, d3_ok);
-        assert_eq!(s1.len(), 6_144);
-        let s2 = format!("{}/{}/{}/{}/{}/{}/{}", b, c1, d1, c2, d2, c3, d3_err);
-        assert_eq!(s2.len(), 6_145);
-
-        for (s, expected) in [
-            ("projects/my-project/databases/my-database/documents", false),
-            (
-                "projects/my-project/databases/my-database/documents/c",
-                false,
-            ),
-            (
-                "projects/my-project/databases/my-database/documents/c/d",
-                true,
-            ),
-            (
-                "projects/my-project/databases/my-database/documents/c/d/c",
-                false,
-            ),
-            (
-                "projects/my-project/databases/my-database/documents/c/d/c/d",
-                true,
-            ),
-            (s1.as_ref(), true),
-            (s2.as_ref(), false),
+    fn test_try_from_segments() -> anyhow::Result<()> {
+        let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+        let root_document_name = database_name.root_document_name();
+        let segments: Vec<String> = vec!["chatrooms".to_string(), "chatroom1".to_string()];
+        assert_eq!(
+            DocumentName::try_from_segments(root_document_name, &segments)?,
+            database_name.doc("chatrooms/chatroom1")?
+        );
+        for segments in [
+            vec![],
+            vec!["chatrooms"],
+            vec!["chatrooms", "chatroom1", "messages"],
+            vec!["__x__", "chatroom1"],
+            vec!["chatrooms", ".."],
         ] {
-            assert_eq!(DocumentName::from_str(s).is_ok(), expected);
-            assert_eq!(DocumentName::try_from(s).is_ok(), expected);
-            assert_eq!(DocumentName::try_from(s.to_string()).is_ok(), expected);
-            if expected {
-                assert_eq!(DocumentName::from_str(s)?, DocumentName::try_from(s)?);
-                assert_eq!(
-                    DocumentName::from_str(s)?,
-                    DocumentName::try_from(s.to_string())?
-                );
-                assert_eq!(DocumentName::from_str(s)?.to_string(), s);
-            }
+            assert!(DocumentName::try_from_segments(database_name.clone(), segments).is_err());
         }
+        let x = "x".repeat(1_500);
+        assert_eq!(
+            DocumentName::try_from_segments(
+                database_name,
+                [&x, &x, &x, &x, &x, &x, &x, &x, &x, &x]
+            )
+            .unwrap_err()
+            .kind(),
+            &ErrorKind::LengthOutOfBounds
+        );
         Ok(())
     }
 
     #[test]
-    fn test_parent() -> anyhow::Result<()> {
-        let document_name = DocumentName::from_str(
-            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
-        )?;
+    fn test_stable_hash() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1";
+        let document_name = DocumentName::from_str(s)?;
+        assert_eq!(document_name.stable_hash(), 0x15159496b290bdc7);
         assert_eq!(
-            document_name.into_parent(),
-            CollectionName::from_str(
-                "projects/my-project/databases/my-database/documents/chatrooms",
-            )?
+            DatabaseName::from_str("projects/my-project/databases/my-database")?
+                .doc("chatrooms/chatroom1")?
+                .stable_hash(),
+            document_name.stable_hash()
+        );
+        assert_eq!(
+            document_name.document_path().stable_hash(),
+            DocumentPath::from_str("chatrooms/chatroom1")?.stable_hash()
+        );
+        assert_ne!(
+            document_name.stable_hash(),
+            document_name.parent().stable_hash()
+        );
+        assert_eq!(
+            document_name.parent().collection_path().stable_hash(),
+            CollectionPath::from_str("chatrooms")?.stable_hash()
         );
         Ok(())
     }
@@ -1,6 +1,9 @@
 use std::str::FromStr;
 
-use crate::{error::ErrorKind, CollectionId, CollectionPath, DocumentId, Error};
+use crate::{
+    error::ErrorKind, limits, stable_hash::StableHasher, AnyPath, CollectionId, CollectionPath,
+    DocumentId, Error, ParseOptions, Segment,
+};
 
 /// A document path.
 ///
@@ -8,6 +11,10 @@ use crate::{error::ErrorKind, CollectionId, CollectionPath, DocumentId, Error};
 ///
 /// `{collection_path}/{document_id}`
 ///
+/// # Ordering
+///
+/// `DocumentPath`s are ordered segment by segment (a path comes before its descendants), which matches the ordering of references (`__name__`) in Firestore.
+///
 /// # Examples
 ///
 /// ```rust
@@ -21,7 +28,7 @@ use crate::{error::ErrorKind, CollectionId, CollectionPath, DocumentId, Error};
 /// # }
 /// ```
 ///
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct DocumentPath {
     collection_path: Box<CollectionPath>,
     document_id: DocumentId,
@@ -52,6 +59,66 @@ impl DocumentPath {
         }
     }
 
+    /// Creates a new `DocumentPath` by appending the relative `document_path` to this `DocumentPath`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the depth of the appended `DocumentPath` exceeds the limit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentPath;
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(
+    ///     document_path.append(DocumentPath::from_str("messages/message1")?)?,
+    ///     DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn append(self, document_path: DocumentPath) -> Result<DocumentPath, Error> {
+        limits::check_depth(self.depth() + document_path.depth())?;
+        let mut appended = self;
+        for (collection_id, document_id) in document_path.into_pairs() {
+            appended = DocumentPath::new(
+                CollectionPath::new(Some(appended), collection_id),
+                document_id,
+            );
+        }
+        Ok(appended)
+    }
+
+    /// Returns an iterator over the ancestors of this `DocumentPath`.
+    ///
+    /// The iterator yields the parent `CollectionPath`, the parent `DocumentPath`, ... and the root `CollectionPath` in that order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyPath,CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+    /// assert_eq!(
+    ///     document_path.ancestors().collect::<Vec<AnyPath>>(),
+    ///     vec![
+    ///         AnyPath::from(CollectionPath::from_str("chatrooms/chatroom1/messages")?),
+    ///         AnyPath::from(DocumentPath::from_str("chatrooms/chatroom1")?),
+    ///         AnyPath::from(CollectionPath::from_str("chatrooms")?),
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn ancestors(&self) -> impl Iterator<Item = AnyPath> {
+        std::iter::successors(Some(AnyPath::from(self.parent().clone())), AnyPath::parent)
+    }
+
     /// Creates a new `CollectionPath` from this `DocumentPath` and `collection_path`.
     ///
     /// # Examples
@@ -89,6 +156,70 @@ impl DocumentPath {
         self.clone().into_collection(collection_path)
     }
 
+    /// Returns the depth (the number of collections) of this `DocumentPath`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentPath;
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(document_path.depth(), 1);
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+    /// assert_eq!(document_path.depth(), 2);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn depth(&self) -> usize {
+        self.collection_path.depth()
+    }
+
+    /// Creates a new `DocumentPath` by appending `(collection_id, document_id)` pairs to this `DocumentPath`.
+    ///
+    /// Returns a clone of this `DocumentPath` if `pairs` is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the ids cannot be converted or the new `DocumentPath` is too deep.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentPath;
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(
+    ///     document_path.descend([("messages", "message1"), ("reactions", "reaction1")])?,
+    ///     DocumentPath::from_str("chatrooms/chatroom1/messages/message1/reactions/reaction1")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn descend<C, D, I>(&self, pairs: I) -> Result<DocumentPath, Error>
+    where
+        C: TryInto<CollectionId>,
+        C::Error: std::fmt::Display,
+        D: TryInto<DocumentId>,
+        D::Error: std::fmt::Display,
+        I: IntoIterator<Item = (C, D)>,
+    {
+        let mut document_path = self.clone();
+        for (collection_id, document_id) in pairs {
+            let (collection_id, document_id) = try_into_pair(collection_id, document_id)?;
+            limits::check_depth(document_path.depth() + 1)?;
+            document_path = DocumentPath::new(
+                CollectionPath::new(Some(document_path), collection_id),
+                document_id,
+            );
+        }
+        Ok(document_path)
+    }
+
     /// Creates a new `DocumentPath` from this `DocumentPath` and `document_path`.
     ///
     /// # Examples
@@ -147,6 +278,55 @@ impl DocumentPath {
         self.collection_path.collection_id()
     }
 
+    /// Returns the `CollectionId` at `index` (0-based, from the root) of this `DocumentPath`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+    /// assert_eq!(document_path.collection_id_at(0), Some(&CollectionId::from_str("chatrooms")?));
+    /// assert_eq!(document_path.collection_id_at(1), Some(&CollectionId::from_str("messages")?));
+    /// assert_eq!(document_path.collection_id_at(2), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn collection_id_at(&self, index: usize) -> Option<&CollectionId> {
+        self.collection_ids().nth(index)
+    }
+
+    /// Returns an iterator over the `CollectionId`s of this `DocumentPath` from the root to the leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+    /// assert_eq!(
+    ///     document_path.collection_ids().collect::<Vec<&CollectionId>>(),
+    ///     vec![
+    ///         &CollectionId::from_str("chatrooms")?,
+    ///         &CollectionId::from_str("messages")?
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn collection_ids(&self) -> impl Iterator<Item = &CollectionId> {
+        self.segments().filter_map(|segment| match segment {
+            Segment::Collection(collection_id) => Some(collection_id),
+            Segment::Document(_) => None,
+        })
+    }
+
     /// Returns the `DocumentId` of this `DocumentPath`.
     ///
     /// # Examples
@@ -168,6 +348,150 @@ impl DocumentPath {
         &self.document_id
     }
 
+    /// Returns the `DocumentId` at `index` (0-based, from the root) of this `DocumentPath`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentId,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+    /// assert_eq!(document_path.document_id_at(0), Some(&DocumentId::from_str("chatroom1")?));
+    /// assert_eq!(document_path.document_id_at(1), Some(&DocumentId::from_str("message1")?));
+    /// assert_eq!(document_path.document_id_at(2), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn document_id_at(&self, index: usize) -> Option<&DocumentId> {
+        self.document_ids().nth(index)
+    }
+
+    /// Returns an iterator over the `DocumentId`s of this `DocumentPath` from the root to the leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentId,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+    /// assert_eq!(
+    ///     document_path.document_ids().collect::<Vec<&DocumentId>>(),
+    ///     vec![
+    ///         &DocumentId::from_str("chatroom1")?,
+    ///         &DocumentId::from_str("message1")?
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn document_ids(&self) -> impl Iterator<Item = &DocumentId> {
+        self.segments().filter_map(|segment| match segment {
+            Segment::Collection(_) => None,
+            Segment::Document(document_id) => Some(document_id),
+        })
+    }
+
+    /// Returns the length in bytes of the canonical string representation of this `DocumentPath`.
+    ///
+    /// This is the same as `to_string().len()` but does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentPath;
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(document_path.encoded_len(), document_path.to_string().len());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn encoded_len(&self) -> usize {
+        self.collection_path.encoded_len() + 1 + self.document_id.as_ref().len()
+    }
+
+    /// Creates a new `DocumentPath` from `(CollectionId, DocumentId)` pairs ordered from the root to the leaf.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pairs` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,DocumentId,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_pairs(vec![
+    ///     (CollectionId::from_str("chatrooms")?, DocumentId::from_str("chatroom1")?),
+    ///     (CollectionId::from_str("messages")?, DocumentId::from_str("message1")?),
+    /// ])?;
+    /// assert_eq!(
+    ///     document_path,
+    ///     DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_pairs<I>(pairs: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (CollectionId, DocumentId)>,
+    {
+        let mut document_path = None;
+        for (depth, (collection_id, document_id)) in pairs.into_iter().enumerate() {
+            limits::check_depth(depth + 1)?;
+            document_path = Some(DocumentPath::new(
+                CollectionPath::new(document_path, collection_id),
+                document_id,
+            ));
+        }
+        document_path.ok_or_else(|| Error::from(ErrorKind::InvalidNumberOfPathComponents))
+    }
+
+    /// Creates a new `DocumentPath` from `s` with `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid `DocumentPath` under `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentPath,ParseOptions};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     DocumentPath::from_str_with("chatrooms/chatroom1", &ParseOptions::strict())?,
+    ///     DocumentPath::from_str("chatrooms/chatroom1")?
+    /// );
+    /// assert!(DocumentPath::from_str("chatrooms/__chatroom1__").is_err());
+    /// assert!(DocumentPath::from_str_with("chatrooms/__chatroom1__", &ParseOptions::lenient()).is_ok());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        options.parse_normalized(s, |s| {
+            if !s.contains('/') {
+                return Err(Error::from(ErrorKind::NotContainsSlash));
+            }
+            match AnyPath::parse(s, options)? {
+                AnyPath::Collection(_) => {
+                    Err(Error::from(ErrorKind::InvalidNumberOfPathComponents))
+                }
+                AnyPath::Document(document_path) => Ok(document_path),
+            }
+        })
+    }
+
     /// Creates a new `CollectionPath` by consuming the `DocumentPath` with the provided `collection_path`.
     ///
     /// # Examples
@@ -205,6 +529,7 @@ impl DocumentPath {
         let mut collection_path: CollectionPath = collection_path
             .try_into()
             .map_err(|e| Error::from(ErrorKind::CollectionPathConversion(e.to_string())))?;
+        limits::check_depth(self.depth() + collection_path.depth())?;
 
         enum I {
             C(CollectionId),
@@ -212,10 +537,10 @@ impl DocumentPath {
         }
         let mut path_components = vec![];
         loop {
-            let (parent, collection_id) = collection_path.into_tuple();
+            let (parent, collection_id) = collection_path.into_parts();
             path_components.push(I::C(collection_id));
             if let Some(document_path) = parent {
-                let (next_collection_path, document_id) = document_path.into_tuple();
+                let (next_collection_path, document_id) = document_path.into_parts();
                 path_components.push(I::D(document_id));
                 collection_path = next_collection_path;
             } else {
@@ -279,6 +604,7 @@ impl DocumentPath {
         let mut document_path: DocumentPath = document_path
             .try_into()
             .map_err(|e| Error::from(ErrorKind::DocumentPathConversion(e.to_string())))?;
+        limits::check_depth(self.depth() + document_path.depth())?;
 
         enum I {
             C(CollectionId),
@@ -286,10 +612,10 @@ impl DocumentPath {
         }
         let mut path_components = vec![];
         loop {
-            let (collection_path, document_id) = document_path.into_tuple();
+            let (collection_path, document_id) = document_path.into_parts();
             path_components.push(I::D(document_id));
 
-            let (next_document_path, collection_id) = collection_path.into_tuple();
+            let (next_document_path, collection_id) = collection_path.into_parts();
             path_components.push(I::C(collection_id));
             if let Some(next_document_path) = next_document_path {
                 document_path = next_document_path;
@@ -317,6 +643,39 @@ impl DocumentPath {
         Ok(document_path)
     }
 
+    /// Consumes the `DocumentPath`, returning the `(CollectionId, DocumentId)` pairs ordered from the root to the leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,DocumentId,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+    /// assert_eq!(
+    ///     document_path.into_pairs(),
+    ///     vec![
+    ///         (CollectionId::from_str("chatrooms")?, DocumentId::from_str("chatroom1")?),
+    ///         (CollectionId::from_str("messages")?, DocumentId::from_str("message1")?),
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_pairs(self) -> Vec<(CollectionId, DocumentId)> {
+        let mut pairs = vec![];
+        let mut document_path = Some(self);
+        while let Some(current) = document_path {
+            let (collection_path, document_id) = current.into_parts();
+            let (parent, collection_id) = collection_path.into_parts();
+            pairs.push((collection_id, document_id));
+            document_path = parent;
+        }
+        pairs.reverse();
+        pairs
+    }
+
     /// Consumes the `DocumentPath`, returning the parent `CollectionPath`.
     ///
     /// # Examples
@@ -342,58 +701,546 @@ impl DocumentPath {
         *self.collection_path
     }
 
-    /// Returns the parent `CollectionPath` of this `DocumentPath`.
+    /// Consumes the `DocumentPath`, returning the parent `CollectionPath` and the `DocumentId`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # fn main() -> anyhow::Result<()> {
-    /// use firestore_path::{CollectionPath,DocumentPath};
+    /// use firestore_path::{CollectionPath,DocumentId,DocumentPath};
     /// use std::str::FromStr;
     ///
-    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
     /// assert_eq!(
-    ///     document_path.parent(),
-    ///     &CollectionPath::from_str("chatrooms")?
+    ///     document_path.into_parent_and_id(),
+    ///     (
+    ///         CollectionPath::from_str("chatrooms/chatroom1/messages")?,
+    ///         DocumentId::from_str("message1")?
+    ///     )
     /// );
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn parent(&self) -> &CollectionPath {
-        self.collection_path.as_ref()
-    }
-
-    pub(crate) fn into_tuple(self) -> (CollectionPath, DocumentId) {
+    pub fn into_parent_and_id(self) -> (CollectionPath, DocumentId) {
         (*self.collection_path, self.document_id)
     }
-}
 
-impl std::convert::From<DocumentPath> for CollectionPath {
-    fn from(document_path: DocumentPath) -> Self {
-        *document_path.collection_path
+    /// Consumes the `DocumentPath`, returning the parent `CollectionPath` and the `DocumentId`.
+    ///
+    /// This is the same as `into_parent_and_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionPath,DocumentId,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(
+    ///     document_path.into_parts(),
+    ///     (
+    ///         CollectionPath::from_str("chatrooms")?,
+    ///         DocumentId::from_str("chatroom1")?
+    ///     )
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_parts(self) -> (CollectionPath, DocumentId) {
+        (*self.collection_path, self.document_id)
     }
-}
 
-impl std::convert::From<DocumentPath> for DocumentId {
-    fn from(document_path: DocumentPath) -> Self {
-        document_path.document_id
+    /// Converts this `DocumentPath` into its canonical string representation.
+    ///
+    /// The string is written into a buffer of exactly `encoded_len()` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentPath;
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(document_path.into_string(), "chatrooms/chatroom1");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_string(self) -> String {
+        let mut s = String::with_capacity(self.encoded_len());
+        self.write_to(&mut s)
+            .expect("writing to a String to succeed");
+        s
     }
-}
 
-impl std::convert::TryFrom<&str> for DocumentPath {
-    type Error = Error;
-
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
-        Ok(match s.rsplit_once('/') {
-            Some((collection_path, document_id)) => Self {
-                collection_path: Box::new(CollectionPath::from_str(collection_path)?),
-                document_id: DocumentId::from_str(document_id)?,
-            },
-            None => {
-                return Err(Error::from(ErrorKind::NotContainsSlash));
-            }
-        })
-    }
+    /// Returns `true` if this `DocumentPath` belongs to the collection group `collection_id`.
+    ///
+    /// That is, the `CollectionId` of this `DocumentPath` or any of its ancestors is equal to `collection_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+    /// assert!(document_path.is_in_collection_group(&CollectionId::from_str("chatrooms")?));
+    /// assert!(document_path.is_in_collection_group(&CollectionId::from_str("messages")?));
+    /// assert!(!document_path.is_in_collection_group(&CollectionId::from_str("users")?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_in_collection_group(&self, collection_id: &CollectionId) -> bool {
+        self.segments()
+            .any(|segment| segment == Segment::Collection(collection_id))
+    }
+
+    /// Returns `true` if `s` is a valid `DocumentPath`.
+    ///
+    /// This is the same as `DocumentPath::validate(s).is_ok()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DocumentPath;
+    ///
+    /// assert!(DocumentPath::is_valid("chatrooms/chatroom1"));
+    /// assert!(!DocumentPath::is_valid("chatrooms"));
+    /// ```
+    pub fn is_valid(s: &str) -> bool {
+        Self::validate(s).is_ok()
+    }
+
+    /// Creates a new `DocumentPath` by consuming the `DocumentPath` and transforming the `DocumentId` with `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentPath;
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1-v1")?;
+    /// assert_eq!(
+    ///     document_path.map_document_id(|document_id| document_id.as_ref().replace("-v1", "-v2"))?,
+    ///     DocumentPath::from_str("chatrooms/chatroom1/messages/message1-v2")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn map_document_id<E, F, T>(self, f: F) -> Result<DocumentPath, Error>
+    where
+        E: std::fmt::Display,
+        F: FnOnce(DocumentId) -> T,
+        T: TryInto<DocumentId, Error = E>,
+    {
+        let (collection_path, document_id) = self.into_parts();
+        collection_path.into_doc(f(document_id))
+    }
+
+    /// Returns the parent `CollectionPath` of this `DocumentPath`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(
+    ///     document_path.parent(),
+    ///     &CollectionPath::from_str("chatrooms")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parent(&self) -> &CollectionPath {
+        self.collection_path.as_ref()
+    }
+
+    /// Returns the ancestor `n` levels above this `DocumentPath`.
+    ///
+    /// `parent_at(0)` returns this `DocumentPath` itself and `parent_at(1)` returns the parent `CollectionPath`.
+    /// Returns `None` if `n` is greater than the number of ancestors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyPath,CollectionPath,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+    /// assert_eq!(
+    ///     document_path.parent_at(1),
+    ///     Some(AnyPath::from(CollectionPath::from_str("chatrooms/chatroom1/messages")?))
+    /// );
+    /// assert_eq!(
+    ///     document_path.parent_at(2),
+    ///     Some(AnyPath::from(DocumentPath::from_str("chatrooms/chatroom1")?))
+    /// );
+    /// assert_eq!(
+    ///     document_path.parent_at(3),
+    ///     Some(AnyPath::from(CollectionPath::from_str("chatrooms")?))
+    /// );
+    /// assert_eq!(document_path.parent_at(4), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parent_at(&self, n: usize) -> Option<AnyPath> {
+        let mut document_path = self;
+        for _ in 0..n / 2 {
+            document_path = document_path.parent().parent()?;
+        }
+        Some(if n % 2 == 1 {
+            AnyPath::from(document_path.parent().clone())
+        } else {
+            AnyPath::from(document_path.clone())
+        })
+    }
+
+    /// Creates a new `DocumentPath` by replacing the ancestor `from` of this `DocumentPath` with `to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `from` is not an ancestor of this `DocumentPath`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentPath;
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("tenants/tenant1/chatrooms/chatroom1")?;
+    /// assert_eq!(
+    ///     document_path.rebase(
+    ///         &DocumentPath::from_str("tenants/tenant1")?,
+    ///         &DocumentPath::from_str("tenants/tenant2")?
+    ///     )?,
+    ///     DocumentPath::from_str("tenants/tenant2/chatrooms/chatroom1")?
+    /// );
+    /// assert!(document_path
+    ///     .rebase(
+    ///         &DocumentPath::from_str("tenants/tenant3")?,
+    ///         &DocumentPath::from_str("tenants/tenant2")?
+    ///     )
+    ///     .is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn rebase(&self, from: &DocumentPath, to: &DocumentPath) -> Result<DocumentPath, Error> {
+        to.doc(self.strip_prefix(from)?)
+    }
+
+    /// Returns an iterator over the segments of this `DocumentPath` from the root.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentPath,Segment};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+    /// assert_eq!(
+    ///     document_path
+    ///         .segments()
+    ///         .map(|segment| segment.to_string())
+    ///         .collect::<Vec<String>>(),
+    ///     vec!["chatrooms", "chatroom1", "messages", "message1"]
+    /// );
+    /// assert!(matches!(
+    ///     document_path.segments().last(),
+    ///     Some(Segment::Document(document_id)) if document_id.as_ref() == "message1"
+    /// ));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn segments(&self) -> impl Iterator<Item = Segment<'_>> {
+        self.collection_path
+            .segments()
+            .chain(std::iter::once(Segment::Document(&self.document_id)))
+    }
+
+    /// Creates a new `DocumentPath` with the same parent as this `DocumentPath` and the provided `document_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentId,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(
+    ///     document_path.sibling("chatroom2")?,
+    ///     DocumentPath::from_str("chatrooms/chatroom2")?
+    /// );
+    /// assert_eq!(
+    ///     document_path.sibling(DocumentId::from_str("chatroom3")?)?,
+    ///     DocumentPath::from_str("chatrooms/chatroom3")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn sibling<E, T>(&self, document_id: T) -> Result<DocumentPath, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DocumentId, Error = E>,
+    {
+        self.collection_path.doc(document_id)
+    }
+
+    /// Returns a 64-bit hash of the canonical string representation of this `DocumentPath`.
+    ///
+    /// Unlike `std::hash::Hash`, the value is stable across versions, platforms and processes.
+    /// It is the 64-bit FNV-1a hash of the UTF-8 bytes of `to_string()`, so it can be persisted or computed in other languages.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentPath;
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(document_path.stable_hash(), 0xb0129bde26616dee);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn stable_hash(&self) -> u64 {
+        StableHasher::hash(|hasher| self.write_to(hasher))
+    }
+
+    /// Returns the `DocumentPath` relative to the ancestor `prefix`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `prefix` is not an ancestor of this `DocumentPath`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentPath;
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+    /// assert_eq!(
+    ///     document_path.strip_prefix(&DocumentPath::from_str("chatrooms/chatroom1")?)?,
+    ///     DocumentPath::from_str("messages/message1")?
+    /// );
+    /// assert!(document_path.strip_prefix(&DocumentPath::from_str("chatrooms/chatroom2")?).is_err());
+    /// assert!(document_path.strip_prefix(&document_path).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn strip_prefix(&self, prefix: &DocumentPath) -> Result<DocumentPath, Error> {
+        let segments = self.segments().collect::<Vec<Segment>>();
+        let prefix = prefix.segments().collect::<Vec<Segment>>();
+        if prefix.len() >= segments.len() || !segments.starts_with(&prefix) {
+            return Err(Error::from(ErrorKind::PrefixMismatch));
+        }
+        DocumentPath::try_from_segments(&segments[prefix.len()..])
+    }
+
+    /// Creates a new `DocumentPath` from the segments.
+    ///
+    /// The segments must alternate between collection ids and document ids, starting with a collection id and ending with a document id.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentPath;
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     DocumentPath::try_from_segments(["chatrooms", "chatroom1"])?,
+    ///     DocumentPath::from_str("chatrooms/chatroom1")?
+    /// );
+    /// assert_eq!(
+    ///     DocumentPath::try_from_segments(vec![
+    ///         "chatrooms".to_string(),
+    ///         "chatroom1".to_string(),
+    ///         "messages".to_string(),
+    ///         "message1".to_string(),
+    ///     ])?,
+    ///     DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?
+    /// );
+    /// assert!(DocumentPath::try_from_segments(["chatrooms"]).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn try_from_segments<I, S>(segments: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let segments = segments.into_iter().collect::<Vec<S>>();
+        if segments.is_empty() || segments.len() % 2 != 0 {
+            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+        }
+        limits::check_depth(segments.len() / 2)?;
+        let mut document_path = None;
+        for pair in segments.chunks(2) {
+            let collection_path =
+                CollectionPath::new(document_path, CollectionId::from_str(pair[0].as_ref())?);
+            document_path = Some(DocumentPath::new(
+                collection_path,
+                DocumentId::from_str(pair[1].as_ref())?,
+            ));
+        }
+        Ok(document_path.expect("segments to be non-empty"))
+    }
+
+    /// Creates a new `DocumentPath` by consuming the `DocumentPath` and replacing the `DocumentId` with `document_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentId,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+    /// assert_eq!(
+    ///     document_path.clone().with_document_id("message2")?,
+    ///     DocumentPath::from_str("chatrooms/chatroom1/messages/message2")?
+    /// );
+    /// assert_eq!(
+    ///     document_path.with_document_id(DocumentId::from_str("message3")?)?,
+    ///     DocumentPath::from_str("chatrooms/chatroom1/messages/message3")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn with_document_id<E, T>(self, document_id: T) -> Result<DocumentPath, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DocumentId, Error = E>,
+    {
+        self.map_document_id(|_| document_id)
+    }
+
+    /// Checks that `s` is a valid `DocumentPath` without creating a `DocumentPath`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that `DocumentPath::from_str(s)` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DocumentPath;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(DocumentPath::validate("chatrooms/chatroom1").is_ok());
+    /// assert_eq!(
+    ///     DocumentPath::validate("chatrooms").unwrap_err(),
+    ///     DocumentPath::from_str("chatrooms").unwrap_err()
+    /// );
+    /// ```
+    pub fn validate(s: &str) -> Result<(), Error> {
+        if !s.contains('/') {
+            return Err(Error::from(ErrorKind::NotContainsSlash));
+        }
+        let count = AnyPath::validate(s)?;
+        if count % 2 == 0 {
+            Ok(())
+        } else {
+            Err(Error::from(ErrorKind::InvalidNumberOfPathComponents))
+        }
+    }
+
+    /// Writes the canonical string representation of this `DocumentPath` into `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentPath;
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// let mut buffer = String::with_capacity(document_path.encoded_len() + 1);
+    /// document_path.write_to(&mut buffer)?;
+    /// buffer.push('\n');
+    /// assert_eq!(buffer, format!("{}\n", document_path));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn write_to<W>(&self, writer: &mut W) -> std::fmt::Result
+    where
+        W: std::fmt::Write,
+    {
+        self.collection_path.write_to(writer)?;
+        writer.write_str("/")?;
+        writer.write_str(self.document_id.as_ref())
+    }
+}
+
+pub(crate) fn try_into_pair<C, D>(
+    collection_id: C,
+    document_id: D,
+) -> Result<(CollectionId, DocumentId), Error>
+where
+    C: TryInto<CollectionId>,
+    C::Error: std::fmt::Display,
+    D: TryInto<DocumentId>,
+    D::Error: std::fmt::Display,
+{
+    let collection_id = collection_id
+        .try_into()
+        .map_err(|e| Error::from(ErrorKind::CollectionIdConversion(e.to_string())))?;
+    let document_id = document_id
+        .try_into()
+        .map_err(|e| Error::from(ErrorKind::DocumentIdConversion(e.to_string())))?;
+    Ok((collection_id, document_id))
+}
+
+impl std::cmp::Ord for DocumentPath {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.segments().cmp(other.segments())
+    }
+}
+
+impl std::cmp::PartialOrd for DocumentPath {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::convert::From<DocumentPath> for CollectionPath {
+    fn from(document_path: DocumentPath) -> Self {
+        *document_path.collection_path
+    }
+}
+
+impl std::convert::From<DocumentPath> for DocumentId {
+    fn from(document_path: DocumentPath) -> Self {
+        document_path.document_id
+    }
+}
+
+impl std::convert::TryFrom<&[&str]> for DocumentPath {
+    type Error = Error;
+
+    fn try_from(segments: &[&str]) -> Result<Self, Self::Error> {
+        Self::try_from_segments(segments)
+    }
+}
+
+impl std::convert::TryFrom<&str> for DocumentPath {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str_with(s, &ParseOptions::strict())
+    }
 }
 
 impl std::convert::TryFrom<String> for DocumentPath {
@@ -410,6 +1257,56 @@ impl std::fmt::Display for DocumentPath {
     }
 }
 
+/// Creates a new `CollectionPath` by joining `rhs` to the `DocumentPath`.
+///
+/// # Panics
+///
+/// Panics if `rhs` is not a valid `CollectionPath`. Use [`DocumentPath::into_collection`] to handle the error instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{CollectionPath,DocumentPath};
+/// use std::str::FromStr;
+///
+/// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+/// assert_eq!(
+///     document_path / "messages",
+///     CollectionPath::from_str("chatrooms/chatroom1/messages")?
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+impl std::ops::Div<&str> for DocumentPath {
+    type Output = CollectionPath;
+
+    fn div(self, rhs: &str) -> Self::Output {
+        self.into_collection(rhs)
+            .unwrap_or_else(|e| panic!("DocumentPath / &str: {e}"))
+    }
+}
+
+/// Creates a new `CollectionPath` by joining `rhs` to the `DocumentPath`.
+impl std::ops::Div<CollectionPath> for DocumentPath {
+    type Output = CollectionPath;
+
+    fn div(self, rhs: CollectionPath) -> Self::Output {
+        self.into_collection(rhs)
+            .unwrap_or_else(|e| panic!("DocumentPath / CollectionPath: {e}"))
+    }
+}
+
+/// Creates a new `DocumentPath` by joining `rhs` to the `DocumentPath`.
+impl std::ops::Div<DocumentPath> for DocumentPath {
+    type Output = DocumentPath;
+
+    fn div(self, rhs: DocumentPath) -> Self::Output {
+        self.into_doc(rhs)
+            .unwrap_or_else(|e| panic!("DocumentPath / DocumentPath: {e}"))
+    }
+}
+
 impl std::str::FromStr for DocumentPath {
     type Err = Error;
 
@@ -436,6 +1333,16 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_ancestors() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+        assert_eq!(
+            document_path.ancestors().collect::<Vec<AnyPath>>(),
+            vec![AnyPath::from(CollectionPath::from_str("chatrooms")?)]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_collection() -> anyhow::Result<()> {
         let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
@@ -481,6 +1388,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_depth() -> anyhow::Result<()> {
+        for (s, expected) in [
+            ("chatrooms/chatroom1", 1),
+            ("chatrooms/chatroom1/messages/message1", 2),
+            ("chatrooms/chatroom1/messages/message1/col/doc", 3),
+        ] {
+            assert_eq!(DocumentPath::from_str(s)?.depth(), expected);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_document_id() -> anyhow::Result<()> {
         let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
@@ -519,8 +1438,12 @@ mod tests {
     #[test]
     fn test_impl_from_str_and_impl_try_from_string() -> anyhow::Result<()> {
         for (s, expected) in [
+            ("", false),
             ("chatrooms", false),
+            ("chatrooms/", false),
             ("chatrooms/chatroom1", true),
+            ("chatrooms/chatroom1/messages", false),
+            ("/chatroom1/messages/message1", false),
             ("chatrooms/chatroom1/messages/message1", true),
         ] {
             assert_eq!(DocumentPath::from_str(s).is_ok(), expected);
@@ -538,6 +1461,74 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_sibling() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+        assert_eq!(
+            document_path.sibling("message2")?,
+            DocumentPath::from_str("chatrooms/chatroom1/messages/message2")?
+        );
+        assert!(document_path.sibling("message/2").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_prefix() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+        for (prefix, expected) in [
+            ("chatrooms/chatroom1", Some("messages/message1")),
+            ("chatrooms/chatroom2", None),
+            ("chatroom/chatroom1", None),
+            ("chatrooms/chatroom1/messages/message1", None),
+            ("chatrooms/chatroom1/messages/message1/col/doc", None),
+        ] {
+            assert_eq!(
+                document_path
+                    .strip_prefix(&DocumentPath::from_str(prefix)?)
+                    .ok(),
+                expected.map(DocumentPath::from_str).transpose()?
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_try_from_segments() -> anyhow::Result<()> {
+        for (segments, expected) in [
+            (vec![], None),
+            (vec!["chatrooms"], None),
+            (vec!["chatrooms", "chatroom1"], Some("chatrooms/chatroom1")),
+            (vec!["chatrooms", "chatroom1", "messages"], None),
+            (
+                vec!["chatrooms", "chatroom1", "messages", "message1"],
+                Some("chatrooms/chatroom1/messages/message1"),
+            ),
+            (vec!["chatrooms", "chat/room1"], None),
+            (vec!["chatrooms", ".."], None),
+        ] {
+            let expected = expected.map(DocumentPath::from_str).transpose()?;
+            assert_eq!(
+                DocumentPath::try_from_segments(segments.iter()).ok(),
+                expected
+            );
+            assert_eq!(DocumentPath::try_from(segments.as_slice()).ok(), expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_document_id() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+        assert_eq!(
+            document_path
+                .clone()
+                .map_document_id(|document_id| format!("{}-v2", document_id))?,
+            DocumentPath::from_str("chatrooms/chatroom1-v2")?
+        );
+        assert!(document_path.map_document_id(|_| "").is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_new() -> anyhow::Result<()> {
         let collection_path = build_collection_path()?;
@@ -565,6 +1556,63 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_rebase() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("tenants/tenant1/chatrooms/chatroom1")?;
+        for (from, to, expected) in [
+            (
+                "tenants/tenant1",
+                "tenants/tenant2",
+                Some("tenants/tenant2/chatrooms/chatroom1"),
+            ),
+            (
+                "tenants/tenant1",
+                "archives/archive1/tenants/tenant1",
+                Some("archives/archive1/tenants/tenant1/chatrooms/chatroom1"),
+            ),
+            ("tenants/tenant2", "tenants/tenant3", None),
+            (
+                "tenants/tenant1/chatrooms/chatroom1",
+                "tenants/tenant2",
+                None,
+            ),
+        ] {
+            assert_eq!(
+                document_path
+                    .rebase(&DocumentPath::from_str(from)?, &DocumentPath::from_str(to)?)
+                    .ok(),
+                expected.map(DocumentPath::from_str).transpose()?
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_segments() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+        assert_eq!(
+            document_path.segments().collect::<Vec<Segment>>(),
+            vec![
+                Segment::Collection(&CollectionId::from_str("chatrooms")?),
+                Segment::Document(&DocumentId::from_str("chatroom1")?),
+                Segment::Collection(&CollectionId::from_str("messages")?),
+                Segment::Document(&DocumentId::from_str("message1")?),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_document_id() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+        assert_eq!(
+            document_path.clone().with_document_id("message2")?,
+            DocumentPath::from_str("chatrooms/chatroom1/messages/message2")?
+        );
+        assert!(document_path.with_document_id("message/2").is_err());
+        Ok(())
+    }
+
     fn build_collection_path() -> anyhow::Result<CollectionPath> {
         Ok(CollectionPath::from_str("chatrooms")?)
     }
@@ -572,4 +1620,291 @@ mod tests {
     fn build_document_id() -> anyhow::Result<DocumentId> {
         Ok(DocumentId::from_str("chatroom1")?)
     }
+
+    #[test]
+    fn test_is_in_collection_group() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+        for (collection_id, expected) in [
+            ("chatrooms", true),
+            ("messages", true),
+            ("chatroom1", false),
+            ("users", false),
+        ] {
+            assert_eq!(
+                document_path.is_in_collection_group(&CollectionId::from_str(collection_id)?),
+                expected
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_ids_and_document_ids() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+        assert_eq!(
+            document_path
+                .collection_ids()
+                .map(CollectionId::as_ref)
+                .collect::<Vec<&str>>(),
+            vec!["chatrooms", "messages"]
+        );
+        assert_eq!(
+            document_path
+                .document_ids()
+                .map(DocumentId::as_ref)
+                .collect::<Vec<&str>>(),
+            vec!["chatroom1", "message1"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_id_at_and_document_id_at() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+        for (index, expected) in [(0, Some("chatrooms")), (1, Some("messages")), (2, None)] {
+            assert_eq!(
+                document_path
+                    .collection_id_at(index)
+                    .map(CollectionId::as_ref),
+                expected
+            );
+        }
+        for (index, expected) in [(0, Some("chatroom1")), (1, Some("message1")), (2, None)] {
+            assert_eq!(
+                document_path.document_id_at(index).map(DocumentId::as_ref),
+                expected
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parent_at() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+        for (n, expected) in [
+            (0, Some("chatrooms/chatroom1/messages/message1")),
+            (1, Some("chatrooms/chatroom1/messages")),
+            (2, Some("chatrooms/chatroom1")),
+            (3, Some("chatrooms")),
+            (4, None),
+            (5, None),
+        ] {
+            assert_eq!(
+                document_path
+                    .parent_at(n)
+                    .map(|any_path| any_path.to_string()),
+                expected.map(ToString::to_string)
+            );
+            if n > 0 {
+                assert_eq!(
+                    document_path.parent_at(n),
+                    document_path.ancestors().nth(n - 1)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_pairs_and_into_pairs() -> anyhow::Result<()> {
+        for s in [
+            "chatrooms/chatroom1",
+            "chatrooms/chatroom1/messages/message1",
+            "a/b/c/d/e/f/g/h",
+        ] {
+            let document_path = DocumentPath::from_str(s)?;
+            let pairs = document_path.clone().into_pairs();
+            assert_eq!(pairs.len(), document_path.depth());
+            assert_eq!(DocumentPath::from_pairs(pairs)?, document_path);
+        }
+        assert!(DocumentPath::from_pairs(vec![]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_div() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+        assert_eq!(
+            (document_path.clone() / "messages").to_string(),
+            "chatrooms/chatroom1/messages"
+        );
+        assert_eq!(
+            (document_path.clone() / CollectionPath::from_str("messages")?).to_string(),
+            "chatrooms/chatroom1/messages"
+        );
+        assert_eq!(
+            (document_path / DocumentPath::from_str("messages/message1")?).to_string(),
+            "chatrooms/chatroom1/messages/message1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_impl_div_panics() {
+        let _ = DocumentPath::from_str("chatrooms/chatroom1").unwrap() / "messages/message1";
+    }
+
+    #[test]
+    fn test_append() -> anyhow::Result<()> {
+        for (s, other, expected) in [
+            ("c1/d1", "c2/d2", "c1/d1/c2/d2"),
+            ("c1/d1/c2/d2", "c3/d3", "c1/d1/c2/d2/c3/d3"),
+            ("c1/d1", "c2/d2/c3/d3", "c1/d1/c2/d2/c3/d3"),
+        ] {
+            let document_path = DocumentPath::from_str(s)?;
+            let other = DocumentPath::from_str(other)?;
+            assert_eq!(
+                document_path.clone().append(other.clone())?,
+                DocumentPath::from_str(expected)?
+            );
+            assert_eq!(
+                document_path.clone().append(other.clone())?,
+                document_path.doc(other)?
+            );
+        }
+
+        let document_path = DocumentPath::from_str(&format!("c/d{}", "/c/d".repeat(59)))?;
+        assert_eq!(
+            document_path
+                .clone()
+                .append(DocumentPath::from_str(&format!(
+                    "c/d{}",
+                    "/c/d".repeat(39)
+                ))?)?
+                .depth(),
+            limits::MAX_DEPTH
+        );
+        assert_eq!(
+            document_path
+                .clone()
+                .append(document_path)
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::DepthOutOfBounds
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_ord() -> anyhow::Result<()> {
+        let expected = vec![
+            "a/b",
+            "a/b/c/d",
+            "a/b/c/d/e/f",
+            "a/b/c2/d",
+            "a/b2",
+            "a2/b",
+            "z/a",
+        ];
+        let mut sorted = expected
+            .iter()
+            .rev()
+            .map(|s| DocumentPath::from_str(s))
+            .collect::<Result<Vec<DocumentPath>, Error>>()?;
+        sorted.sort();
+        assert_eq!(
+            sorted
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_deep_path() -> anyhow::Result<()> {
+        let depth = 100_000;
+        let s = vec!["c/d"; depth].join("/");
+        assert_eq!(
+            DocumentPath::from_str(&s).unwrap_err().kind(),
+            &ErrorKind::DepthOutOfBounds
+        );
+
+        // A deep path can still be built with `new`, and it must not overflow the stack.
+        let mut document_path = DocumentPath::from_str("c/d")?;
+        for _ in 1..depth {
+            document_path = DocumentPath::new(
+                CollectionPath::new(Some(document_path), CollectionId::from_str("c")?),
+                DocumentId::from_str("d")?,
+            );
+        }
+        assert_eq!(document_path.depth(), depth);
+        assert_eq!(document_path.to_string(), s);
+        drop(document_path);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encoded_len_and_write_to() -> anyhow::Result<()> {
+        for s in ["chatrooms/chatroom1", "chatrooms/chatroom1/x/y"] {
+            let document_path = DocumentPath::from_str(s)?;
+            assert_eq!(document_path.encoded_len(), s.len());
+            let mut buffer = String::from(">");
+            document_path.write_to(&mut buffer)?;
+            assert_eq!(buffer, format!(">{s}"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_string() -> anyhow::Result<()> {
+        let s = "chatrooms/chatroom1";
+        assert_eq!(DocumentPath::from_str(s)?.into_string(), s);
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> anyhow::Result<()> {
+        for s in [
+            "chatrooms/chatroom1".to_string(),
+            "chatrooms".to_string(),
+            "chatrooms/chatroom1/messages".to_string(),
+            "chatrooms/__x__".to_string(),
+            "chatrooms//".to_string(),
+            "c/d/".repeat(100) + "c/d",
+        ] {
+            assert_eq!(
+                DocumentPath::validate(&s).err(),
+                DocumentPath::from_str(&s).err()
+            );
+            assert_eq!(
+                DocumentPath::is_valid(&s),
+                DocumentPath::from_str(&s).is_ok()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_descend() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+        assert_eq!(
+            document_path.descend(Vec::<(&str, &str)>::new())?,
+            document_path
+        );
+        assert_eq!(
+            document_path.descend([("messages", "message1")])?,
+            document_path.doc("messages/message1")?
+        );
+        assert!(matches!(
+            document_path
+                .descend([("messages", "message/1")])
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DocumentIdConversion(_)
+        ));
+        assert!(document_path
+            .descend(std::iter::repeat_n(("c", "d"), limits::MAX_DEPTH - 1))
+            .is_ok());
+        assert_eq!(
+            document_path
+                .descend(std::iter::repeat_n(("c", "d"), limits::MAX_DEPTH))
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::DepthOutOfBounds
+        );
+        Ok(())
+    }
 }
@@ -1,36 +1,526 @@
+use std::ops::Range;
+
 /// An error that occurs in this crate.
-#[derive(Debug, thiserror::Error)]
-#[error(transparent)]
-pub struct Error(#[from] ErrorKind);
+///
+/// When parsing a path or a name fails at one of its segments, the error also has the location of the segment
+/// (see [`Error::segment_index`], [`Error::segment`] and [`Error::byte_range`]).
+///
+/// # Examples
+///
+/// ```rust
+/// use firestore_path::DocumentName;
+/// use std::str::FromStr;
+///
+/// let error = DocumentName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms/__chatroom1__"
+/// )
+/// .unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "segment 6 (`__chatroom1__`) at bytes 62..75: matches the regular expression `__.*__`"
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Error {
+    kind: ErrorKind,
+    location: Option<Location>,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Location {
+    index: usize,
+    segment: String,
+    start: usize,
+}
+
+impl Error {
+    /// Returns the byte range of the segment that caused this `Error` in the parsed string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::{DocumentId,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let error = DocumentPath::from_str("chatrooms/chatroom1/messages/.").unwrap_err();
+    /// assert_eq!(error.byte_range(), Some(29..30));
+    ///
+    /// let error = DocumentId::from_str(".").unwrap_err();
+    /// assert_eq!(error.byte_range(), None);
+    /// ```
+    pub fn byte_range(&self) -> Option<Range<usize>> {
+        self.location
+            .as_ref()
+            .map(|location| location.start..location.start + location.segment.len())
+    }
+
+    /// Returns `true` if this `Error` is caused by a character that is not allowed (in the position).
+    ///
+    /// The kinds are `ContainsInvalidCharacter`, `ContainsSlash`, `EndsWithHyphen` and `StartsWithNonLetter`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::{DatabaseId,DocumentId,ProjectId};
+    /// use std::str::FromStr;
+    ///
+    /// assert!(DatabaseId::from_str("my_database").unwrap_err().is_invalid_character());
+    /// assert!(DocumentId::from_str("chatrooms/chatroom1").unwrap_err().is_invalid_character());
+    /// assert!(ProjectId::from_str("my-project-").unwrap_err().is_invalid_character());
+    /// assert!(ProjectId::from_str("1my-project").unwrap_err().is_invalid_character());
+    /// assert!(!DocumentId::from_str("").unwrap_err().is_invalid_character());
+    /// ```
+    pub fn is_invalid_character(&self) -> bool {
+        matches!(
+            self.kind,
+            ErrorKind::ContainsInvalidCharacter
+                | ErrorKind::ContainsSlash
+                | ErrorKind::EndsWithHyphen
+                | ErrorKind::StartsWithNonLetter
+        )
+    }
+
+    /// Returns `true` if this `Error` is caused by an id or a name that is too short or too long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::{DocumentId,ProjectId};
+    /// use std::str::FromStr;
+    ///
+    /// assert!(DocumentId::from_str("").unwrap_err().is_length_error());
+    /// assert!(ProjectId::from_str("x").unwrap_err().is_length_error());
+    /// assert!(!DocumentId::from_str("..").unwrap_err().is_length_error());
+    /// ```
+    pub fn is_length_error(&self) -> bool {
+        matches!(self.kind, ErrorKind::LengthOutOfBounds)
+    }
+
+    /// Returns `true` if this `Error` is caused by the structure of a path or a name rather than by an id in it.
+    ///
+    /// The kinds are `InvalidName`, `InvalidNumberOfPathComponents`, `NotContainsSlash`, `PrefixMismatch` and `SegmentKindMismatch`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::{DatabaseName,DocumentName,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// assert!(DatabaseName::from_str("projects/my-project/database/my-database")
+    ///     .unwrap_err()
+    ///     .is_structure_error());
+    /// assert!(DocumentName::from_str("projects/my-project/databases/my-database/documents/chatrooms")
+    ///     .unwrap_err()
+    ///     .is_structure_error());
+    /// assert!(DocumentPath::from_str("chatrooms").unwrap_err().is_structure_error());
+    /// assert!(!DocumentPath::from_str("chatrooms/..").unwrap_err().is_structure_error());
+    /// ```
+    pub fn is_structure_error(&self) -> bool {
+        matches!(
+            self.kind,
+            ErrorKind::InvalidName
+                | ErrorKind::InvalidNumberOfPathComponents
+                | ErrorKind::NotContainsSlash
+                | ErrorKind::PrefixMismatch
+                | ErrorKind::SegmentKindMismatch
+        )
+    }
+
+    /// Returns the `ErrorKind` of this `Error`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::{DocumentId,ErrorKind};
+    /// use std::str::FromStr;
+    ///
+    /// let error = DocumentId::from_str("chatrooms/chatroom1").unwrap_err();
+    /// assert_eq!(error.kind(), &ErrorKind::ContainsSlash);
+    ///
+    /// let error = DocumentId::from_str(&"x".repeat(1_501)).unwrap_err();
+    /// assert_eq!(error.kind(), &ErrorKind::LengthOutOfBounds);
+    /// ```
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Returns the segment that caused this `Error`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::{DocumentId,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let error = DocumentPath::from_str("chatrooms/chatroom1/messages/.").unwrap_err();
+    /// assert_eq!(error.segment(), Some("."));
+    ///
+    /// let error = DocumentId::from_str(".").unwrap_err();
+    /// assert_eq!(error.segment(), None);
+    /// ```
+    pub fn segment(&self) -> Option<&str> {
+        self.location
+            .as_ref()
+            .map(|location| location.segment.as_str())
+    }
+
+    /// Returns the index (0-based) of the segment that caused this `Error`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::{DocumentId,DocumentPath};
+    /// use std::str::FromStr;
+    ///
+    /// let error = DocumentPath::from_str("chatrooms/chatroom1/messages/.").unwrap_err();
+    /// assert_eq!(error.segment_index(), Some(3));
+    ///
+    /// let error = DocumentId::from_str(".").unwrap_err();
+    /// assert_eq!(error.segment_index(), None);
+    /// ```
+    pub fn segment_index(&self) -> Option<usize> {
+        self.location.as_ref().map(|location| location.index)
+    }
+
+    /// Sets the location of this `Error` to the `index`-th segment `segment` starting at the byte `start`.
+    pub(crate) fn at_segment(self, index: usize, segment: &str, start: usize) -> Self {
+        Self {
+            kind: self.kind,
+            location: Some(Location {
+                index,
+                segment: segment.to_string(),
+                start,
+            }),
+        }
+    }
+
+    /// Shifts the location of this `Error` by `segments` segments and `bytes` bytes.
+    ///
+    /// This is used when the segment was parsed as a part of a longer string.
+    pub(crate) fn offset(self, segments: usize, bytes: usize) -> Self {
+        Self {
+            kind: self.kind,
+            location: self.location.map(|location| Location {
+                index: location.index + segments,
+                segment: location.segment,
+                start: location.start + bytes,
+            }),
+        }
+    }
+
+    /// Moves the location of this `Error` to the segment index and the start byte returned by `f` for the start byte.
+    ///
+    /// This is used when the segment was parsed from a normalized string.
+    pub(crate) fn relocate<F>(self, f: F) -> Self
+    where
+        F: FnOnce(usize) -> (usize, usize),
+    {
+        Self {
+            kind: self.kind,
+            location: self.location.map(|location| {
+                let (index, start) = f(location.start);
+                Location {
+                    index,
+                    segment: location.segment,
+                    start,
+                }
+            }),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(
+                f,
+                "segment {} (`{}`) at bytes {}..{}: {}",
+                location.index,
+                location.segment,
+                location.start,
+                location.start + location.segment.len(),
+                self.kind
+            ),
+            None => self.kind.fmt(f),
+        }
+    }
+}
+
+impl std::convert::From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            location: None,
+        }
+    }
+}
 
+/// The kind of an `Error`.
+///
+/// The `*Conversion` variants contain the message of the error that occurred in the conversion of an argument.
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, thiserror::Error)]
-pub(crate) enum ErrorKind {
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The conversion into a `CollectionId` failed.
+    #[error("collection id conversion {0}")]
+    CollectionIdConversion(String),
+    /// The conversion into a `CollectionPath` failed.
     #[error("collection path conversion {0}")]
     CollectionPathConversion(String),
+    /// The conversion into a `DatabaseId` failed.
+    #[error("database id conversion {0}")]
+    DatabaseIdConversion(String),
+    /// The names are in different databases.
+    #[error("database name mismatch")]
+    DatabaseNameMismatch,
+    /// The path or name is deeper than `limits::MAX_DEPTH`.
+    #[error("depth exceeded")]
+    DepthOutOfBounds,
+    /// The id contains a character that is not allowed.
     #[error("contains invalid charactor")]
     ContainsInvalidCharacter,
+    /// The id contains a restricted string (e.g. `google`).
+    #[error("contains restricted string")]
+    ContainsRestrictedString,
+    /// The id contains a slash (`/`).
     #[error("contains slash")]
     ContainsSlash,
+    /// The conversion into a `DocumentId` failed.
     #[error("document id conversion {0}")]
     DocumentIdConversion(String),
+    /// The conversion into a `DocumentPath` failed.
     #[error("document path conversion {0}")]
     DocumentPathConversion(String),
+    /// The id ends with a hyphen (`-`).
     #[error("ends with hyphen")]
     EndsWithHyphen,
+    /// The conversion from or to a `gcloud-sdk` message failed.
+    #[cfg(feature = "gcloud-sdk")]
+    #[error("gcloud-sdk conversion {0}")]
+    GcloudSdkConversion(String),
+    /// The conversion from or to a `googapis` message failed.
+    #[cfg(feature = "googapis")]
+    #[error("googapis conversion {0}")]
+    GoogapisConversion(String),
+    /// The name does not have the `projects`, `databases` or `documents` segment in the expected position.
     #[error("invalid name")]
     InvalidName,
+    /// The name or path has the wrong number of segments (e.g. a document path with an odd number of segments).
     #[error("invalid number of path components")]
     InvalidNumberOfPathComponents,
+    /// The id or name is too short or too long.
     #[error("byte length exceeded")]
     LengthOutOfBounds,
+    /// The id matches the reserved pattern `__.*__`.
     #[error("matches the regular expression `__.*__`")]
     MatchesReservedIdPattern,
+    /// The id matches the UUID-like pattern `[0-9a-f]{8}(-[0-9a-f]{4}){3}-[0-9a-f]{12}`.
+    #[error("matches the regular expression `[0-9a-f]{{8}}(-[0-9a-f]{{4}}){{3}}-[0-9a-f]{{12}}`")]
+    MatchesUuidPattern,
+    /// The request to the metadata server failed.
+    #[cfg(feature = "metadata")]
+    #[error("metadata server {0}")]
+    MetadataServer(String),
+    /// The `name` of the document is empty.
+    #[cfg(feature = "proto")]
+    #[error("missing document name")]
+    MissingDocumentName,
+    /// The string does not contain a slash (`/`).
     #[error("not contains slash")]
     NotContainsSlash,
+    /// The value is not a reference value.
+    #[cfg(feature = "proto")]
+    #[error("not a reference value")]
+    NotReferenceValue,
+    /// The conversion into a `firestore` `ParentPathBuilder` failed.
+    #[cfg(feature = "firestore")]
+    #[error("parent path builder conversion {0}")]
+    ParentPathBuilderConversion(String),
+    /// The name or path does not start with the prefix.
+    #[error("prefix mismatch")]
+    PrefixMismatch,
+    /// The conversion into a `ProjectId` failed.
     #[error("project id conversion {0}")]
     ProjectIdConversion(String),
+    /// The project id is not found in the environment variables.
+    #[error("project id not found")]
+    ProjectIdNotFound,
+    /// The segment is not of the expected kind (a collection id or a document id).
+    #[error("segment kind mismatch")]
+    SegmentKindMismatch,
+    /// The shard index is not less than the number of shards.
+    #[error("shard index out of bounds")]
+    ShardIndexOutOfBounds,
+    /// The id is `.` or `..`.
     #[error("single period or double periods")]
     SinglePeriodOrDoublePeriods,
+    /// The id does not start with a letter.
     #[error("starts with non letter")]
     StartsWithNonLetter,
 }
+
+#[cfg(any(feature = "miette", feature = "tonic"))]
+impl ErrorKind {
+    /// Returns the name of this `ErrorKind` in `UPPER_SNAKE_CASE` (e.g. `LENGTH_OUT_OF_BOUNDS`).
+    pub(crate) fn reason(&self) -> &'static str {
+        match self {
+            ErrorKind::CollectionIdConversion(_) => "COLLECTION_ID_CONVERSION",
+            ErrorKind::CollectionPathConversion(_) => "COLLECTION_PATH_CONVERSION",
+            ErrorKind::DatabaseIdConversion(_) => "DATABASE_ID_CONVERSION",
+            ErrorKind::DatabaseNameMismatch => "DATABASE_NAME_MISMATCH",
+            ErrorKind::DepthOutOfBounds => "DEPTH_OUT_OF_BOUNDS",
+            ErrorKind::ContainsInvalidCharacter => "CONTAINS_INVALID_CHARACTER",
+            ErrorKind::ContainsRestrictedString => "CONTAINS_RESTRICTED_STRING",
+            ErrorKind::ContainsSlash => "CONTAINS_SLASH",
+            ErrorKind::DocumentIdConversion(_) => "DOCUMENT_ID_CONVERSION",
+            ErrorKind::DocumentPathConversion(_) => "DOCUMENT_PATH_CONVERSION",
+            ErrorKind::EndsWithHyphen => "ENDS_WITH_HYPHEN",
+            #[cfg(feature = "gcloud-sdk")]
+            ErrorKind::GcloudSdkConversion(_) => "GCLOUD_SDK_CONVERSION",
+            #[cfg(feature = "googapis")]
+            ErrorKind::GoogapisConversion(_) => "GOOGAPIS_CONVERSION",
+            ErrorKind::InvalidName => "INVALID_NAME",
+            ErrorKind::InvalidNumberOfPathComponents => "INVALID_NUMBER_OF_PATH_COMPONENTS",
+            ErrorKind::LengthOutOfBounds => "LENGTH_OUT_OF_BOUNDS",
+            ErrorKind::MatchesReservedIdPattern => "MATCHES_RESERVED_ID_PATTERN",
+            ErrorKind::MatchesUuidPattern => "MATCHES_UUID_PATTERN",
+            #[cfg(feature = "metadata")]
+            ErrorKind::MetadataServer(_) => "METADATA_SERVER",
+            #[cfg(feature = "proto")]
+            ErrorKind::MissingDocumentName => "MISSING_DOCUMENT_NAME",
+            ErrorKind::NotContainsSlash => "NOT_CONTAINS_SLASH",
+            #[cfg(feature = "proto")]
+            ErrorKind::NotReferenceValue => "NOT_REFERENCE_VALUE",
+            #[cfg(feature = "firestore")]
+            ErrorKind::ParentPathBuilderConversion(_) => "PARENT_PATH_BUILDER_CONVERSION",
+            ErrorKind::PrefixMismatch => "PREFIX_MISMATCH",
+            ErrorKind::ProjectIdConversion(_) => "PROJECT_ID_CONVERSION",
+            ErrorKind::ProjectIdNotFound => "PROJECT_ID_NOT_FOUND",
+            ErrorKind::SegmentKindMismatch => "SEGMENT_KIND_MISMATCH",
+            ErrorKind::ShardIndexOutOfBounds => "SHARD_INDEX_OUT_OF_BOUNDS",
+            ErrorKind::SinglePeriodOrDoublePeriods => "SINGLE_PERIOD_OR_DOUBLE_PERIODS",
+            ErrorKind::StartsWithNonLetter => "STARTS_WITH_NON_LETTER",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{
+        CollectionName, CollectionPath, DatabaseName, DocumentId, DocumentName, RootDocumentName,
+    };
+
+    #[test]
+    fn test_clone_and_eq() -> anyhow::Result<()> {
+        let errors = [
+            "projects/my-project/databases/my-database/documents/chatrooms/..",
+            "projects/my-project/databases/my-database/documents/chatrooms/..",
+            "projects/my-project/databases/my-database/documents/messages/..",
+            "projects/my-project/databases/my-database/documents/chatrooms",
+        ]
+        .into_iter()
+        .map(|s| DocumentName::from_str(s).unwrap_err())
+        .collect::<Vec<Error>>();
+        assert_eq!(errors[0], errors[1]);
+        assert_eq!(errors[0].clone(), errors[0]);
+        assert_ne!(errors[0], errors[2]);
+        assert_eq!(errors[0].kind(), errors[2].kind());
+        assert_eq!(
+            errors
+                .into_iter()
+                .collect::<std::collections::HashSet<Error>>()
+                .len(),
+            3
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_predicates() -> anyhow::Result<()> {
+        for (kind, is_invalid_character, is_length_error, is_structure_error) in [
+            (ErrorKind::ContainsInvalidCharacter, true, false, false),
+            (ErrorKind::ContainsRestrictedString, false, false, false),
+            (ErrorKind::ContainsSlash, true, false, false),
+            (ErrorKind::DatabaseNameMismatch, false, false, false),
+            (ErrorKind::DepthOutOfBounds, false, false, false),
+            (ErrorKind::EndsWithHyphen, true, false, false),
+            (ErrorKind::InvalidName, false, false, true),
+            (ErrorKind::InvalidNumberOfPathComponents, false, false, true),
+            (ErrorKind::LengthOutOfBounds, false, true, false),
+            (ErrorKind::MatchesReservedIdPattern, false, false, false),
+            (ErrorKind::MatchesUuidPattern, false, false, false),
+            (ErrorKind::NotContainsSlash, false, false, true),
+            (ErrorKind::PrefixMismatch, false, false, true),
+            (ErrorKind::ProjectIdNotFound, false, false, false),
+            (ErrorKind::SegmentKindMismatch, false, false, true),
+            (ErrorKind::SinglePeriodOrDoublePeriods, false, false, false),
+            (ErrorKind::StartsWithNonLetter, true, false, false),
+        ] {
+            let error = Error::from(kind);
+            assert_eq!(error.is_invalid_character(), is_invalid_character);
+            assert_eq!(error.is_length_error(), is_length_error);
+            assert_eq!(error.is_structure_error(), is_structure_error);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_location() -> anyhow::Result<()> {
+        let error =
+            DatabaseName::from_str("projects/my-project/databases/my_database").unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::ContainsInvalidCharacter);
+        assert_eq!(error.segment_index(), Some(3));
+        assert_eq!(error.segment(), Some("my_database"));
+        assert_eq!(error.byte_range(), Some(30..41));
+
+        let error =
+            RootDocumentName::from_str("projects/x/databases/my-database/documents").unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::LengthOutOfBounds);
+        assert_eq!(error.segment_index(), Some(1));
+        assert_eq!(error.segment(), Some("x"));
+        assert_eq!(error.byte_range(), Some(9..10));
+
+        let s =
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/__messages__";
+        let error = CollectionName::from_str(s).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::MatchesReservedIdPattern);
+        assert_eq!(error.segment_index(), Some(7));
+        assert_eq!(error.segment(), Some("__messages__"));
+        assert_eq!(error.byte_range(), Some(72..84));
+        assert_eq!(&s[72..84], "__messages__");
+
+        let s = "projects/my-project/databases/my-database/documents/chatrooms/..";
+        let error = DocumentName::from_str(s).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::SinglePeriodOrDoublePeriods);
+        assert_eq!(error.segment_index(), Some(6));
+        assert_eq!(error.byte_range(), Some(62..64));
+        assert_eq!(
+            error.to_string(),
+            "segment 6 (`..`) at bytes 62..64: single period or double periods"
+        );
+
+        let error = CollectionPath::from_str("chatrooms//messages").unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::LengthOutOfBounds);
+        assert_eq!(error.segment_index(), Some(1));
+        assert_eq!(error.segment(), Some(""));
+        assert_eq!(error.byte_range(), Some(10..10));
+
+        let error = DocumentId::from_str("..").unwrap_err();
+        assert_eq!(error.segment_index(), None);
+        assert_eq!(error.segment(), None);
+        assert_eq!(error.byte_range(), None);
+        assert_eq!(error.to_string(), "single period or double periods");
+
+        let error =
+            DocumentName::from_str("projects/my-project/databases/my-database/documents/chatrooms")
+                .unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidNumberOfPathComponents);
+        assert_eq!(error.segment_index(), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+// `fake::Dummy<fake::Faker>` implementations, which generate realistic valid values.
+//
+// - `ProjectId` and `DatabaseId`: a lorem word followed by a random suffix (`DatabaseId` is
+//   sometimes `(default)`)
+// - `CollectionId`: a lorem word
+// - `DocumentId`: 20 alphanumeric characters (like the ids generated by Firestore)
+// - names and paths: a depth of 1 to 3
+
+use ::fake::{faker::lorem::en::Word, Dummy, Fake, Faker, RngExt};
+
+use crate::{
+    CollectionId, CollectionName, CollectionPath, DatabaseId, DatabaseName, DocumentId,
+    DocumentName, DocumentPath, ProjectId, RootDocumentName,
+};
+
+// Returns `{word}-{digits}`, which satisfies the limits of `ProjectId` and `DatabaseId`.
+fn lowercase_id<R: RngExt + ?Sized>(rng: &mut R) -> String {
+    let word: String = Word().fake_with_rng(rng);
+    format!(
+        "{}-{}",
+        word.to_ascii_lowercase(),
+        rng.random_range(100_000..1_000_000)
+    )
+}
+
+impl Dummy<Faker> for CollectionId {
+    fn dummy_with_rng<R: RngExt + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        let word: String = Word().fake_with_rng(rng);
+        CollectionId::try_from(word).expect("a lorem word to be a valid collection id")
+    }
+}
+
+impl Dummy<Faker> for CollectionName {
+    fn dummy_with_rng<R: RngExt + ?Sized>(config: &Faker, rng: &mut R) -> Self {
+        CollectionName::new(
+            RootDocumentName::dummy_with_rng(config, rng),
+            CollectionPath::dummy_with_rng(config, rng),
+        )
+    }
+}
+
+impl Dummy<Faker> for CollectionPath {
+    fn dummy_with_rng<R: RngExt + ?Sized>(config: &Faker, rng: &mut R) -> Self {
+        let mut document_path = None;
+        for _ in 0..rng.random_range(0..3) {
+            document_path = Some(DocumentPath::new(
+                CollectionPath::new(document_path, CollectionId::dummy_with_rng(config, rng)),
+                DocumentId::dummy_with_rng(config, rng),
+            ));
+        }
+        CollectionPath::new(document_path, CollectionId::dummy_with_rng(config, rng))
+    }
+}
+
+impl Dummy<Faker> for DatabaseId {
+    fn dummy_with_rng<R: RngExt + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        if rng.random_bool(0.5) {
+            return DatabaseId::default();
+        }
+        DatabaseId::try_from(lowercase_id(rng)).expect("lowercase_id to be a valid database id")
+    }
+}
+
+impl Dummy<Faker> for DatabaseName {
+    fn dummy_with_rng<R: RngExt + ?Sized>(config: &Faker, rng: &mut R) -> Self {
+        DatabaseName::new(
+            ProjectId::dummy_with_rng(config, rng),
+            DatabaseId::dummy_with_rng(config, rng),
+        )
+    }
+}
+
+impl Dummy<Faker> for DocumentId {
+    fn dummy_with_rng<R: RngExt + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        let s: String = 20.fake_with_rng(rng);
+        DocumentId::try_from(s).expect("alphanumeric characters to be a valid document id")
+    }
+}
+
+impl Dummy<Faker> for DocumentName {
+    fn dummy_with_rng<R: RngExt + ?Sized>(config: &Faker, rng: &mut R) -> Self {
+        DocumentName::new(
+            RootDocumentName::dummy_with_rng(config, rng),
+            DocumentPath::dummy_with_rng(config, rng),
+        )
+    }
+}
+
+impl Dummy<Faker> for DocumentPath {
+    fn dummy_with_rng<R: RngExt + ?Sized>(config: &Faker, rng: &mut R) -> Self {
+        DocumentPath::new(
+            CollectionPath::dummy_with_rng(config, rng),
+            DocumentId::dummy_with_rng(config, rng),
+        )
+    }
+}
+
+impl Dummy<Faker> for ProjectId {
+    fn dummy_with_rng<R: RngExt + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        // Retries if the word contains a reserved word (e.g. `null`).
+        loop {
+            if let Ok(project_id) = ProjectId::try_from(lowercase_id(rng)) {
+                return project_id;
+            }
+        }
+    }
+}
+
+impl Dummy<Faker> for RootDocumentName {
+    fn dummy_with_rng<R: RngExt + ?Sized>(config: &Faker, rng: &mut R) -> Self {
+        RootDocumentName::new(DatabaseName::dummy_with_rng(config, rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        for _ in 0..100 {
+            let collection_name: CollectionName = Faker.fake();
+            assert_eq!(
+                CollectionName::from_str(collection_name.as_str())?,
+                collection_name
+            );
+            let document_name: DocumentName = Faker.fake();
+            assert_eq!(
+                DocumentName::from_str(document_name.as_str())?,
+                document_name
+            );
+            assert!((1..=3).contains(&document_name.depth()));
+            assert_eq!(document_name.document_id().as_ref().len(), 20);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_in_tuple() {
+        let (document_name, collection_name): (DocumentName, CollectionName) = Faker.fake();
+        assert!(DocumentName::from_str(document_name.as_str()).is_ok());
+        assert!(CollectionName::from_str(collection_name.as_str()).is_ok());
+    }
+}
@@ -0,0 +1,371 @@
+use std::borrow::Cow;
+
+use crate::{
+    AnyName, AnyPath, CollectionName, CollectionPath, DocumentName, DocumentPath, RootDocumentName,
+};
+
+/// The operations common to all name and path types.
+///
+/// This trait is implemented for `AnyName`, `CollectionName`, `DocumentName`, `RootDocumentName`
+/// (the names) and `AnyPath`, `CollectionPath`, `DocumentPath` (the paths).
+/// The parent of a name is an `AnyName` and the parent of a path is an `AnyPath`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{CollectionPath,DocumentName,FirestorePathLike};
+/// use std::str::FromStr;
+///
+/// fn describe<P: FirestorePathLike>(p: &P) -> String {
+///     format!("{} (depth={}, leaf_id={:?})", p.as_str(), p.depth(), p.leaf_id())
+/// }
+///
+/// assert_eq!(
+///     describe(&DocumentName::from_str(
+///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+///     )?),
+///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1 (depth=1, leaf_id=Some(\"chatroom1\"))"
+/// );
+/// assert_eq!(
+///     describe(&CollectionPath::from_str("chatrooms/chatroom1/messages")?),
+///     "chatrooms/chatroom1/messages (depth=2, leaf_id=Some(\"messages\"))"
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+pub trait FirestorePathLike: std::fmt::Display {
+    /// The type of the parent (`AnyName` for names and `AnyPath` for paths).
+    ///
+    /// The parent of the parent has the same type, so ancestors can be walked with a loop.
+    type Parent: FirestorePathLike<Parent = Self::Parent>;
+
+    /// Returns the string representation.
+    ///
+    /// This borrows the canonical string of `CollectionName` and `DocumentName` and allocates for the others.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentPath,FirestorePathLike};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(FirestorePathLike::as_str(&document_path), "chatrooms/chatroom1");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    fn as_str(&self) -> Cow<'_, str>;
+
+    /// Returns the depth (the number of collections).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentPath,FirestorePathLike,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(FirestorePathLike::depth(&document_path), 1);
+    /// let root_document_name = RootDocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents"
+    /// )?;
+    /// assert_eq!(FirestorePathLike::depth(&root_document_name), 0);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    fn depth(&self) -> usize;
+
+    /// Returns the last collection id or document id, or `None` for a `RootDocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentPath,FirestorePathLike,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(document_path.leaf_id(), Some("chatroom1"));
+    /// let root_document_name = RootDocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents"
+    /// )?;
+    /// assert_eq!(root_document_name.leaf_id(), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    fn leaf_id(&self) -> Option<&str>;
+
+    /// Returns the parent, or `None` if there is no parent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyPath,CollectionPath,DocumentPath,FirestorePathLike};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(
+    ///     FirestorePathLike::parent(&document_path),
+    ///     Some(AnyPath::from(CollectionPath::from_str("chatrooms")?))
+    /// );
+    /// let collection_path = CollectionPath::from_str("chatrooms")?;
+    /// assert_eq!(FirestorePathLike::parent(&collection_path), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    fn parent(&self) -> Option<Self::Parent>;
+}
+
+impl FirestorePathLike for AnyName {
+    type Parent = AnyName;
+
+    fn as_str(&self) -> Cow<'_, str> {
+        match self {
+            AnyName::Collection(collection_name) => FirestorePathLike::as_str(collection_name),
+            AnyName::Document(document_name) => FirestorePathLike::as_str(document_name),
+            AnyName::RootDocument(root_document_name) => {
+                FirestorePathLike::as_str(root_document_name)
+            }
+        }
+    }
+
+    fn depth(&self) -> usize {
+        match self {
+            AnyName::Collection(collection_name) => collection_name.depth(),
+            AnyName::Document(document_name) => document_name.depth(),
+            AnyName::RootDocument(_) => 0,
+        }
+    }
+
+    fn leaf_id(&self) -> Option<&str> {
+        match self {
+            AnyName::Collection(collection_name) => collection_name.leaf_id(),
+            AnyName::Document(document_name) => document_name.leaf_id(),
+            AnyName::RootDocument(_) => None,
+        }
+    }
+
+    fn parent(&self) -> Option<Self::Parent> {
+        AnyName::parent(self)
+    }
+}
+
+impl FirestorePathLike for AnyPath {
+    type Parent = AnyPath;
+
+    fn as_str(&self) -> Cow<'_, str> {
+        Cow::Owned(self.to_string())
+    }
+
+    fn depth(&self) -> usize {
+        match self {
+            AnyPath::Collection(collection_path) => collection_path.depth(),
+            AnyPath::Document(document_path) => document_path.depth(),
+        }
+    }
+
+    fn leaf_id(&self) -> Option<&str> {
+        match self {
+            AnyPath::Collection(collection_path) => collection_path.leaf_id(),
+            AnyPath::Document(document_path) => document_path.leaf_id(),
+        }
+    }
+
+    fn parent(&self) -> Option<Self::Parent> {
+        AnyPath::parent(self)
+    }
+}
+
+impl FirestorePathLike for CollectionName {
+    type Parent = AnyName;
+
+    fn as_str(&self) -> Cow<'_, str> {
+        Cow::Borrowed(CollectionName::as_str(self))
+    }
+
+    fn depth(&self) -> usize {
+        CollectionName::depth(self)
+    }
+
+    fn leaf_id(&self) -> Option<&str> {
+        Some(self.collection_id().as_ref())
+    }
+
+    fn parent(&self) -> Option<Self::Parent> {
+        Some(
+            CollectionName::parent(self)
+                .map(AnyName::from)
+                .unwrap_or_else(|| AnyName::from(self.root_document_name().clone())),
+        )
+    }
+}
+
+impl FirestorePathLike for CollectionPath {
+    type Parent = AnyPath;
+
+    fn as_str(&self) -> Cow<'_, str> {
+        Cow::Owned(self.to_string())
+    }
+
+    fn depth(&self) -> usize {
+        CollectionPath::depth(self)
+    }
+
+    fn leaf_id(&self) -> Option<&str> {
+        Some(self.collection_id().as_ref())
+    }
+
+    fn parent(&self) -> Option<Self::Parent> {
+        CollectionPath::parent(self).cloned().map(AnyPath::from)
+    }
+}
+
+impl FirestorePathLike for DocumentName {
+    type Parent = AnyName;
+
+    fn as_str(&self) -> Cow<'_, str> {
+        Cow::Borrowed(DocumentName::as_str(self))
+    }
+
+    fn depth(&self) -> usize {
+        DocumentName::depth(self)
+    }
+
+    fn leaf_id(&self) -> Option<&str> {
+        Some(self.document_id().as_ref())
+    }
+
+    fn parent(&self) -> Option<Self::Parent> {
+        Some(AnyName::from(DocumentName::parent(self)))
+    }
+}
+
+impl FirestorePathLike for DocumentPath {
+    type Parent = AnyPath;
+
+    fn as_str(&self) -> Cow<'_, str> {
+        Cow::Owned(self.to_string())
+    }
+
+    fn depth(&self) -> usize {
+        DocumentPath::depth(self)
+    }
+
+    fn leaf_id(&self) -> Option<&str> {
+        Some(self.document_id().as_ref())
+    }
+
+    fn parent(&self) -> Option<Self::Parent> {
+        Some(AnyPath::from(DocumentPath::parent(self).clone()))
+    }
+}
+
+impl FirestorePathLike for RootDocumentName {
+    type Parent = AnyName;
+
+    fn as_str(&self) -> Cow<'_, str> {
+        Cow::Owned(self.to_string())
+    }
+
+    fn depth(&self) -> usize {
+        0
+    }
+
+    fn leaf_id(&self) -> Option<&str> {
+        None
+    }
+
+    fn parent(&self) -> Option<Self::Parent> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn ancestors<P: FirestorePathLike>(p: &P) -> Vec<String> {
+        std::iter::successors(FirestorePathLike::parent(p), FirestorePathLike::parent)
+            .map(|parent| parent.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_names() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages";
+        let collection_name = CollectionName::from_str(s)?;
+        assert_eq!(FirestorePathLike::as_str(&collection_name), s);
+        assert_eq!(FirestorePathLike::depth(&collection_name), 2);
+        assert_eq!(collection_name.leaf_id(), Some("messages"));
+        assert_eq!(
+            ancestors(&collection_name),
+            [
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+                "projects/my-project/databases/my-database/documents/chatrooms",
+                "projects/my-project/databases/my-database/documents",
+            ]
+        );
+
+        let document_name = collection_name.doc("message1")?;
+        assert_eq!(FirestorePathLike::depth(&document_name), 2);
+        assert_eq!(document_name.leaf_id(), Some("message1"));
+        assert_eq!(
+            FirestorePathLike::parent(&document_name),
+            Some(AnyName::from(collection_name))
+        );
+
+        let any_name = AnyName::from(document_name);
+        assert_eq!(FirestorePathLike::depth(&any_name), 2);
+        assert_eq!(any_name.leaf_id(), Some("message1"));
+        assert_eq!(ancestors(&any_name).len(), 4);
+
+        let root_document_name =
+            RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?;
+        assert_eq!(
+            FirestorePathLike::as_str(&root_document_name),
+            "projects/my-project/databases/my-database/documents"
+        );
+        assert_eq!(FirestorePathLike::depth(&root_document_name), 0);
+        assert_eq!(root_document_name.leaf_id(), None);
+        assert_eq!(FirestorePathLike::parent(&root_document_name), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_paths() -> anyhow::Result<()> {
+        let collection_path = CollectionPath::from_str("chatrooms/chatroom1/messages")?;
+        assert_eq!(
+            FirestorePathLike::as_str(&collection_path),
+            "chatrooms/chatroom1/messages"
+        );
+        assert_eq!(FirestorePathLike::depth(&collection_path), 2);
+        assert_eq!(collection_path.leaf_id(), Some("messages"));
+        assert_eq!(
+            ancestors(&collection_path),
+            ["chatrooms/chatroom1", "chatrooms"]
+        );
+
+        let document_path = collection_path.doc("message1")?;
+        assert_eq!(FirestorePathLike::depth(&document_path), 2);
+        assert_eq!(document_path.leaf_id(), Some("message1"));
+        assert_eq!(
+            FirestorePathLike::parent(&document_path),
+            Some(AnyPath::from(collection_path))
+        );
+
+        let any_path = AnyPath::from(document_path);
+        assert_eq!(
+            FirestorePathLike::as_str(&any_path),
+            "chatrooms/chatroom1/messages/message1"
+        );
+        assert_eq!(any_path.leaf_id(), Some("message1"));
+        assert_eq!(ancestors(&any_path).len(), 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+// Conversions from and to the `firestore` (firestore-rs) crate types.
+
+use std::str::FromStr;
+
+use ::firestore::{FirestoreDb, FirestoreDocument, ParentPathBuilder};
+
+use crate::{error::ErrorKind, DocumentName, Error, RootDocumentName};
+
+impl DocumentName {
+    /// Creates a new `DocumentName` from the `name` of `document`.
+    ///
+    /// This method requires the `firestore` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore::FirestoreDocument;
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1";
+    /// let document = FirestoreDocument {
+    ///     name: s.to_string(),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     DocumentName::from_firestore_document(&document)?,
+    ///     DocumentName::from_str(s)?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_firestore_document(document: &FirestoreDocument) -> Result<Self, Error> {
+        DocumentName::from_str(&document.name)
+    }
+
+    /// Creates a new `ParentPathBuilder` of `db` that points to this document.
+    ///
+    /// The `ParentPathBuilder` is used as the `parent` of the subcollections of this document in the fluent API of `db`.
+    ///
+    /// This method requires the `firestore` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this document is not in the database of `db`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn example(db: &firestore::FirestoreDb) -> anyhow::Result<()> {
+    /// use firestore_path::RootDocumentName;
+    ///
+    /// let document_name = RootDocumentName::from_firestore_db(db)?.doc("chatrooms/chatroom1")?;
+    /// let parent_path = document_name.to_parent_path_builder(db)?;
+    /// assert_eq!(parent_path.to_string(), document_name.to_string());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_parent_path_builder(&self, db: &FirestoreDb) -> Result<ParentPathBuilder, Error> {
+        if db.get_documents_path() != &self.root_document_name().to_string() {
+            return Err(Error::from(ErrorKind::DatabaseNameMismatch));
+        }
+        let mut pairs = self.collection_ids().zip(self.document_ids());
+        let (collection_id, document_id) = pairs.next().expect("document name to have a pair");
+        let parent_path = db
+            .parent_path(collection_id, document_id)
+            .and_then(|parent_path| {
+                pairs.try_fold(parent_path, |parent_path, (collection_id, document_id)| {
+                    parent_path.at(collection_id, document_id)
+                })
+            })
+            .map_err(|e| Error::from(ErrorKind::ParentPathBuilderConversion(e.to_string())))?;
+        Ok(parent_path)
+    }
+}
+
+impl RootDocumentName {
+    /// Creates a new `RootDocumentName` of the database of `db`.
+    ///
+    /// This method requires the `firestore` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn example(db: &firestore::FirestoreDb) -> anyhow::Result<()> {
+    /// use firestore_path::RootDocumentName;
+    ///
+    /// let root_document_name = RootDocumentName::from_firestore_db(db)?;
+    /// assert_eq!(
+    ///     root_document_name.to_string(),
+    ///     db.get_documents_path().as_str()
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_firestore_db(db: &FirestoreDb) -> Result<Self, Error> {
+        RootDocumentName::from_str(db.get_documents_path())
+    }
+}
@@ -0,0 +1,198 @@
+use googleapis_tonic_google_firestore_v1::google::firestore::v1;
+
+use crate::{error::ErrorKind, Error};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Conversions of the `google.firestore.v1` messages between `googleapis-tonic-google-firestore-v1` and `gcloud-sdk`.
+///
+/// The request constructors of this crate (the `proto` feature) return the `googleapis-tonic-google-firestore-v1` messages.
+/// This trait converts them into the same messages of `gcloud-sdk` and vice versa, so that the constructors are shared by both.
+///
+/// This trait is sealed and implemented only for the messages that the constructors of this crate take or return
+/// (e.g. `GetDocumentRequest`, `StructuredQuery` and `ListCollectionIdsResponse`). Each message is paired with the message of the same name in `gcloud-sdk`.
+/// It requires the `gcloud-sdk` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{DocumentName,GcloudSdkInterop};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::Document;
+/// use std::str::FromStr;
+///
+/// let document_name = DocumentName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+/// )?;
+///
+/// let request: gcloud_sdk::google::firestore::v1::GetDocumentRequest =
+///     document_name.to_get_document_request().to_gcloud_sdk()?;
+/// assert_eq!(request.name, document_name.to_string());
+///
+/// let document = gcloud_sdk::google::firestore::v1::Document {
+///     name: document_name.to_string(),
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     DocumentName::try_from_document(&Document::from_gcloud_sdk(&document)?)?,
+///     document_name
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// The messages of different types cannot be converted into each other.
+///
+/// ```rust,compile_fail
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{DatabaseName,GcloudSdkInterop};
+/// use std::str::FromStr;
+///
+/// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+/// let request: gcloud_sdk::google::firestore::v1::GetDocumentRequest =
+///     database_name.to_commit_request(vec![]).to_gcloud_sdk()?;
+/// #     Ok(())
+/// # }
+/// ```
+pub trait GcloudSdkInterop: prost::Message + Default + private::Sealed {
+    /// The same message of `gcloud-sdk`.
+    type GcloudSdk: ::gcloud_sdk::prost::Message + Default;
+
+    /// Converts a `gcloud-sdk` message into this message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `message` cannot be decoded as this message.
+    fn from_gcloud_sdk(message: &Self::GcloudSdk) -> Result<Self, Error> {
+        Self::decode(::gcloud_sdk::prost::Message::encode_to_vec(message).as_slice())
+            .map_err(|e| Error::from(ErrorKind::GcloudSdkConversion(e.to_string())))
+    }
+
+    /// Converts this message into the `gcloud-sdk` message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this message cannot be decoded as the `gcloud-sdk` message.
+    fn to_gcloud_sdk(&self) -> Result<Self::GcloudSdk, Error> {
+        <Self::GcloudSdk as ::gcloud_sdk::prost::Message>::decode(self.encode_to_vec().as_slice())
+            .map_err(|e| Error::from(ErrorKind::GcloudSdkConversion(e.to_string())))
+    }
+}
+
+macro_rules! impl_gcloud_sdk_interop {
+    ($($($name:ident)::+),* $(,)?) => {
+        $(
+            impl private::Sealed for v1::$($name)::+ {}
+
+            impl GcloudSdkInterop for v1::$($name)::+ {
+                type GcloudSdk = ::gcloud_sdk::google::firestore::v1::$($name)::+;
+            }
+        )*
+    };
+}
+
+impl_gcloud_sdk_interop!(
+    BatchGetDocumentsRequest,
+    BeginTransactionRequest,
+    CommitRequest,
+    CreateDocumentRequest,
+    DeleteDocumentRequest,
+    Document,
+    DocumentMask,
+    GetDocumentRequest,
+    ListCollectionIdsRequest,
+    ListCollectionIdsResponse,
+    ListDocumentsRequest,
+    PartitionQueryRequest,
+    PartitionQueryResponse,
+    RunQueryRequest,
+    StructuredQuery,
+    structured_query::CollectionSelector,
+    Target,
+    UpdateDocumentRequest,
+    Value,
+    Write,
+);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ::gcloud_sdk::google::firestore::v1 as gcloud_sdk_v1;
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+        ListCollectionIdsResponse, PartitionQueryResponse,
+    };
+
+    use super::*;
+    use crate::{CollectionId, DatabaseName, DocumentName};
+
+    #[test]
+    fn test_requests() -> anyhow::Result<()> {
+        let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+        let document_name = database_name.doc("chatrooms/chatroom1/messages/message1")?;
+
+        let request: gcloud_sdk_v1::BatchGetDocumentsRequest = database_name
+            .to_batch_get_documents_request([&document_name])?
+            .to_gcloud_sdk()?;
+        assert_eq!(request.database, database_name.to_string());
+        assert_eq!(request.documents, [document_name.to_string()]);
+
+        let request: gcloud_sdk_v1::CreateDocumentRequest = document_name
+            .to_create_document_request(Default::default())
+            .to_gcloud_sdk()?;
+        assert_eq!(
+            request.parent,
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+        );
+        assert_eq!(request.collection_id, "messages");
+        assert_eq!(request.document_id, "message1");
+
+        let request: gcloud_sdk_v1::RunQueryRequest = document_name
+            .parent()
+            .to_run_query_request(Default::default())
+            .to_gcloud_sdk()?;
+        let Some(gcloud_sdk_v1::run_query_request::QueryType::StructuredQuery(query)) =
+            request.query_type
+        else {
+            unreachable!()
+        };
+        assert_eq!(query.from[0].collection_id, "messages");
+        Ok(())
+    }
+
+    #[test]
+    fn test_responses() -> anyhow::Result<()> {
+        let response = gcloud_sdk_v1::ListCollectionIdsResponse {
+            collection_ids: vec!["messages".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            CollectionId::from_list_collection_ids_response(
+                &ListCollectionIdsResponse::from_gcloud_sdk(&response)?
+            )?,
+            [CollectionId::from_str("messages")?]
+        );
+
+        let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1";
+        let response = gcloud_sdk_v1::PartitionQueryResponse {
+            partitions: vec![gcloud_sdk_v1::Cursor {
+                values: vec![gcloud_sdk_v1::Value {
+                    value_type: Some(gcloud_sdk_v1::value::ValueType::ReferenceValue(
+                        s.to_string(),
+                    )),
+                }],
+                before: true,
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            DocumentName::from_partition_query_response(&PartitionQueryResponse::from_gcloud_sdk(
+                &response
+            )?)?,
+            [DocumentName::from_str(s)?]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use googleapis_tonic_google_firestore_v1::google::firestore::v1;
+
+use crate::{error::ErrorKind, Error};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Conversions of the `google.firestore.v1` messages between `googleapis-tonic-google-firestore-v1` and the legacy `googapis` 0.6.
+///
+/// Like `GcloudSdkInterop` (the `gcloud-sdk` feature), this trait lets the request constructors of this crate (the `proto` feature) be used with the `googapis` messages.
+///
+/// Like `GcloudSdkInterop`, this trait is sealed and implemented only for the messages that the constructors of this crate take or return.
+/// Each message is paired with the message of the same name in `googapis`. It requires the `googapis` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{DocumentName,GoogapisInterop};
+/// use googleapis_tonic_google_firestore_v1::google::firestore::v1::Document;
+/// use std::str::FromStr;
+///
+/// let document_name = DocumentName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+/// )?;
+///
+/// let request: googapis::google::firestore::v1::GetDocumentRequest =
+///     document_name.to_get_document_request().to_googapis()?;
+/// assert_eq!(request.name, document_name.to_string());
+///
+/// let document = googapis::google::firestore::v1::Document {
+///     name: document_name.to_string(),
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     DocumentName::try_from_document(&Document::from_googapis(&document)?)?,
+///     document_name
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// The messages of different types cannot be converted into each other.
+///
+/// ```rust,compile_fail
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{DatabaseName,GoogapisInterop};
+/// use std::str::FromStr;
+///
+/// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+/// let request: googapis::google::firestore::v1::GetDocumentRequest =
+///     database_name.to_commit_request(vec![]).to_googapis()?;
+/// #     Ok(())
+/// # }
+/// ```
+pub trait GoogapisInterop: prost::Message + Default + private::Sealed {
+    /// The same message of `googapis`.
+    type Googapis: prost_09::Message + Default;
+
+    /// Converts a `googapis` message into this message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `message` cannot be decoded as this message.
+    fn from_googapis(message: &Self::Googapis) -> Result<Self, Error> {
+        Self::decode(prost_09::Message::encode_to_vec(message).as_slice())
+            .map_err(|e| Error::from(ErrorKind::GoogapisConversion(e.to_string())))
+    }
+
+    /// Converts this message into the `googapis` message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this message cannot be decoded as the `googapis` message.
+    fn to_googapis(&self) -> Result<Self::Googapis, Error> {
+        <Self::Googapis as prost_09::Message>::decode(self.encode_to_vec().as_slice())
+            .map_err(|e| Error::from(ErrorKind::GoogapisConversion(e.to_string())))
+    }
+}
+
+macro_rules! impl_googapis_interop {
+    ($($($name:ident)::+),* $(,)?) => {
+        $(
+            impl private::Sealed for v1::$($name)::+ {}
+
+            impl GoogapisInterop for v1::$($name)::+ {
+                type Googapis = ::googapis::google::firestore::v1::$($name)::+;
+            }
+        )*
+    };
+}
+
+impl_googapis_interop!(
+    BatchGetDocumentsRequest,
+    BeginTransactionRequest,
+    CommitRequest,
+    CreateDocumentRequest,
+    DeleteDocumentRequest,
+    Document,
+    DocumentMask,
+    GetDocumentRequest,
+    ListCollectionIdsRequest,
+    ListCollectionIdsResponse,
+    ListDocumentsRequest,
+    PartitionQueryRequest,
+    PartitionQueryResponse,
+    RunQueryRequest,
+    StructuredQuery,
+    structured_query::CollectionSelector,
+    Target,
+    UpdateDocumentRequest,
+    Value,
+    Write,
+);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use googapis::google::firestore::v1 as googapis_v1;
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::ListCollectionIdsResponse;
+
+    use super::*;
+    use crate::{CollectionId, DatabaseName};
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+        let document_name = database_name.doc("chatrooms/chatroom1/messages/message1")?;
+
+        let request: googapis_v1::CreateDocumentRequest = document_name
+            .to_create_document_request(Default::default())
+            .to_googapis()?;
+        assert_eq!(
+            request.parent,
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+        );
+        assert_eq!(request.collection_id, "messages");
+        assert_eq!(request.document_id, "message1");
+
+        let response = googapis_v1::ListCollectionIdsResponse {
+            collection_ids: vec!["messages".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            CollectionId::from_list_collection_ids_response(
+                &ListCollectionIdsResponse::from_googapis(&response)?
+            )?,
+            [CollectionId::from_str("messages")?]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{CollectionName, CollectionPath, DocumentName, DocumentPath};
+
+/// A name or path that has a parent.
+///
+/// `DocumentName` and `DocumentPath` always have a parent collection. For `CollectionName` and
+/// `CollectionPath`, the parent is `None` if the collection is at the top level.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{DocumentName,DocumentPath,HasParent};
+/// use std::str::FromStr;
+///
+/// // Returns the parent document of the parent collection.
+/// fn parent_document<T, C, D>(t: &T) -> Option<D>
+/// where
+///     T: HasParent<Parent = C>,
+///     C: HasParent<Parent = Option<D>>,
+/// {
+///     t.parent().parent()
+/// }
+///
+/// assert_eq!(
+///     parent_document(&DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?),
+///     Some(DocumentPath::from_str("chatrooms/chatroom1")?)
+/// );
+/// assert_eq!(
+///     parent_document(&DocumentName::from_str(
+///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+///     )?),
+///     None
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+pub trait HasParent {
+    /// The type of the parent.
+    type Parent;
+
+    /// Returns the parent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionPath,DocumentPath,HasParent};
+    /// use std::str::FromStr;
+    ///
+    /// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+    /// assert_eq!(
+    ///     HasParent::parent(&document_path),
+    ///     CollectionPath::from_str("chatrooms")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    fn parent(&self) -> Self::Parent;
+}
+
+impl HasParent for CollectionName {
+    type Parent = Option<DocumentName>;
+
+    fn parent(&self) -> Self::Parent {
+        CollectionName::parent(self)
+    }
+}
+
+impl HasParent for CollectionPath {
+    type Parent = Option<DocumentPath>;
+
+    fn parent(&self) -> Self::Parent {
+        CollectionPath::parent(self).cloned()
+    }
+}
+
+impl HasParent for DocumentName {
+    type Parent = CollectionName;
+
+    fn parent(&self) -> Self::Parent {
+        DocumentName::parent(self)
+    }
+}
+
+impl HasParent for DocumentPath {
+    type Parent = CollectionPath;
+
+    fn parent(&self) -> Self::Parent {
+        DocumentPath::parent(self).clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn parent_of<T: HasParent>(t: &T) -> T::Parent {
+        t.parent()
+    }
+
+    #[test]
+    fn test_names() -> anyhow::Result<()> {
+        let document_name = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
+        )?;
+        let collection_name = parent_of(&document_name);
+        assert_eq!(
+            collection_name,
+            CollectionName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+            )?
+        );
+        let document_name = parent_of(&collection_name);
+        assert_eq!(
+            document_name,
+            Some(DocumentName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+            )?)
+        );
+        let collection_name = document_name.as_ref().map(parent_of);
+        assert_eq!(
+            collection_name,
+            Some(CollectionName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms"
+            )?)
+        );
+        assert_eq!(collection_name.as_ref().and_then(parent_of), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_paths() -> anyhow::Result<()> {
+        let document_path = DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?;
+        let collection_path = parent_of(&document_path);
+        assert_eq!(
+            collection_path,
+            CollectionPath::from_str("chatrooms/chatroom1/messages")?
+        );
+        let document_path = parent_of(&collection_path);
+        assert_eq!(
+            document_path,
+            Some(DocumentPath::from_str("chatrooms/chatroom1")?)
+        );
+        let collection_path = document_path.as_ref().map(parent_of);
+        assert_eq!(
+            collection_path,
+            Some(CollectionPath::from_str("chatrooms")?)
+        );
+        assert_eq!(collection_path.as_ref().and_then(parent_of), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+// The string that backs the id types.
+//
+// An id is either a `'static` string (created in a const context by `from_static`) or an owned
+// string. Comparison, ordering and hashing only look at the string, so both forms are
+// interchangeable.
+#[derive(Clone)]
+pub(crate) enum IdString<T = Owned> {
+    Static(&'static str),
+    Owned(T),
+}
+
+#[cfg(feature = "smol_str")]
+pub(crate) type Owned = smol_str::SmolStr;
+
+#[cfg(not(feature = "smol_str"))]
+pub(crate) type Owned = String;
+
+impl<T: AsRef<str>> IdString<T> {
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            IdString::Static(s) => s,
+            IdString::Owned(s) => s.as_ref(),
+        }
+    }
+}
+
+impl<T: AsRef<str>> std::cmp::Eq for IdString<T> {}
+
+impl<T: AsRef<str>> std::cmp::Ord for IdString<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<T: AsRef<str>> std::cmp::PartialEq for IdString<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<T: AsRef<str>> std::cmp::PartialOrd for IdString<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: From<String>> std::convert::From<String> for IdString<T> {
+    fn from(s: String) -> Self {
+        IdString::Owned(T::from(s))
+    }
+}
+
+impl<T: AsRef<str>> std::fmt::Debug for IdString<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<T: AsRef<str>> std::fmt::Display for IdString<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<T: AsRef<str>> std::hash::Hash for IdString<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+// `const` string helpers for the id validations, which also run at compile time in `from_static`.
+
+pub(crate) const fn contains(s: &str, pattern: &str) -> bool {
+    let (s, pattern) = (s.as_bytes(), pattern.as_bytes());
+    let mut i = 0;
+    while i + pattern.len() <= s.len() {
+        if matches_at(s, i, pattern) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+pub(crate) const fn ends_with(s: &str, suffix: &str) -> bool {
+    s.len() >= suffix.len() && matches_at(s.as_bytes(), s.len() - suffix.len(), suffix.as_bytes())
+}
+
+pub(crate) const fn eq(s: &str, other: &str) -> bool {
+    s.len() == other.len() && matches_at(s.as_bytes(), 0, other.as_bytes())
+}
+
+pub(crate) const fn starts_with(s: &str, prefix: &str) -> bool {
+    s.len() >= prefix.len() && matches_at(s.as_bytes(), 0, prefix.as_bytes())
+}
+
+const fn matches_at(s: &[u8], offset: usize, pattern: &[u8]) -> bool {
+    let mut j = 0;
+    while j < pattern.len() {
+        if s[offset + j] != pattern[j] {
+            return false;
+        }
+        j += 1;
+    }
+    true
+}
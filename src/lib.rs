@@ -42,6 +42,11 @@
 //! #     Ok(())
 //! # }
 //! ```
+mod any_name;
+mod any_path;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod child_of;
 mod collection_id;
 mod collection_name;
 mod collection_path;
@@ -51,9 +56,46 @@ mod document_id;
 mod document_name;
 mod document_path;
 mod error;
+#[cfg(feature = "fake")]
+mod fake;
+mod firestore_path_like;
+#[cfg(feature = "firestore")]
+mod firestore_rs;
+#[cfg(feature = "gcloud-sdk")]
+mod gcloud_sdk_interop;
+#[cfg(feature = "googapis")]
+mod googapis_interop;
+mod has_parent;
+mod id_string;
+pub mod limits;
+mod macros;
+#[cfg(feature = "metadata")]
+mod metadata;
+#[cfg(feature = "miette")]
+mod miette;
+mod parse_options;
+mod path_builder;
+mod path_map;
+mod path_set;
 mod project_id;
+mod relation;
+#[cfg(feature = "proto")]
+mod requests;
 mod root_document_name;
+mod security_rules;
+mod segment;
+mod stable_hash;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "tonic")]
+mod tonic_status;
+mod write_batch;
 
+pub use self::any_name::AnyName;
+pub use self::any_path::AnyPath;
+pub use self::child_of::ChildOf;
 pub use self::collection_id::CollectionId;
 pub use self::collection_name::CollectionName;
 pub use self::collection_path::CollectionPath;
@@ -62,6 +104,23 @@ pub use self::database_name::DatabaseName;
 pub use self::document_id::DocumentId;
 pub use self::document_name::DocumentName;
 pub use self::document_path::DocumentPath;
-pub use self::error::Error;
+pub use self::error::{Error, ErrorKind};
+pub use self::firestore_path_like::FirestorePathLike;
+#[cfg(feature = "gcloud-sdk")]
+pub use self::gcloud_sdk_interop::GcloudSdkInterop;
+#[cfg(feature = "googapis")]
+pub use self::googapis_interop::GoogapisInterop;
+pub use self::has_parent::HasParent;
+#[doc(hidden)]
+pub use self::macros::__private;
+pub use self::parse_options::{ParseOptions, SlashFix};
+pub use self::path_builder::PathBuilder;
+pub use self::path_map::PathMap;
+pub use self::path_set::PathSet;
 pub use self::project_id::ProjectId;
+pub use self::relation::Relation;
 pub use self::root_document_name::RootDocumentName;
+pub use self::segment::Segment;
+pub use self::write_batch::WriteBatch;
+#[cfg(feature = "derive")]
+pub use firestore_path_derive::FirestorePath;
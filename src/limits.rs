@@ -0,0 +1,210 @@
+//! The limits of Firestore that this crate checks.
+//!
+//! The constants are the limits used by `FromStr` of each type, and the `check_*` functions check a
+//! string (or a number) against them without creating a value.
+//!
+//! - <https://firebase.google.com/docs/firestore/quotas#collections_documents_and_fields>
+//! - <https://firebase.google.com/docs/firestore/reference/rest/v1/projects.databases/create#query-parameters>
+//! - <https://cloud.google.com/resource-manager/docs/creating-managing-projects>
+//!
+//! # Examples
+//!
+//! ```rust
+//! use firestore_path::limits;
+//!
+//! assert!(limits::check_document_id("chatroom1").is_ok());
+//! assert!(limits::check_document_id(&"x".repeat(limits::MAX_ID_BYTES + 1)).is_err());
+//! assert!(limits::check_name_length(&"x".repeat(limits::MAX_DOCUMENT_NAME_BYTES)).is_ok());
+//! assert!(limits::check_name_length(&"x".repeat(limits::MAX_DOCUMENT_NAME_BYTES + 1)).is_err());
+//! ```
+
+use crate::{collection_id, database_id, document_id, error::ErrorKind, project_id, Error};
+
+/// The maximum length in bytes of a `DatabaseId` (except `(default)`).
+pub const MAX_DATABASE_ID_LEN: usize = 63;
+
+/// The maximum depth (the number of collections) of a document or a collection.
+pub const MAX_DEPTH: usize = 100;
+
+/// The maximum number of documents in a `BatchGetDocumentsRequest` (`proto` feature).
+///
+/// Firestore does not document a limit on the number of documents in a `BatchGetDocumentsRequest`.
+/// This is the cap this crate uses so that a single request stays small; split larger sets of
+/// documents into several requests.
+pub const MAX_DOCUMENTS_PER_BATCH_GET: usize = 1_000;
+
+/// The maximum number of documents in a `DocumentsTarget` of the `Listen` (`proto` feature).
+///
+/// Firestore does not document a limit on the number of documents in a `DocumentsTarget`. This is
+/// the cap this crate uses so that a single target stays small; split larger sets of documents
+/// into several targets.
+pub const MAX_DOCUMENTS_PER_TARGET: usize = 1_000;
+
+/// The maximum length in bytes of a `DocumentName` (and of the other names).
+pub const MAX_DOCUMENT_NAME_BYTES: usize = 6_144;
+
+/// The maximum length in bytes of a `CollectionId` or a `DocumentId`.
+pub const MAX_ID_BYTES: usize = 1_500;
+
+/// The maximum length in bytes of a `ProjectId`.
+pub const MAX_PROJECT_ID_LEN: usize = 30;
+
+/// The minimum length in bytes of a `DatabaseId` (except `(default)`).
+pub const MIN_DATABASE_ID_LEN: usize = 4;
+
+/// The minimum length in bytes of a `ProjectId`.
+pub const MIN_PROJECT_ID_LEN: usize = 6;
+
+/// Checks that `s` is a valid `CollectionId`.
+///
+/// # Examples
+///
+/// ```rust
+/// use firestore_path::limits;
+///
+/// assert!(limits::check_collection_id("chatrooms").is_ok());
+/// assert!(limits::check_collection_id("chat/rooms").is_err());
+/// ```
+pub fn check_collection_id(s: &str) -> Result<(), Error> {
+    Ok(collection_id::validate(s)?)
+}
+
+/// Checks that `s` is a valid `DatabaseId`.
+///
+/// # Examples
+///
+/// ```rust
+/// use firestore_path::limits;
+///
+/// assert!(limits::check_database_id("my-database").is_ok());
+/// assert!(limits::check_database_id("(default)").is_ok());
+/// assert!(limits::check_database_id("db").is_err());
+/// ```
+pub fn check_database_id(s: &str) -> Result<(), Error> {
+    Ok(database_id::validate(s)?)
+}
+
+/// Checks that `depth` (the number of collections) is at most [`MAX_DEPTH`].
+///
+/// # Examples
+///
+/// ```rust
+/// use firestore_path::limits;
+///
+/// assert!(limits::check_depth(limits::MAX_DEPTH).is_ok());
+/// assert!(limits::check_depth(limits::MAX_DEPTH + 1).is_err());
+/// ```
+pub fn check_depth(depth: usize) -> Result<(), Error> {
+    if depth > MAX_DEPTH {
+        return Err(Error::from(ErrorKind::DepthOutOfBounds));
+    }
+    Ok(())
+}
+
+/// Checks that `s` is a valid `DocumentId`.
+///
+/// # Examples
+///
+/// ```rust
+/// use firestore_path::limits;
+///
+/// assert!(limits::check_document_id("chatroom1").is_ok());
+/// assert!(limits::check_document_id("..").is_err());
+/// ```
+pub fn check_document_id(s: &str) -> Result<(), Error> {
+    Ok(document_id::validate(s)?)
+}
+
+/// Checks that the length of the name `s` is 1 to [`MAX_DOCUMENT_NAME_BYTES`] bytes.
+///
+/// This does not check the segments of `s`.
+///
+/// # Examples
+///
+/// ```rust
+/// use firestore_path::limits;
+///
+/// assert!(limits::check_name_length("projects/my-project/databases/my-database").is_ok());
+/// assert!(limits::check_name_length("").is_err());
+/// ```
+pub fn check_name_length(s: &str) -> Result<(), Error> {
+    if !(1..=MAX_DOCUMENT_NAME_BYTES).contains(&s.len()) {
+        return Err(Error::from(ErrorKind::LengthOutOfBounds));
+    }
+    Ok(())
+}
+
+/// Checks that `s` is a valid `ProjectId`.
+///
+/// # Examples
+///
+/// ```rust
+/// use firestore_path::limits;
+///
+/// assert!(limits::check_project_id("my-project").is_ok());
+/// assert!(limits::check_project_id("my-google-project").is_err());
+/// ```
+pub fn check_project_id(s: &str) -> Result<(), Error> {
+    Ok(project_id::validate(s)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{CollectionId, DatabaseId, DocumentId, ProjectId};
+
+    #[test]
+    fn test_check_ids() -> anyhow::Result<()> {
+        for s in [
+            "",
+            "(default)",
+            "x".repeat(MIN_DATABASE_ID_LEN).as_str(),
+            "x".repeat(MIN_PROJECT_ID_LEN).as_str(),
+            "x".repeat(MAX_DATABASE_ID_LEN + 1).as_str(),
+            "x".repeat(MAX_ID_BYTES).as_str(),
+            "x".repeat(MAX_ID_BYTES + 1).as_str(),
+            "chat/rooms",
+            "__x__",
+            "my-google-project",
+        ] {
+            assert_eq!(
+                check_collection_id(s).is_ok(),
+                CollectionId::from_str(s).is_ok()
+            );
+            assert_eq!(
+                check_database_id(s).is_ok(),
+                DatabaseId::from_str(s).is_ok()
+            );
+            assert_eq!(
+                check_document_id(s).is_ok(),
+                DocumentId::from_str(s).is_ok()
+            );
+            assert_eq!(check_project_id(s).is_ok(), ProjectId::from_str(s).is_ok());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_depth_and_name_length() -> anyhow::Result<()> {
+        assert!(check_depth(0).is_ok());
+        assert!(check_depth(MAX_DEPTH).is_ok());
+        assert_eq!(
+            check_depth(MAX_DEPTH + 1).unwrap_err().kind(),
+            &ErrorKind::DepthOutOfBounds
+        );
+        assert_eq!(
+            check_name_length("").unwrap_err().kind(),
+            &ErrorKind::LengthOutOfBounds
+        );
+        assert!(check_name_length(&"x".repeat(MAX_DOCUMENT_NAME_BYTES)).is_ok());
+        assert_eq!(
+            check_name_length(&"x".repeat(MAX_DOCUMENT_NAME_BYTES + 1))
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::LengthOutOfBounds
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,456 @@
+/// Creates a `CollectionId` from a string literal validated at compile time.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{collection_id,CollectionId};
+/// use std::str::FromStr;
+///
+/// let collection_id: CollectionId = collection_id!("chatrooms");
+/// assert_eq!(collection_id, CollectionId::from_str("chatrooms")?);
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// ```rust,compile_fail
+/// use firestore_path::collection_id;
+///
+/// let collection_id = collection_id!("chat/rooms");
+/// ```
+#[macro_export]
+macro_rules! collection_id {
+    ($s:literal) => {{
+        const COLLECTION_ID: $crate::CollectionId = $crate::CollectionId::from_static($s);
+        COLLECTION_ID
+    }};
+}
+
+/// Creates a `CollectionPath` from a string literal validated at compile time.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{collection_path,CollectionPath};
+/// use std::str::FromStr;
+///
+/// let collection_path: CollectionPath = collection_path!("chatrooms/chatroom1/messages");
+/// assert_eq!(
+///     collection_path,
+///     CollectionPath::from_str("chatrooms/chatroom1/messages")?
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// ```rust,compile_fail
+/// use firestore_path::collection_path;
+///
+/// let collection_path = collection_path!("chatrooms/chatroom1");
+/// ```
+#[macro_export]
+macro_rules! collection_path {
+    ($s:literal) => {{
+        const _: () = ::std::assert!(
+            $crate::__private::is_valid_collection_path($s),
+            ::std::concat!("invalid collection path: ", $s)
+        );
+        <$crate::CollectionPath as ::std::str::FromStr>::from_str($s)
+            .expect("the collection path to be validated at compile time")
+    }};
+}
+
+/// Creates a `DocumentName` from a string literal validated at compile time.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{document_name,DocumentName};
+/// use std::str::FromStr;
+///
+/// let document_name: DocumentName = document_name!(
+///     "projects/my-project/databases/(default)/documents/chatrooms/chatroom1"
+/// );
+/// assert_eq!(
+///     document_name,
+///     DocumentName::from_str(
+///         "projects/my-project/databases/(default)/documents/chatrooms/chatroom1"
+///     )?
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// ```rust,compile_fail
+/// use firestore_path::document_name;
+///
+/// let document_name = document_name!(
+///     "projects/my-project/databases/(default)/documents/chatrooms"
+/// );
+/// ```
+#[macro_export]
+macro_rules! document_name {
+    ($s:literal) => {{
+        const _: () = ::std::assert!(
+            $crate::__private::is_valid_document_name($s),
+            ::std::concat!("invalid document name: ", $s)
+        );
+        <$crate::DocumentName as ::std::str::FromStr>::from_str($s)
+            .expect("the document name to be validated at compile time")
+    }};
+}
+
+/// Defines a module of typed path functions for each collection in a schema.
+///
+/// Each collection `x` becomes a module `x` with the following functions, where the parameters
+/// are the document ids of the ancestors named after their collections:
+///
+/// - `x::collection(..) -> Result<CollectionPath, Error>`
+/// - `x::doc(.., x) -> Result<DocumentPath, Error>`
+///
+/// Subcollections are nested in braces after their parent collection.
+/// The collection ids are validated at compile time.
+/// Because the parameters are named after the collections, a collection cannot have the same name
+/// as one of its ancestors.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{firestore_schema,CollectionPath,DocumentPath};
+/// use std::str::FromStr;
+///
+/// firestore_schema! {
+///     chatrooms {
+///         messages
+///     }
+///     users
+/// }
+///
+/// assert_eq!(chatrooms::collection()?, CollectionPath::from_str("chatrooms")?);
+/// assert_eq!(chatrooms::doc("chatroom1")?, DocumentPath::from_str("chatrooms/chatroom1")?);
+/// assert_eq!(
+///     chatrooms::messages::collection("chatroom1")?,
+///     CollectionPath::from_str("chatrooms/chatroom1/messages")?
+/// );
+/// assert_eq!(
+///     chatrooms::messages::doc("chatroom1", "message1")?,
+///     DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?
+/// );
+/// assert_eq!(users::doc("user1")?, DocumentPath::from_str("users/user1")?);
+/// #     Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! firestore_schema {
+    (@collections [$($parent:ident)*]) => {};
+    (@collections [$($parent:ident)*] $name:ident $($rest:tt)*) => {
+        $crate::firestore_schema!(@collection [$($parent)*] $name {} $($rest)*);
+    };
+    (@collection [$($parent:ident)*] $name:ident {} { $($children:tt)* } $($rest:tt)*) => {
+        $crate::firestore_schema!(@collection [$($parent)*] $name { $($children)* } $($rest)*);
+    };
+    (@collection [$($parent:ident)*] $name:ident { $($children:tt)* } $($rest:tt)*) => {
+        #[doc = ::std::concat!("The `", ::std::stringify!($name), "` collection.")]
+        #[allow(dead_code)]
+        pub mod $name {
+            #[doc = ::std::concat!("Returns the path of the `", ::std::stringify!($name), "` collection.")]
+            pub fn collection(
+                $($parent: impl ::std::convert::AsRef<str>),*
+            ) -> ::std::result::Result<$crate::CollectionPath, $crate::Error> {
+                $crate::__private::schema_collection_path(
+                    ::std::vec![
+                        $({
+                            const ID: $crate::CollectionId =
+                                $crate::CollectionId::from_static(::std::stringify!($parent));
+                            ID
+                        },)*
+                        {
+                            const ID: $crate::CollectionId =
+                                $crate::CollectionId::from_static(::std::stringify!($name));
+                            ID
+                        }
+                    ],
+                    &[$(::std::convert::AsRef::<str>::as_ref(&$parent)),*],
+                )
+            }
+
+            #[doc = ::std::concat!("Returns the path of a document in the `", ::std::stringify!($name), "` collection.")]
+            pub fn doc(
+                $($parent: impl ::std::convert::AsRef<str>,)*
+                $name: impl ::std::convert::AsRef<str>
+            ) -> ::std::result::Result<$crate::DocumentPath, $crate::Error> {
+                collection($($parent),*)?.into_doc(::std::convert::AsRef::<str>::as_ref(&$name))
+            }
+
+            $crate::firestore_schema!(@collections [$($parent)* $name] $($children)*);
+        }
+
+        $crate::firestore_schema!(@collections [$($parent)*] $($rest)*);
+    };
+    ($($schema:tt)*) => {
+        $crate::firestore_schema!(@collections [] $($schema)*);
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use crate::{
+        collection_id, database_id, document_id, error::ErrorKind, limits, project_id,
+        CollectionId, CollectionPath, Error,
+    };
+
+    pub const fn is_valid_collection_path(s: &str) -> bool {
+        matches!(path_kind(s), Some(PathKind::Collection))
+    }
+
+    pub const fn is_valid_document_name(s: &str) -> bool {
+        // <https://firebase.google.com/docs/firestore/quotas#collections_documents_and_fields>
+        if s.is_empty() || s.len() > limits::MAX_DOCUMENT_NAME_BYTES {
+            return false;
+        }
+
+        let (projects, Some(s)) = split_first(s) else {
+            return false;
+        };
+        let (project_id, Some(s)) = split_first(s) else {
+            return false;
+        };
+        let (databases, Some(s)) = split_first(s) else {
+            return false;
+        };
+        let (database_id, Some(s)) = split_first(s) else {
+            return false;
+        };
+        let (documents, Some(document_path)) = split_first(s) else {
+            return false;
+        };
+        crate::id_string::eq(projects, "projects")
+            && is_ok(project_id::validate(project_id))
+            && crate::id_string::eq(databases, "databases")
+            && is_ok(database_id::validate(database_id))
+            && crate::id_string::eq(documents, "documents")
+            && matches!(path_kind(document_path), Some(PathKind::Document))
+    }
+
+    // Returns the path `c0/d0/c1/d1/.../cn` of the collection ids `[c0, ..., cn]` and the document ids `[d0, ..., dn-1]`.
+    pub fn schema_collection_path(
+        collection_ids: Vec<CollectionId>,
+        document_ids: &[&str],
+    ) -> Result<CollectionPath, Error> {
+        let mut collection_ids = collection_ids.into_iter();
+        let mut collection_path = CollectionPath::from(
+            collection_ids
+                .next()
+                .expect("the schema to have at least one collection id"),
+        );
+        for (document_id, collection_id) in document_ids.iter().zip(collection_ids) {
+            collection_path = collection_path
+                .into_doc(*document_id)?
+                .into_collection(CollectionPath::from(collection_id))?;
+        }
+        Ok(collection_path)
+    }
+
+    enum PathKind {
+        Collection,
+        Document,
+    }
+
+    // Returns the kind of the path `s`, or `None` if `s` is not a valid path.
+    const fn path_kind(s: &str) -> Option<PathKind> {
+        let mut rest = s;
+        let mut is_collection = true;
+        let mut number_of_segments = 0;
+        loop {
+            // <https://firebase.google.com/docs/firestore/quotas#collections_documents_and_fields>
+            number_of_segments += 1;
+            if number_of_segments > 2 * limits::MAX_DEPTH {
+                return None;
+            }
+            let (segment, next) = split_first(rest);
+            let is_valid = if is_collection {
+                is_ok(collection_id::validate(segment))
+            } else {
+                is_ok(document_id::validate(segment))
+            };
+            if !is_valid {
+                return None;
+            }
+            match next {
+                Some(next) => {
+                    rest = next;
+                    is_collection = !is_collection;
+                }
+                None if is_collection => return Some(PathKind::Collection),
+                None => return Some(PathKind::Document),
+            }
+        }
+    }
+
+    // `ErrorKind` has a destructor, which cannot be run in a const context.
+    const fn is_ok(result: Result<(), ErrorKind>) -> bool {
+        let is_ok = result.is_ok();
+        std::mem::forget(result);
+        is_ok
+    }
+
+    // Returns the part of `s` before the first slash and the rest after it (`None` if `s` contains no slash).
+    const fn split_first(s: &str) -> (&str, Option<&str>) {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'/' {
+                let (first, rest) = s.split_at(i);
+                let (_, rest) = rest.split_at(1);
+                return (first, Some(rest));
+            }
+            i += 1;
+        }
+        (s, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{CollectionId, CollectionPath, DocumentId, DocumentName, DocumentPath};
+
+    use super::__private::*;
+
+    #[test]
+    fn test_collection_id() -> anyhow::Result<()> {
+        assert_eq!(
+            collection_id!("chatrooms"),
+            CollectionId::from_str("chatrooms")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_path() -> anyhow::Result<()> {
+        assert_eq!(
+            collection_path!("chatrooms/chatroom1/messages"),
+            CollectionPath::from_str("chatrooms/chatroom1/messages")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_document_name() -> anyhow::Result<()> {
+        assert_eq!(
+            document_name!(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+            ),
+            DocumentName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+            )?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_firestore_schema() -> anyhow::Result<()> {
+        let reaction_id = "reaction1".to_string();
+        firestore_schema! {
+            chatrooms {
+                members
+                messages {
+                    reactions
+                }
+            }
+            users
+        }
+
+        assert_eq!(
+            chatrooms::collection()?,
+            CollectionPath::from_str("chatrooms")?
+        );
+        assert_eq!(
+            chatrooms::doc("chatroom1")?,
+            DocumentPath::from_str("chatrooms/chatroom1")?
+        );
+        assert_eq!(
+            chatrooms::members::doc("chatroom1", "member1")?,
+            DocumentPath::from_str("chatrooms/chatroom1/members/member1")?
+        );
+        assert_eq!(
+            chatrooms::messages::collection("chatroom1")?,
+            CollectionPath::from_str("chatrooms/chatroom1/messages")?
+        );
+        assert_eq!(
+            chatrooms::messages::reactions::doc(
+                "chatroom1",
+                DocumentId::from_str("message1")?,
+                &reaction_id
+            )?,
+            DocumentPath::from_str("chatrooms/chatroom1/messages/message1/reactions/reaction1")?
+        );
+        assert_eq!(users::doc("user1")?, DocumentPath::from_str("users/user1")?);
+
+        assert!(chatrooms::doc("chat/room1").is_err());
+        assert!(chatrooms::messages::collection("").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_valid_collection_path() {
+        for (s, expected) in [
+            ("", false),
+            ("chatrooms", true),
+            ("chatrooms/", false),
+            ("chatrooms/chatroom1", false),
+            ("chatrooms//messages", false),
+            ("chatrooms/chatroom1/messages", true),
+            ("chatrooms/../messages", false),
+            ("__x__/chatroom1/messages", false),
+        ] {
+            assert_eq!(is_valid_collection_path(s), expected, "{s}");
+            assert_eq!(CollectionPath::from_str(s).is_ok(), expected, "{s}");
+        }
+        for (depth, expected) in [(100, true), (101, false)] {
+            let s = format!("{}c", "c/d/".repeat(depth - 1));
+            assert_eq!(is_valid_collection_path(&s), expected, "{depth}");
+            assert_eq!(CollectionPath::from_str(&s).is_ok(), expected, "{depth}");
+        }
+    }
+
+    #[test]
+    fn test_is_valid_document_name() {
+        let root = "projects/my-project/databases/my-database/documents";
+        for (s, expected) in [
+            (root.to_string(), false),
+            (format!("{root}/chatrooms"), false),
+            (format!("{root}/chatrooms/chatroom1"), true),
+            (
+                format!("{root}/chatrooms/chatroom1/messages/message1"),
+                true,
+            ),
+            (format!("{root}/chatrooms/chatroom1/"), false),
+            (format!("{root}/c/{}", "x".repeat(1500)), true),
+            (format!("{root}{}", "/c/d".repeat(1_600)), false),
+            (format!("{root}{}", "/c/d".repeat(100)), true),
+            (format!("{root}{}", "/c/d".repeat(101)), false),
+            (
+                "projects/my-project/databases/(default)/documents/chatrooms/chatroom1".to_string(),
+                true,
+            ),
+            (
+                "projects/MY-PROJECT/databases/my-database/documents/chatrooms/chatroom1"
+                    .to_string(),
+                false,
+            ),
+            (
+                "projects/my-project/databases/my-database/document/chatrooms/chatroom1"
+                    .to_string(),
+                false,
+            ),
+        ] {
+            assert_eq!(is_valid_document_name(&s), expected, "{s}");
+            assert_eq!(DocumentName::from_str(&s).is_ok(), expected, "{s}");
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Lookup of the project id from the metadata server of Compute Engine, Cloud Run and the other Google Cloud environments.
+
+use std::{str::FromStr, time::Duration};
+
+use crate::{error::ErrorKind, Error, ProjectId};
+
+// The metadata server responds quickly on Google Cloud. Outside Google Cloud, `metadata.google.internal`
+// may not resolve or may not respond at all, so the request gives up early like the Google auth libraries.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+impl ProjectId {
+    /// Gets the `ProjectId` of the current environment from the metadata server.
+    ///
+    /// The host of the metadata server is `GCE_METADATA_HOST` if the environment variable is set, otherwise `metadata.google.internal`.
+    /// The request fails if it cannot connect within 1 second or does not complete within 3 seconds.
+    ///
+    /// This method requires the `metadata` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or times out (e.g. outside Google Cloud) or the response is not a valid `ProjectId`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseId,DatabaseName,ProjectId};
+    ///
+    /// let project_id = match ProjectId::from_env() {
+    ///     Ok(project_id) => project_id,
+    ///     Err(_) => ProjectId::from_metadata_server().await?,
+    /// };
+    /// let database_name = DatabaseName::new(project_id, DatabaseId::default());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub async fn from_metadata_server() -> Result<Self, Error> {
+        let host = std::env::var("GCE_METADATA_HOST")
+            .unwrap_or_else(|_| "metadata.google.internal".to_string());
+        from_metadata_server_at(&host).await
+    }
+}
+
+async fn from_metadata_server_at(host: &str) -> Result<ProjectId, Error> {
+    // <https://cloud.google.com/compute/docs/metadata/predefined-metadata-keys#project-metadata>
+    let client = reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(TIMEOUT)
+        .build()
+        .map_err(|e| Error::from(ErrorKind::MetadataServer(e.to_string())))?;
+    let response = client
+        .get(format!(
+            "http://{}/computeMetadata/v1/project/project-id",
+            host
+        ))
+        .header("Metadata-Flavor", "Google")
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| Error::from(ErrorKind::MetadataServer(e.to_string())))?;
+    let body = response
+        .text()
+        .await
+        .map_err(|e| Error::from(ErrorKind::MetadataServer(e.to_string())))?;
+    ProjectId::from_str(body.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    fn serve_once(status: &'static str, body: &'static str) -> anyhow::Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let host = listener.local_addr()?.to_string();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept");
+            let mut buf = [0_u8; 1024];
+            let n = stream.read(&mut buf).expect("read");
+            let request = String::from_utf8_lossy(&buf[..n]).to_ascii_lowercase();
+            let status = if request.contains("metadata-flavor: google") {
+                status
+            } else {
+                "403 Forbidden"
+            };
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .expect("write");
+        });
+        Ok(host)
+    }
+
+    #[tokio::test]
+    async fn test_from_metadata_server_at() -> anyhow::Result<()> {
+        let host = serve_once("200 OK", "my-project\n")?;
+        assert_eq!(
+            from_metadata_server_at(&host).await?,
+            ProjectId::from_str("my-project")?
+        );
+
+        let host = serve_once("404 Not Found", "")?;
+        assert!(matches!(
+            from_metadata_server_at(&host).await.unwrap_err().kind(),
+            ErrorKind::MetadataServer(_)
+        ));
+
+        let host = serve_once("200 OK", "My-Project")?;
+        assert_eq!(
+            from_metadata_server_at(&host).await.unwrap_err().kind(),
+            &ErrorKind::ContainsInvalidCharacter
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_from_metadata_server_at_timeout() -> anyhow::Result<()> {
+        // Accepts the connection but never responds.
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let host = listener.local_addr()?.to_string();
+        std::thread::spawn(move || {
+            let (_stream, _) = listener.accept().expect("accept");
+            std::thread::sleep(TIMEOUT * 2);
+        });
+
+        let started_at = std::time::Instant::now();
+        assert!(matches!(
+            from_metadata_server_at(&host).await.unwrap_err().kind(),
+            ErrorKind::MetadataServer(_)
+        ));
+        assert!(started_at.elapsed() < TIMEOUT * 2);
+        Ok(())
+    }
+}
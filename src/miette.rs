@@ -0,0 +1,98 @@
+// `miette::Diagnostic` implementation for `Error`.
+//
+// `Error` does not keep the parsed string, so the source code is attached by the caller
+// (e.g. `miette::Report::new(error).with_source_code(input)`).
+
+use ::miette::{Diagnostic, LabeledSpan};
+
+use crate::Error;
+
+/// Reports an `Error` as a `miette` diagnostic.
+///
+/// The `code` is `firestore_path::{kind}` (e.g. `firestore_path::length_out_of_bounds`),
+/// and the segment that caused the `Error` (if any) is labeled with the `ErrorKind`.
+///
+/// This implementation requires the `miette` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// use firestore_path::DocumentName;
+/// use miette::Diagnostic;
+/// use std::str::FromStr;
+///
+/// let input = "projects/my-project/databases/my-database/documents/chatrooms/..";
+/// let error = DocumentName::from_str(input).unwrap_err();
+/// assert_eq!(
+///     error.code().map(|code| code.to_string()),
+///     Some("firestore_path::single_period_or_double_periods".to_string())
+/// );
+/// let labels = error.labels().unwrap().collect::<Vec<_>>();
+/// assert_eq!(labels[0].offset(), 62);
+/// assert_eq!(labels[0].len(), 2);
+/// assert_eq!(labels[0].label(), Some("single period or double periods"));
+///
+/// // Attach the input to render the labeled span.
+/// let report = miette::Report::new(error).with_source_code(input);
+/// # let _ = report;
+/// ```
+impl Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new(format!(
+            "firestore_path::{}",
+            self.kind().reason().to_ascii_lowercase()
+        )))
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let byte_range = self.byte_range()?;
+        Some(Box::new(std::iter::once(LabeledSpan::new_with_span(
+            Some(self.kind().to_string()),
+            byte_range,
+        ))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ::miette::{NarratableReportHandler, Report};
+
+    use super::*;
+    use crate::{CollectionName, DocumentId};
+
+    #[test]
+    fn test_diagnostic() -> anyhow::Result<()> {
+        let input =
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/__messages__";
+        let error = CollectionName::from_str(input).unwrap_err();
+        assert_eq!(
+            error.code().map(|code| code.to_string()),
+            Some("firestore_path::matches_reserved_id_pattern".to_string())
+        );
+        let labels = error
+            .labels()
+            .expect("error to have labels")
+            .collect::<Vec<_>>();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(
+            &input[labels[0].offset()..][..labels[0].len()],
+            "__messages__"
+        );
+
+        let report = Report::new(error).with_source_code(input);
+        let mut rendered = String::new();
+        NarratableReportHandler::new().render_report(&mut rendered, report.as_ref())?;
+        assert!(rendered.contains("__messages__"));
+        assert!(rendered.contains("matches the regular expression `__.*__`"));
+
+        let error = DocumentId::from_str("").unwrap_err();
+        assert_eq!(
+            error.code().map(|code| code.to_string()),
+            Some("firestore_path::length_out_of_bounds".to_string())
+        );
+        assert!(error.labels().is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,542 @@
+use std::{borrow::Cow, ops::Range};
+
+use crate::Error;
+
+/// Options of the `from_str_with` constructors of the ids, paths and names.
+///
+/// `ParseOptions::strict()` (the default) checks everything that `FromStr` checks.
+/// Each check can be turned off for the environments that need less strictness (e.g. the emulator or the rules tests).
+/// `ParseOptions::lenient()` turns off all the checks that can be turned off.
+///
+/// The syntax (e.g. the `/` separators and the `projects`, `databases` and `documents` segments) is always checked.
+/// With `normalize_slashes`, the stray slashes of the paths and the names are removed before the check.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{DocumentId,ParseOptions};
+/// use std::str::FromStr;
+///
+/// let options = ParseOptions::strict().length_limits(false);
+/// assert!(DocumentId::from_str(&"x".repeat(1_501)).is_err());
+/// assert!(DocumentId::from_str_with(&"x".repeat(1_501), &options).is_ok());
+/// assert!(DocumentId::from_str_with("__x__", &options).is_err());
+///
+/// let options = ParseOptions::lenient();
+/// assert!(DocumentId::from_str_with("__x__", &options).is_ok());
+/// assert!(DocumentId::from_str_with("x/y", &options).is_err());
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ParseOptions {
+    pub(crate) length_limits: bool,
+    pub(crate) normalize_slashes: bool,
+    pub(crate) relaxed_project_ids: bool,
+    pub(crate) reserved_ids: bool,
+    pub(crate) wildcard_project: bool,
+}
+
+impl ParseOptions {
+    /// Returns the options that turn off all the checks that can be turned off, normalize the slashes and accept the wildcard project.
+    ///
+    /// This is suitable for the project ids of the emulator (e.g. `demo-my-project`, `my_project` or `ssl-test`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::ParseOptions;
+    ///
+    /// assert_eq!(
+    ///     ParseOptions::lenient(),
+    ///     ParseOptions::strict()
+    ///         .length_limits(false)
+    ///         .normalize_slashes(true)
+    ///         .relaxed_project_ids(true)
+    ///         .reserved_ids(false)
+    ///         .wildcard_project(true)
+    /// );
+    /// ```
+    pub const fn lenient() -> Self {
+        Self {
+            length_limits: false,
+            normalize_slashes: true,
+            relaxed_project_ids: true,
+            reserved_ids: false,
+            wildcard_project: true,
+        }
+    }
+
+    /// Returns the options that check everything that `FromStr` checks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::ParseOptions;
+    ///
+    /// assert_eq!(ParseOptions::strict(), ParseOptions::default());
+    /// ```
+    pub const fn strict() -> Self {
+        Self {
+            length_limits: true,
+            normalize_slashes: false,
+            relaxed_project_ids: false,
+            reserved_ids: true,
+            wildcard_project: false,
+        }
+    }
+
+    /// Sets whether to check the length limits.
+    ///
+    /// The length limits are the lengths of the ids (an id is never empty), the length of the names and the depth of the paths and names (see the `limits` module).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{ParseOptions,ProjectId};
+    ///
+    /// let options = ParseOptions::strict().length_limits(false);
+    /// assert!(ProjectId::from_str_with("my-p", &options).is_ok());
+    /// assert!(ProjectId::from_str_with("", &options).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub const fn length_limits(self, length_limits: bool) -> Self {
+        Self {
+            length_limits,
+            ..self
+        }
+    }
+
+    /// Removes the stray slashes of `s` if `normalize_slashes` is set.
+    ///
+    /// The stray slashes are the leading slashes, the trailing slashes and the repeated separators (e.g. `/chatrooms//chatroom1/`).
+    /// Returns the normalized string and the fixes made to `s` in order.
+    /// The paths and the names are parsed from the normalized string, but the locations of their errors are mapped back to `s`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::{ParseOptions,SlashFix};
+    ///
+    /// let (s, fixes) = ParseOptions::lenient().normalize("/chatrooms//chatroom1/");
+    /// assert_eq!(s, "chatrooms/chatroom1");
+    /// assert_eq!(
+    ///     fixes,
+    ///     [
+    ///         SlashFix::LeadingSlashes(0..1),
+    ///         SlashFix::RepeatedSlashes(11..12),
+    ///         SlashFix::TrailingSlashes(21..22),
+    ///     ]
+    /// );
+    ///
+    /// let (s, fixes) = ParseOptions::strict().normalize("/chatrooms//chatroom1/");
+    /// assert_eq!(s, "/chatrooms//chatroom1/");
+    /// assert!(fixes.is_empty());
+    /// ```
+    pub fn normalize<'a>(&self, s: &'a str) -> (Cow<'a, str>, Vec<SlashFix>) {
+        if !self.normalize_slashes {
+            return (Cow::Borrowed(s), vec![]);
+        }
+
+        let start = s.len() - s.trim_start_matches('/').len();
+        let end = start.max(s.trim_end_matches('/').len());
+        let mut fixes = vec![];
+        if start > 0 {
+            fixes.push(SlashFix::LeadingSlashes(0..start));
+        }
+        let mut segments = vec![];
+        let mut offset = start;
+        for segment in s[start..end].split('/').filter(|_| start < end) {
+            if segment.is_empty() {
+                match fixes.last_mut() {
+                    Some(SlashFix::RepeatedSlashes(range)) if range.end == offset => range.end += 1,
+                    _ => fixes.push(SlashFix::RepeatedSlashes(offset..offset + 1)),
+                }
+            } else {
+                segments.push(segment);
+            }
+            offset += segment.len() + 1;
+        }
+        if end < s.len() {
+            fixes.push(SlashFix::TrailingSlashes(end..s.len()));
+        }
+
+        if fixes.is_empty() {
+            (Cow::Borrowed(s), fixes)
+        } else {
+            (Cow::Owned(segments.join("/")), fixes)
+        }
+    }
+
+    /// Sets whether to remove the stray slashes of the paths and the names before parsing them.
+    ///
+    /// See `ParseOptions::normalize` for the stray slashes. The ids are never normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentPath,ParseOptions};
+    /// use std::str::FromStr;
+    ///
+    /// let options = ParseOptions::strict().normalize_slashes(true);
+    /// assert_eq!(
+    ///     DocumentPath::from_str_with("/chatrooms//chatroom1/", &options)?,
+    ///     DocumentPath::from_str("chatrooms/chatroom1")?
+    /// );
+    /// assert!(DocumentPath::from_str("/chatrooms//chatroom1/").is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub const fn normalize_slashes(self, normalize_slashes: bool) -> Self {
+        Self {
+            normalize_slashes,
+            ..self
+        }
+    }
+
+    /// Sets whether to relax the validation of the project ids for the emulator.
+    ///
+    /// The emulator accepts project ids that are not valid in production (e.g. `demo-My_Project`).
+    /// With this option, a project id is only required to be non-empty and not to contain `/`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{ParseOptions,ProjectId};
+    /// use std::str::FromStr;
+    ///
+    /// let options = ParseOptions::strict().relaxed_project_ids(true);
+    /// assert!(ProjectId::from_str("demo-My_Project").is_err());
+    /// assert!(ProjectId::from_str_with("demo-My_Project", &options).is_ok());
+    /// assert!(ProjectId::from_str_with("null-ssl", &options).is_ok());
+    /// assert!(ProjectId::from_str_with("demo/project", &options).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub const fn relaxed_project_ids(self, relaxed_project_ids: bool) -> Self {
+        Self {
+            relaxed_project_ids,
+            ..self
+        }
+    }
+
+    /// Sets whether to reject the reserved ids.
+    ///
+    /// The reserved ids are the collection ids and the document ids that match `__.*__`, the project ids that contain a restricted string (e.g. `google`) and the UUID-like database ids.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,ParseOptions};
+    ///
+    /// let options = ParseOptions::strict().reserved_ids(false);
+    /// assert!(CollectionId::from_str_with("__x__", &options).is_ok());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub const fn reserved_ids(self, reserved_ids: bool) -> Self {
+        Self {
+            reserved_ids,
+            ..self
+        }
+    }
+
+    /// Sets whether to accept the wildcard project id `_` (see `ProjectId::wildcard`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseName,ParseOptions,ProjectId};
+    /// use std::str::FromStr;
+    ///
+    /// let s = "projects/_/databases/(default)";
+    /// assert!(DatabaseName::from_str(s).is_err());
+    ///
+    /// let options = ParseOptions::strict().wildcard_project(true);
+    /// let database_name = DatabaseName::from_str_with(s, &options)?;
+    /// assert_eq!(database_name.project_id(), &ProjectId::wildcard());
+    /// assert_eq!(database_name.to_string(), s);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub const fn wildcard_project(self, wildcard_project: bool) -> Self {
+        Self {
+            wildcard_project,
+            ..self
+        }
+    }
+
+    /// Parses the normalized `s` with `parse` and maps the location of the error back to `s`.
+    pub(crate) fn parse_normalized<T, F>(&self, s: &str, parse: F) -> Result<T, Error>
+    where
+        F: FnOnce(&str) -> Result<T, Error>,
+    {
+        let (normalized, fixes) = self.normalize(s);
+        parse(&normalized).map_err(|e| {
+            if fixes.is_empty() {
+                return e;
+            }
+            e.relocate(|start| {
+                // The fixes are in order, so each removed range before `start` shifts it.
+                let start = fixes.iter().fold(start, |start, fix| {
+                    let range = fix.range();
+                    if range.start <= start {
+                        start + range.len()
+                    } else {
+                        start
+                    }
+                });
+                (s[..start].matches('/').count(), start)
+            })
+        })
+    }
+}
+
+/// A fix made by `ParseOptions::normalize`.
+///
+/// Each fix has the byte range of the removed slashes in the original string.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SlashFix {
+    /// The leading slashes are removed.
+    LeadingSlashes(Range<usize>),
+    /// The repeated separators are removed (one slash is kept).
+    RepeatedSlashes(Range<usize>),
+    /// The trailing slashes are removed.
+    TrailingSlashes(Range<usize>),
+}
+
+impl SlashFix {
+    fn range(&self) -> &Range<usize> {
+        match self {
+            SlashFix::LeadingSlashes(range)
+            | SlashFix::RepeatedSlashes(range)
+            | SlashFix::TrailingSlashes(range) => range,
+        }
+    }
+}
+
+impl std::default::Default for ParseOptions {
+    /// Returns `ParseOptions::strict()`.
+    fn default() -> Self {
+        Self::strict()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{
+        error::ErrorKind, limits, CollectionId, CollectionName, CollectionPath, DatabaseId,
+        DatabaseName, DocumentId, DocumentName, DocumentPath, ProjectId, RootDocumentName,
+    };
+
+    #[test]
+    fn test_default() {
+        assert_eq!(ParseOptions::default(), ParseOptions::strict());
+        assert_ne!(ParseOptions::lenient(), ParseOptions::strict());
+    }
+
+    #[test]
+    fn test_ids() -> anyhow::Result<()> {
+        let long_id = "x".repeat(limits::MAX_ID_BYTES + 1);
+        let length_off = ParseOptions::strict().length_limits(false);
+        let reserved_off = ParseOptions::strict().reserved_ids(false);
+
+        for s in ["chatrooms", long_id.as_str(), "__x__"] {
+            assert_eq!(
+                CollectionId::from_str_with(s, &ParseOptions::strict()).ok(),
+                CollectionId::from_str(s).ok()
+            );
+            assert_eq!(
+                DocumentId::from_str_with(s, &ParseOptions::strict()).ok(),
+                DocumentId::from_str(s).ok()
+            );
+        }
+        assert!(CollectionId::from_str_with(&long_id, &length_off).is_ok());
+        assert!(CollectionId::from_str_with(&long_id, &reserved_off).is_err());
+        assert!(CollectionId::from_str_with("__x__", &length_off).is_err());
+        assert!(CollectionId::from_str_with("__x__", &reserved_off).is_ok());
+        assert!(DocumentId::from_str_with(&long_id, &length_off).is_ok());
+        assert!(DocumentId::from_str_with("__x__", &reserved_off).is_ok());
+
+        let lenient = ParseOptions::lenient();
+        assert!(ProjectId::from_str_with("my-google-project", &lenient).is_ok());
+        assert!(ProjectId::from_str_with("my-p", &lenient).is_ok());
+        assert!(
+            DatabaseId::from_str_with("abcdef01-2345-6789-abcd-ef0123456789", &lenient).is_ok()
+        );
+        assert!(DatabaseId::from_str_with("db", &lenient).is_ok());
+
+        for s in ["", "x/y"] {
+            assert!(CollectionId::from_str_with(s, &lenient).is_err());
+            assert!(DatabaseId::from_str_with(s, &lenient).is_err());
+            assert!(DocumentId::from_str_with(s, &lenient).is_err());
+            assert!(ProjectId::from_str_with(s, &lenient).is_err());
+        }
+        assert!(DocumentId::from_str_with("..", &lenient).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_paths_and_names() -> anyhow::Result<()> {
+        let lenient = ParseOptions::lenient();
+        let deep = "c/d/".repeat(limits::MAX_DEPTH) + "c/d";
+
+        assert!(CollectionPath::from_str(&deep[..deep.len() - 2]).is_err());
+        assert!(CollectionPath::from_str_with(&deep[..deep.len() - 2], &lenient).is_ok());
+        assert!(DocumentPath::from_str(&deep).is_err());
+        assert!(DocumentPath::from_str_with(&deep, &lenient).is_ok());
+        assert_eq!(
+            DocumentPath::from_str_with("chatrooms", &lenient)
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::NotContainsSlash
+        );
+
+        let s = "projects/my-google-project/databases/(default)";
+        assert!(DatabaseName::from_str(s).is_err());
+        assert_eq!(
+            DatabaseName::from_str_with(s, &lenient)?.to_string(),
+            s.to_string()
+        );
+        let s = "projects/my-google-project/databases/(default)/documents";
+        assert!(RootDocumentName::from_str(s).is_err());
+        assert_eq!(
+            RootDocumentName::from_str_with(s, &lenient)?.to_string(),
+            s.to_string()
+        );
+
+        let root = "projects/my-project/databases/(default)/documents";
+        let s = format!("{}/{}", root, &deep[..deep.len() - 2]);
+        assert!(CollectionName::from_str(&s).is_err());
+        assert_eq!(CollectionName::from_str_with(&s, &lenient)?.to_string(), s);
+        let s = format!("{}/{}", root, deep);
+        assert!(DocumentName::from_str(&s).is_err());
+        assert_eq!(DocumentName::from_str_with(&s, &lenient)?.to_string(), s);
+
+        let s = format!("{}/__x__", root);
+        assert!(CollectionName::from_str(&s).is_err());
+        assert!(CollectionName::from_str_with(&s, &lenient).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_location() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/(default)/documents/chatrooms/x/y";
+        let error = DocumentName::from_str_with(s, &ParseOptions::lenient()).unwrap_err();
+        assert_eq!(error, DocumentName::from_str(s).unwrap_err());
+
+        let s = "projects/my-project/databases/(default)/documents/chatrooms/..";
+        let error = DocumentName::from_str_with(s, &ParseOptions::lenient()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::SinglePeriodOrDoublePeriods);
+        assert_eq!(error.segment_index(), Some(6));
+        assert_eq!(error.segment(), Some(".."));
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize() {
+        for (s, expected, fixes) in [
+            ("chatrooms/chatroom1", "chatrooms/chatroom1", vec![]),
+            (
+                "/chatrooms/chatroom1",
+                "chatrooms/chatroom1",
+                vec![SlashFix::LeadingSlashes(0..1)],
+            ),
+            (
+                "chatrooms/chatroom1//",
+                "chatrooms/chatroom1",
+                vec![SlashFix::TrailingSlashes(19..21)],
+            ),
+            (
+                "chatrooms///chatroom1/messages//message1",
+                "chatrooms/chatroom1/messages/message1",
+                vec![
+                    SlashFix::RepeatedSlashes(10..12),
+                    SlashFix::RepeatedSlashes(31..32),
+                ],
+            ),
+            ("///", "", vec![SlashFix::LeadingSlashes(0..3)]),
+            ("", "", vec![]),
+        ] {
+            let (normalized, actual) = ParseOptions::lenient().normalize(s);
+            assert_eq!(normalized, expected);
+            assert_eq!(actual, fixes);
+            assert_eq!(matches!(normalized, Cow::Borrowed(_)), actual.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_normalize_slashes() -> anyhow::Result<()> {
+        let options = ParseOptions::strict().normalize_slashes(true);
+        let root = "projects/my-project/databases/(default)";
+        assert_eq!(
+            CollectionPath::from_str_with("/chatrooms/", &options)?,
+            CollectionPath::from_str("chatrooms")?
+        );
+        assert_eq!(
+            DatabaseName::from_str_with(&format!("/{}/", root), &options)?,
+            DatabaseName::from_str(root)?
+        );
+        assert_eq!(
+            RootDocumentName::from_str_with(&format!("{}//documents/", root), &options)?,
+            RootDocumentName::from_str(&format!("{}/documents", root))?
+        );
+        assert_eq!(
+            CollectionName::from_str_with(&format!("/{}/documents//chatrooms", root), &options)?
+                .as_str(),
+            format!("{}/documents/chatrooms", root)
+        );
+        assert_eq!(
+            DocumentName::from_str_with(
+                &format!("{}/documents/chatrooms//chatroom1/", root),
+                &options
+            )?
+            .as_str(),
+            format!("{}/documents/chatrooms/chatroom1", root)
+        );
+        assert!(DocumentPath::from_str_with("/chatrooms/", &options).is_err());
+        assert!(
+            DocumentPath::from_str_with("chatrooms//chatroom1", &ParseOptions::strict()).is_err()
+        );
+        assert!(CollectionId::from_str_with("chatrooms/", &options).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_slashes_error_location() -> anyhow::Result<()> {
+        let options = ParseOptions::lenient();
+        for (s, segment_index, byte_range) in [
+            ("chatrooms/chatroom1/messages/..", 3, 29..31),
+            ("//chatrooms//chatroom1/messages/..", 6, 32..34),
+            ("/chatrooms/../messages/message1/", 2, 11..13),
+        ] {
+            let error = DocumentPath::from_str_with(s, &options).unwrap_err();
+            assert_eq!(error.segment(), Some(".."), "{s}");
+            assert_eq!(error.segment_index(), Some(segment_index), "{s}");
+            assert_eq!(error.byte_range(), Some(byte_range.clone()), "{s}");
+            assert_eq!(&s[byte_range], "..", "{s}");
+        }
+
+        let s = "/projects/my-project//databases/(default)/documents/chatrooms//..";
+        let error = DocumentName::from_str_with(s, &options).unwrap_err();
+        let byte_range = error.byte_range().expect("error to have a location");
+        assert_eq!(&s[byte_range], "..");
+        assert_eq!(error.segment_index(), Some(9));
+
+        let s = "//projects/my-project/databases/my_database";
+        let error = DatabaseName::from_str_with(s, &options).unwrap_err();
+        assert_eq!(error.byte_range(), Some(32..43));
+        assert_eq!(error.segment_index(), Some(5));
+        Ok(())
+    }
+}
@@ -0,0 +1,349 @@
+use crate::{
+    error::ErrorKind, CollectionId, CollectionName, CollectionPath, DatabaseId, DatabaseName,
+    DocumentId, DocumentName, Error, ProjectId, RootDocumentName,
+};
+
+/// A builder of names that adds one id at a time.
+///
+/// The type parameter is the name built so far, so only the valid next step is available:
+/// `database` after `project`, `collection` after `database` or `doc`, and `doc` after `collection`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{CollectionName,DocumentName,PathBuilder};
+/// use std::str::FromStr;
+///
+/// let document_name: DocumentName = PathBuilder::project("my-project")?
+///     .database("my-database")?
+///     .collection("chatrooms")?
+///     .doc("chatroom1")?
+///     .build();
+/// assert_eq!(
+///     document_name,
+///     DocumentName::from_str(
+///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+///     )?
+/// );
+///
+/// let collection_name: CollectionName = PathBuilder::project("my-project")?
+///     .database("my-database")?
+///     .collection("chatrooms")?
+///     .doc("chatroom1")?
+///     .collection("messages")?
+///     .build();
+/// assert_eq!(
+///     collection_name,
+///     CollectionName::from_str(
+///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+///     )?
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// ```rust,compile_fail
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::PathBuilder;
+///
+/// // A document id cannot follow a database id.
+/// let builder = PathBuilder::project("my-project")?
+///     .database("my-database")?
+///     .doc("chatroom1")?;
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PathBuilder<S>(S);
+
+impl PathBuilder<ProjectId> {
+    /// Starts building a name in the project `project_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::PathBuilder;
+    ///
+    /// assert!(PathBuilder::project("my-project").is_ok());
+    /// assert!(PathBuilder::project("MY-PROJECT").is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn project<E, T>(project_id: T) -> Result<Self, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<ProjectId, Error = E>,
+    {
+        let project_id = project_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::ProjectIdConversion(e.to_string())))?;
+        Ok(Self(project_id))
+    }
+
+    /// Adds the `database_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseName,PathBuilder};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     PathBuilder::project("my-project")?.database("my-database")?.build(),
+    ///     DatabaseName::from_str("projects/my-project/databases/my-database")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn database<E, T>(self, database_id: T) -> Result<PathBuilder<DatabaseName>, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DatabaseId, Error = E>,
+    {
+        let database_id = database_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::DatabaseIdConversion(e.to_string())))?;
+        Ok(PathBuilder(DatabaseName::new(self.0, database_id)))
+    }
+}
+
+impl PathBuilder<DatabaseName> {
+    /// Returns the built `DatabaseName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseName,PathBuilder};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     PathBuilder::project("my-project")?.database("my-database")?.build(),
+    ///     DatabaseName::from_str("projects/my-project/databases/my-database")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn build(self) -> DatabaseName {
+        self.0
+    }
+
+    /// Adds the `collection_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,PathBuilder};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     PathBuilder::project("my-project")?
+    ///         .database("my-database")?
+    ///         .collection("chatrooms")?
+    ///         .build(),
+    ///     CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms"
+    ///     )?
+    /// );
+    /// assert!(PathBuilder::project("my-project")?
+    ///     .database("my-database")?
+    ///     .collection("chatrooms/chatroom1/messages")
+    ///     .is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn collection<E, T>(self, collection_id: T) -> Result<PathBuilder<CollectionName>, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<CollectionId, Error = E>,
+    {
+        let collection_id = collection_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::CollectionIdConversion(e.to_string())))?;
+        Ok(PathBuilder(CollectionName::try_new(
+            RootDocumentName::from(self.0),
+            CollectionPath::from(collection_id),
+        )?))
+    }
+}
+
+impl PathBuilder<CollectionName> {
+    /// Returns the built `CollectionName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,PathBuilder};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     PathBuilder::project("my-project")?
+    ///         .database("my-database")?
+    ///         .collection("chatrooms")?
+    ///         .build(),
+    ///     CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn build(self) -> CollectionName {
+        self.0
+    }
+
+    /// Adds the `document_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,PathBuilder};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     PathBuilder::project("my-project")?
+    ///         .database("my-database")?
+    ///         .collection("chatrooms")?
+    ///         .doc("chatroom1")?
+    ///         .build(),
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn doc<E, T>(self, document_id: T) -> Result<PathBuilder<DocumentName>, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DocumentId, Error = E>,
+    {
+        Ok(PathBuilder(self.0.into_doc(document_id)?))
+    }
+}
+
+impl PathBuilder<DocumentName> {
+    /// Returns the built `DocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,PathBuilder};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     PathBuilder::project("my-project")?
+    ///         .database("my-database")?
+    ///         .collection("chatrooms")?
+    ///         .doc("chatroom1")?
+    ///         .build(),
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn build(self) -> DocumentName {
+        self.0
+    }
+
+    /// Adds the `collection_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionName,PathBuilder};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     PathBuilder::project("my-project")?
+    ///         .database("my-database")?
+    ///         .collection("chatrooms")?
+    ///         .doc("chatroom1")?
+    ///         .collection("messages")?
+    ///         .build(),
+    ///     CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    ///     )?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn collection<E, T>(self, collection_id: T) -> Result<PathBuilder<CollectionName>, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<CollectionId, Error = E>,
+    {
+        let collection_id = collection_id
+            .try_into()
+            .map_err(|e| Error::from(ErrorKind::CollectionIdConversion(e.to_string())))?;
+        Ok(PathBuilder(
+            self.0
+                .into_collection(CollectionPath::from(collection_id))?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let builder = PathBuilder::project("my-project")?.database("my-database")?;
+        assert_eq!(
+            builder.clone().build(),
+            DatabaseName::from_str("projects/my-project/databases/my-database")?
+        );
+
+        let builder = builder.collection("chatrooms")?;
+        assert_eq!(
+            builder.clone().build(),
+            CollectionName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms"
+            )?
+        );
+
+        let builder = builder.doc("chatroom1")?;
+        assert_eq!(
+            builder.clone().build(),
+            DocumentName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+            )?
+        );
+
+        let builder = builder.collection("messages")?.doc("message1")?;
+        assert_eq!(
+            builder.build(),
+            DocumentName::from_str(
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+            )?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_ids() -> anyhow::Result<()> {
+        assert!(PathBuilder::project("MY-PROJECT").is_err());
+        let builder = PathBuilder::project("my-project")?;
+        assert!(builder.clone().database("MY-DATABASE").is_err());
+        let builder = builder.database("my-database")?;
+        assert!(builder.clone().collection("chatrooms/chatroom1").is_err());
+        let builder = builder.collection("chatrooms")?;
+        assert!(builder.clone().doc("chatroom1/messages").is_err());
+        let builder = builder.doc("chatroom1")?;
+        assert!(builder.collection("__x__").is_err());
+        Ok(())
+    }
+}
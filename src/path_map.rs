@@ -0,0 +1,556 @@
+use std::collections::BTreeMap;
+
+use crate::{AnyName, RootDocumentName};
+
+/// A map from names to values stored in a trie.
+///
+/// In addition to the usual map operations, a `PathMap` answers longest-prefix-match lookups ([`PathMap::get_nearest_ancestor`]) efficiently and iterates its entries in hierarchical order (parents before their children).
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{AnyName,CollectionName,DocumentName,PathMap};
+/// use std::str::FromStr;
+///
+/// let collection_name = AnyName::from(CollectionName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms"
+/// )?);
+/// let mut path_map = PathMap::new();
+/// path_map.insert(collection_name.clone(), "ttl: 30d");
+///
+/// let document_name = AnyName::from(DocumentName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+/// )?);
+/// assert_eq!(path_map.get(&document_name), None);
+/// assert_eq!(
+///     path_map.get_nearest_ancestor(&document_name),
+///     Some((&collection_name, &"ttl: 30d"))
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PathMap<T> {
+    len: usize,
+    roots: BTreeMap<RootDocumentName, Node<T>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Node<T> {
+    children: BTreeMap<String, Node<T>>,
+    entry: Option<(AnyName, T)>,
+}
+
+impl<T> Node<T> {
+    fn new() -> Self {
+        Self {
+            children: BTreeMap::new(),
+            entry: None,
+        }
+    }
+
+    fn clear(&mut self) -> usize {
+        let removed = self
+            .children
+            .values_mut()
+            .map(|child| usize::from(child.entry.is_some()) + child.clear())
+            .sum();
+        self.children.clear();
+        removed
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entry.is_none() && self.children.is_empty()
+    }
+
+    fn remove(&mut self, segments: &[&str]) -> Option<T> {
+        match segments.split_first() {
+            None => self.entry.take().map(|(_, value)| value),
+            Some((segment, rest)) => {
+                let child = self.children.get_mut(*segment)?;
+                let removed = child.remove(rest);
+                if child.is_empty() {
+                    self.children.remove(*segment);
+                }
+                removed
+            }
+        }
+    }
+}
+
+impl<T> PathMap<T> {
+    /// Creates an empty `PathMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::PathMap;
+    ///
+    /// let path_map = PathMap::<i32>::new();
+    /// assert!(path_map.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            len: 0,
+            roots: BTreeMap::new(),
+        }
+    }
+
+    /// Returns `true` if this `PathMap` contains a value for `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,DocumentName,PathMap};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// let mut path_map = PathMap::new();
+    /// assert!(!path_map.contains_key(&document_name));
+    /// path_map.insert(document_name.clone(), 1);
+    /// assert!(path_map.contains_key(&document_name));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn contains_key(&self, name: &AnyName) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Returns a reference to the value for `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,DocumentName,PathMap};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// let mut path_map = PathMap::new();
+    /// path_map.insert(document_name.clone(), 1);
+    /// assert_eq!(path_map.get(&document_name), Some(&1));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn get(&self, name: &AnyName) -> Option<&T> {
+        self.node(name)?.entry.as_ref().map(|(_, value)| value)
+    }
+
+    /// Returns a mutable reference to the value for `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,DocumentName,PathMap};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// let mut path_map = PathMap::new();
+    /// path_map.insert(document_name.clone(), 1);
+    /// if let Some(value) = path_map.get_mut(&document_name) {
+    ///     *value += 1;
+    /// }
+    /// assert_eq!(path_map.get(&document_name), Some(&2));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn get_mut(&mut self, name: &AnyName) -> Option<&mut T> {
+        let mut node = self.roots.get_mut(name.root_document_name())?;
+        for segment in name.segments() {
+            node = node.children.get_mut(segment.as_ref())?;
+        }
+        node.entry.as_mut().map(|(_, value)| value)
+    }
+
+    /// Returns the entry for `name` itself or its nearest ancestor (the longest prefix match).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionName,DocumentName,PathMap,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name = AnyName::from(RootDocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents"
+    /// )?);
+    /// let document_name = AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// let mut path_map = PathMap::new();
+    /// path_map.insert(root_document_name.clone(), "default");
+    /// path_map.insert(document_name.clone(), "chatroom1");
+    ///
+    /// assert_eq!(
+    ///     path_map.get_nearest_ancestor(&AnyName::from(DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    ///     )?)),
+    ///     Some((&document_name, &"chatroom1"))
+    /// );
+    /// assert_eq!(
+    ///     path_map.get_nearest_ancestor(&document_name),
+    ///     Some((&document_name, &"chatroom1"))
+    /// );
+    /// assert_eq!(
+    ///     path_map.get_nearest_ancestor(&AnyName::from(CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms"
+    ///     )?)),
+    ///     Some((&root_document_name, &"default"))
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn get_nearest_ancestor(&self, name: &AnyName) -> Option<(&AnyName, &T)> {
+        let mut node = self.roots.get(name.root_document_name())?;
+        let mut nearest = node.entry.as_ref();
+        for segment in name.segments() {
+            match node.children.get(segment.as_ref()) {
+                Some(child) => node = child,
+                None => break,
+            }
+            nearest = node.entry.as_ref().or(nearest);
+        }
+        nearest.map(|(name, value)| (name, value))
+    }
+
+    /// Inserts `value` for `name` into this `PathMap`.
+    ///
+    /// Returns the old value if this `PathMap` already contains a value for `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,PathMap};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// let mut path_map = PathMap::new();
+    /// assert_eq!(path_map.insert(document_name.clone(), 1), None);
+    /// assert_eq!(path_map.insert(document_name, 2), Some(1));
+    /// assert_eq!(path_map.len(), 1);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn insert<N>(&mut self, name: N, value: T) -> Option<T>
+    where
+        N: Into<AnyName>,
+    {
+        let name = name.into();
+        let old = self
+            .node_mut(&name)
+            .entry
+            .replace((name, value))
+            .map(|(_, value)| value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    /// Returns `true` if this `PathMap` contains no entries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,PathMap};
+    /// use std::str::FromStr;
+    ///
+    /// let mut path_map = PathMap::new();
+    /// assert!(path_map.is_empty());
+    /// path_map.insert(
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?,
+    ///     1,
+    /// );
+    /// assert!(!path_map.is_empty());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the entries of this `PathMap` in hierarchical order (parents before their children).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionName,DocumentName,PathMap};
+    /// use std::str::FromStr;
+    ///
+    /// let mut path_map = PathMap::new();
+    /// path_map.insert(
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?,
+    ///     2,
+    /// );
+    /// path_map.insert(
+    ///     CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms"
+    ///     )?,
+    ///     1,
+    /// );
+    /// assert_eq!(
+    ///     path_map.iter().map(|(_, value)| *value).collect::<Vec<i32>>(),
+    ///     vec![1, 2]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&AnyName, &T)> {
+        let mut stack = self.roots.values().rev().collect::<Vec<&Node<T>>>();
+        std::iter::from_fn(move || {
+            while let Some(node) = stack.pop() {
+                stack.extend(node.children.values().rev());
+                if let Some((name, value)) = node.entry.as_ref() {
+                    return Some((name, value));
+                }
+            }
+            None
+        })
+    }
+
+    /// Returns the number of entries in this `PathMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,PathMap};
+    /// use std::str::FromStr;
+    ///
+    /// let mut path_map = PathMap::new();
+    /// path_map.insert(
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?,
+    ///     1,
+    /// );
+    /// assert_eq!(path_map.len(), 1);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Removes the value for `name` from this `PathMap`, returning it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,DocumentName,PathMap};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// let mut path_map = PathMap::new();
+    /// path_map.insert(document_name.clone(), 1);
+    /// assert_eq!(path_map.remove(&document_name), Some(1));
+    /// assert_eq!(path_map.remove(&document_name), None);
+    /// assert!(path_map.is_empty());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn remove(&mut self, name: &AnyName) -> Option<T> {
+        let root_document_name = name.root_document_name();
+        let root = self.roots.get_mut(root_document_name)?;
+        let segments = name.segments();
+        let segments = segments
+            .iter()
+            .map(|segment| segment.as_ref())
+            .collect::<Vec<&str>>();
+        let removed = root.remove(&segments);
+        if root.is_empty() {
+            self.roots.remove(root_document_name);
+        }
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    pub(crate) fn contains_ancestor_of(&self, name: &AnyName) -> bool {
+        let Some(mut node) = self.roots.get(name.root_document_name()) else {
+            return false;
+        };
+        for segment in name.segments() {
+            if node.entry.is_some() {
+                return true;
+            }
+            match node.children.get(segment.as_ref()) {
+                Some(child) => node = child,
+                None => return false,
+            }
+        }
+        false
+    }
+
+    pub(crate) fn insert_pruning_descendants(&mut self, name: AnyName, value: T) {
+        let node = self.node_mut(&name);
+        let removed = node.clear();
+        let old = node.entry.replace((name, value));
+        self.len = self.len + usize::from(old.is_none()) - removed;
+    }
+
+    fn node(&self, name: &AnyName) -> Option<&Node<T>> {
+        let mut node = self.roots.get(name.root_document_name())?;
+        for segment in name.segments() {
+            node = node.children.get(segment.as_ref())?;
+        }
+        Some(node)
+    }
+
+    fn node_mut(&mut self, name: &AnyName) -> &mut Node<T> {
+        let mut node = self
+            .roots
+            .entry(name.root_document_name().clone())
+            .or_insert_with(Node::new);
+        for segment in name.segments() {
+            node = node
+                .children
+                .entry(segment.as_ref().to_string())
+                .or_insert_with(Node::new);
+        }
+        node
+    }
+}
+
+impl<T> std::default::Default for PathMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, T> std::iter::Extend<(N, T)> for PathMap<T>
+where
+    N: Into<AnyName>,
+{
+    fn extend<I: IntoIterator<Item = (N, T)>>(&mut self, iter: I) {
+        for (name, value) in iter {
+            self.insert(name, value);
+        }
+    }
+}
+
+impl<N, T> std::iter::FromIterator<(N, T)> for PathMap<T>
+where
+    N: Into<AnyName>,
+{
+    fn from_iter<I: IntoIterator<Item = (N, T)>>(iter: I) -> Self {
+        let mut path_map = PathMap::new();
+        path_map.extend(iter);
+        path_map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{CollectionName, DocumentName};
+
+    use super::*;
+
+    fn any_name(s: &str) -> anyhow::Result<AnyName> {
+        let s = format!("projects/my-project/databases/my-database/documents{}", s);
+        Ok(match s.split('/').count() {
+            5 => AnyName::from(RootDocumentName::from_str(&s)?),
+            n if n % 2 == 0 => AnyName::from(CollectionName::from_str(&s)?),
+            _ => AnyName::from(DocumentName::from_str(&s)?),
+        })
+    }
+
+    #[test]
+    fn test_get_nearest_ancestor() -> anyhow::Result<()> {
+        let path_map = [("/c1", 1), ("/c1/d1/c2", 2), ("/c3/d3", 3)]
+            .into_iter()
+            .map(|(s, value)| Ok((any_name(s)?, value)))
+            .collect::<anyhow::Result<PathMap<i32>>>()?;
+        for (s, expected) in [
+            ("", None),
+            ("/c1", Some(("/c1", 1))),
+            ("/c1/d1", Some(("/c1", 1))),
+            ("/c1/d1/c2", Some(("/c1/d1/c2", 2))),
+            ("/c1/d1/c2/d2/c/d", Some(("/c1/d1/c2", 2))),
+            ("/c1/d2/c2", Some(("/c1", 1))),
+            ("/c3", None),
+            ("/c3/d3/c", Some(("/c3/d3", 3))),
+            ("/c4/d", None),
+        ] {
+            let expected = match expected {
+                Some((s, value)) => Some((any_name(s)?, value)),
+                None => None,
+            };
+            assert_eq!(
+                path_map
+                    .get_nearest_ancestor(&any_name(s)?)
+                    .map(|(name, value)| (name.clone(), *value)),
+                expected,
+                "{s}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_get_and_remove() -> anyhow::Result<()> {
+        let mut path_map = PathMap::new();
+        assert_eq!(path_map.insert(any_name("/c/d")?, 1), None);
+        assert_eq!(path_map.insert(any_name("/c/d/c/d")?, 2), None);
+        assert_eq!(path_map.insert(any_name("/c/d")?, 3), Some(1));
+        assert_eq!(path_map.len(), 2);
+        assert_eq!(path_map.get(&any_name("/c/d")?), Some(&3));
+        assert_eq!(path_map.get(&any_name("/c")?), None);
+
+        assert_eq!(path_map.remove(&any_name("/c")?), None);
+        assert_eq!(path_map.remove(&any_name("/c/d/c/d")?), Some(2));
+        assert_eq!(path_map.remove(&any_name("/c/d/c/d")?), None);
+        assert_eq!(path_map.remove(&any_name("/c/d")?), Some(3));
+        assert!(path_map.is_empty());
+        assert_eq!(path_map, PathMap::new());
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter() -> anyhow::Result<()> {
+        let expected = vec!["", "/a", "/a/b", "/a/b/c", "/a/b2", "/a-", "/a2/b"]
+            .into_iter()
+            .map(any_name)
+            .collect::<anyhow::Result<Vec<AnyName>>>()?;
+        let path_map = expected
+            .iter()
+            .rev()
+            .cloned()
+            .enumerate()
+            .map(|(index, name)| (name, index))
+            .collect::<PathMap<usize>>();
+        assert_eq!(
+            path_map
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<AnyName>>(),
+            expected
+        );
+        Ok(())
+    }
+}
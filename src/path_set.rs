@@ -0,0 +1,402 @@
+use crate::{AnyName, PathMap};
+
+/// A set of names stored in a trie.
+///
+/// In addition to the usual set operations, a `PathSet` answers ancestor queries efficiently and iterates its names in hierarchical order (parents before their children).
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{AnyName,CollectionName,DocumentName,PathSet};
+/// use std::str::FromStr;
+///
+/// let mut path_set = PathSet::new();
+/// path_set.insert(CollectionName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms"
+/// )?);
+///
+/// let document_name = AnyName::from(DocumentName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+/// )?);
+/// assert!(!path_set.contains(&document_name));
+/// assert!(path_set.contains_ancestor_of(&document_name));
+/// #     Ok(())
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PathSet {
+    path_map: PathMap<()>,
+}
+
+impl PathSet {
+    /// Creates an empty `PathSet`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::PathSet;
+    ///
+    /// let path_set = PathSet::new();
+    /// assert!(path_set.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if this `PathSet` contains `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,DocumentName,PathSet};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// let mut path_set = PathSet::new();
+    /// assert!(!path_set.contains(&document_name));
+    /// path_set.insert(document_name.clone());
+    /// assert!(path_set.contains(&document_name));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn contains(&self, name: &AnyName) -> bool {
+        self.path_map.contains_key(name)
+    }
+
+    /// Returns `true` if this `PathSet` contains a (proper) ancestor of `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,DocumentName,PathSet};
+    /// use std::str::FromStr;
+    ///
+    /// let mut path_set = PathSet::new();
+    /// path_set.insert(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// assert!(path_set.contains_ancestor_of(&AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    /// )?)));
+    /// assert!(!path_set.contains_ancestor_of(&AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?)));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn contains_ancestor_of(&self, name: &AnyName) -> bool {
+        self.path_map.contains_ancestor_of(name)
+    }
+
+    /// Adds `name` to this `PathSet`.
+    ///
+    /// Returns `false` if this `PathSet` already contains `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,PathSet};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// let mut path_set = PathSet::new();
+    /// assert!(path_set.insert(document_name.clone()));
+    /// assert!(!path_set.insert(document_name));
+    /// assert_eq!(path_set.len(), 1);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn insert<N>(&mut self, name: N) -> bool
+    where
+        N: Into<AnyName>,
+    {
+        self.path_map.insert(name, ()).is_none()
+    }
+
+    /// Adds `name` to this `PathSet` and removes all of its descendants.
+    ///
+    /// Returns `false` and does nothing if this `PathSet` already contains `name` or any of its ancestors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionName,DocumentName,PathSet};
+    /// use std::str::FromStr;
+    ///
+    /// let mut path_set = PathSet::new();
+    /// path_set.insert(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// path_set.insert(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom2"
+    /// )?);
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert!(path_set.insert_pruning_descendants(collection_name.clone()));
+    /// assert_eq!(
+    ///     path_set.iter().collect::<Vec<&AnyName>>(),
+    ///     vec![&AnyName::from(collection_name)]
+    /// );
+    ///
+    /// assert!(!path_set.insert_pruning_descendants(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom3"
+    /// )?));
+    /// assert_eq!(path_set.len(), 1);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn insert_pruning_descendants<N>(&mut self, name: N) -> bool
+    where
+        N: Into<AnyName>,
+    {
+        let name = name.into();
+        if self.contains(&name) || self.contains_ancestor_of(&name) {
+            return false;
+        }
+        self.path_map.insert_pruning_descendants(name, ());
+        true
+    }
+
+    /// Returns `true` if this `PathSet` contains no names.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,PathSet};
+    /// use std::str::FromStr;
+    ///
+    /// let mut path_set = PathSet::new();
+    /// assert!(path_set.is_empty());
+    /// path_set.insert(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// assert!(!path_set.is_empty());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.path_map.is_empty()
+    }
+
+    /// Returns an iterator over the names of this `PathSet` in hierarchical order (parents before their children).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionName,DocumentName,PathSet};
+    /// use std::str::FromStr;
+    ///
+    /// let mut path_set = PathSet::new();
+    /// path_set.insert(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    /// )?);
+    /// path_set.insert(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// path_set.insert(CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?);
+    /// assert_eq!(
+    ///     path_set.iter().map(AnyName::to_string).collect::<Vec<String>>(),
+    ///     vec![
+    ///         "projects/my-project/databases/my-database/documents/chatrooms",
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &AnyName> {
+        self.path_map.iter().map(|(name, _)| name)
+    }
+
+    /// Returns the number of names in this `PathSet`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,PathSet};
+    /// use std::str::FromStr;
+    ///
+    /// let mut path_set = PathSet::new();
+    /// path_set.insert(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// assert_eq!(path_set.len(), 1);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn len(&self) -> usize {
+        self.path_map.len()
+    }
+
+    /// Removes `name` from this `PathSet`.
+    ///
+    /// Returns `false` if this `PathSet` does not contain `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,DocumentName,PathSet};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = AnyName::from(DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?);
+    /// let mut path_set = PathSet::new();
+    /// path_set.insert(document_name.clone());
+    /// assert!(path_set.remove(&document_name));
+    /// assert!(!path_set.remove(&document_name));
+    /// assert!(path_set.is_empty());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn remove(&mut self, name: &AnyName) -> bool {
+        self.path_map.remove(name).is_some()
+    }
+}
+
+impl<N> std::iter::Extend<N> for PathSet
+where
+    N: Into<AnyName>,
+{
+    fn extend<I: IntoIterator<Item = N>>(&mut self, iter: I) {
+        for name in iter {
+            self.insert(name);
+        }
+    }
+}
+
+impl<N> std::iter::FromIterator<N> for PathSet
+where
+    N: Into<AnyName>,
+{
+    fn from_iter<I: IntoIterator<Item = N>>(iter: I) -> Self {
+        let mut path_set = PathSet::new();
+        path_set.extend(iter);
+        path_set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{CollectionName, DocumentName, RootDocumentName};
+
+    use super::*;
+
+    fn any_name(s: &str) -> anyhow::Result<AnyName> {
+        let s = format!("projects/my-project/databases/my-database/documents{}", s);
+        Ok(match s.split('/').count() {
+            5 => AnyName::from(RootDocumentName::from_str(&s)?),
+            n if n % 2 == 0 => AnyName::from(CollectionName::from_str(&s)?),
+            _ => AnyName::from(DocumentName::from_str(&s)?),
+        })
+    }
+
+    #[test]
+    fn test_contains_and_contains_ancestor_of() -> anyhow::Result<()> {
+        let path_set = ["/c1/d1", "/c2"]
+            .into_iter()
+            .map(any_name)
+            .collect::<anyhow::Result<PathSet>>()?;
+        for (s, contains, contains_ancestor_of) in [
+            ("", false, false),
+            ("/c1", false, false),
+            ("/c1/d1", true, false),
+            ("/c1/d1/c", false, true),
+            ("/c1/d1/c/d", false, true),
+            ("/c1/d2", false, false),
+            ("/c2", true, false),
+            ("/c2/d", false, true),
+            ("/c3", false, false),
+        ] {
+            let name = any_name(s)?;
+            assert_eq!(path_set.contains(&name), contains, "{s}");
+            assert_eq!(
+                path_set.contains_ancestor_of(&name),
+                contains_ancestor_of,
+                "{s}"
+            );
+        }
+
+        let path_set = PathSet::from_iter([any_name("")?]);
+        assert!(path_set.contains_ancestor_of(&any_name("/c1")?));
+        assert!(
+            !path_set.contains_ancestor_of(&AnyName::from(CollectionName::from_str(
+                "projects/my-project/databases/(default)/documents/c1"
+            )?))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_and_remove() -> anyhow::Result<()> {
+        let mut path_set = PathSet::new();
+        assert!(path_set.insert(any_name("/c/d")?));
+        assert!(path_set.insert(any_name("/c/d/c/d")?));
+        assert!(!path_set.insert(any_name("/c/d")?));
+        assert_eq!(path_set.len(), 2);
+
+        assert!(!path_set.remove(&any_name("/c")?));
+        assert!(path_set.remove(&any_name("/c/d/c/d")?));
+        assert!(!path_set.remove(&any_name("/c/d/c/d")?));
+        assert_eq!(path_set.len(), 1);
+        assert!(path_set.remove(&any_name("/c/d")?));
+        assert!(path_set.is_empty());
+        assert_eq!(path_set, PathSet::new());
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_pruning_descendants() -> anyhow::Result<()> {
+        let mut path_set = ["/c1/d1", "/c1/d2/c/d", "/c2/d"]
+            .into_iter()
+            .map(any_name)
+            .collect::<anyhow::Result<PathSet>>()?;
+        assert_eq!(path_set.len(), 3);
+
+        assert!(path_set.insert_pruning_descendants(any_name("/c1")?));
+        assert!(!path_set.insert_pruning_descendants(any_name("/c1")?));
+        assert!(!path_set.insert_pruning_descendants(any_name("/c1/d3")?));
+        assert_eq!(path_set.len(), 2);
+        assert_eq!(
+            path_set.iter().cloned().collect::<Vec<AnyName>>(),
+            vec![any_name("/c1")?, any_name("/c2/d")?]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter() -> anyhow::Result<()> {
+        let expected = vec!["", "/a", "/a/b", "/a/b/c", "/a/b2", "/a-", "/a2/b"]
+            .into_iter()
+            .map(any_name)
+            .collect::<anyhow::Result<Vec<AnyName>>>()?;
+        let path_set = expected.iter().rev().cloned().collect::<PathSet>();
+        assert_eq!(path_set.len(), expected.len());
+        assert_eq!(path_set.iter().cloned().collect::<Vec<AnyName>>(), expected);
+
+        let mut sorted = expected.clone();
+        sorted.sort();
+        assert_eq!(sorted, expected);
+        Ok(())
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{error::ErrorKind, Error};
+use crate::{
+    error::ErrorKind,
+    id_string::{self, IdString},
+    limits, Error, ParseOptions,
+};
 
 /// A project id.
 ///
@@ -13,6 +17,14 @@ use crate::{error::ErrorKind, Error};
 /// > - Cannot be in use or previously used; this includes deleted projects.
 /// > - Cannot contain restricted strings, such as google, null, undefined, and ssl.
 ///
+/// A project number (e.g. `projects/123456789012/databases/(default)` returned by some APIs) is also a valid `ProjectId`.
+/// It consists of 1 to 30 digits. Use `ProjectId::is_number` to distinguish it from a project id.
+///
+/// The wildcard project id `_` (e.g. `projects/_/databases/(default)/documents/...` in the Security Rules) is not a valid `ProjectId` by `FromStr`.
+/// It is created by `ProjectId::wildcard` or parsed with `ParseOptions::wildcard_project`.
+///
+/// The emulator accepts more project ids than the limit above. Use `ParseOptions::relaxed_project_ids` to parse them.
+///
 /// # Examples
 ///
 /// ```rust
@@ -28,58 +40,324 @@ use crate::{error::ErrorKind, Error};
 /// ```
 ///
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct ProjectId(String);
+pub struct ProjectId(IdString);
 
-impl std::convert::AsRef<str> for ProjectId {
-    fn as_ref(&self) -> &str {
-        self.0.as_ref()
+impl ProjectId {
+    /// Creates a new `ProjectId` from a `'static` string.
+    ///
+    /// This is a `const fn`, so well-known ids can be defined as `const`s or `static`s without parsing at runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not a valid `ProjectId`. In a const context, this is a compile-time error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::ProjectId;
+    /// use std::str::FromStr;
+    ///
+    /// static ID: ProjectId = ProjectId::from_static("my-project");
+    /// assert_eq!(ID.as_ref(), "my-project");
+    /// assert_eq!(ID, ProjectId::from_str("my-project").unwrap());
+    /// ```
+    ///
+    /// ```rust,compile_fail
+    /// use firestore_path::ProjectId;
+    ///
+    /// static ID: ProjectId = ProjectId::from_static("My-Project");
+    /// ```
+    pub const fn from_static(s: &'static str) -> Self {
+        let result = validate(s);
+        let is_valid = result.is_ok();
+        // `ErrorKind` has a destructor, which cannot be run in a const context.
+        std::mem::forget(result);
+        assert!(is_valid, "invalid ProjectId");
+        Self(IdString::Static(s))
     }
-}
 
-impl std::convert::TryFrom<&str> for ProjectId {
-    type Error = Error;
+    /// Creates a new `ProjectId` from the environment variables.
+    ///
+    /// The first non-empty variable of `GOOGLE_CLOUD_PROJECT`, `GCLOUD_PROJECT`, `GCP_PROJECT` and `CLOUDSDK_CORE_PROJECT` is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if none of the variables is set or the value is not a valid `ProjectId`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseId,DatabaseName,ProjectId};
+    ///
+    /// let database_name = DatabaseName::new(ProjectId::from_env()?, DatabaseId::default());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_env() -> Result<Self, Error> {
+        Self::from_env_with(|key| std::env::var(key).ok())
+    }
 
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
-        Self::try_from(s.to_string())
+    /// Creates a new `ProjectId` from `s` with `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid `ProjectId` under `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{ParseOptions,ProjectId};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     ProjectId::from_str_with("my-project", &ParseOptions::strict())?,
+    ///     ProjectId::from_str("my-project")?
+    /// );
+    /// assert!(ProjectId::from_str("my-google-project").is_err());
+    /// assert!(ProjectId::from_str_with("my-google-project", &ParseOptions::lenient()).is_ok());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        validate_with(s, options)?;
+        Ok(Self(IdString::from(s.to_string())))
+    }
+
+    /// Returns `true` if this is a demo project id (starts with `demo-`).
+    ///
+    /// The emulator treats a demo project as a project that has no real resources.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::ProjectId;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(ProjectId::from_str("demo-my-project")?.is_demo());
+    /// assert!(!ProjectId::from_str("my-project")?.is_demo());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_demo(&self) -> bool {
+        self.0.as_str().starts_with("demo-")
+    }
+
+    /// Returns `true` if this is a project number (consists of digits only).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseName,ProjectId};
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/123456789012/databases/(default)")?;
+    /// assert!(database_name.project_id().is_number());
+    /// assert!(!ProjectId::from_str("my-project")?.is_number());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_number(&self) -> bool {
+        is_number(self.0.as_str())
+    }
+
+    /// Returns `true` if `s` is a valid `ProjectId`.
+    ///
+    /// This is the same as `ProjectId::validate(s).is_ok()` and does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::ProjectId;
+    ///
+    /// assert!(ProjectId::is_valid("my-project"));
+    /// assert!(!ProjectId::is_valid("my-google-project"));
+    /// ```
+    pub fn is_valid(s: &str) -> bool {
+        validate(s).is_ok()
+    }
+
+    /// Returns `true` if this is the wildcard project id `_`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::ProjectId;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(ProjectId::wildcard().is_wildcard());
+    /// assert!(!ProjectId::from_str("my-project")?.is_wildcard());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_wildcard(&self) -> bool {
+        self.0.as_str() == WILDCARD
+    }
+
+    /// Checks that `s` is a valid `ProjectId` without creating a `ProjectId`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that `ProjectId::from_str(s)` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::ProjectId;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(ProjectId::validate("my-project").is_ok());
+    /// assert_eq!(
+    ///     ProjectId::validate("my-google-project").unwrap_err(),
+    ///     ProjectId::from_str("my-google-project").unwrap_err()
+    /// );
+    /// ```
+    pub fn validate(s: &str) -> Result<(), Error> {
+        Ok(validate(s)?)
+    }
+
+    /// Returns the wildcard project id `_`.
+    ///
+    /// The wildcard stands for the current project in the Security Rules and some emulator contexts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseId,DatabaseName,ProjectId};
+    ///
+    /// let database_name = DatabaseName::new(ProjectId::wildcard(), DatabaseId::default());
+    /// assert_eq!(database_name.to_string(), "projects/_/databases/(default)");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub const fn wildcard() -> Self {
+        Self(IdString::Static(WILDCARD))
+    }
+
+    fn from_env_with<F>(var: F) -> Result<Self, Error>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let s = [
+            "GOOGLE_CLOUD_PROJECT",
+            "GCLOUD_PROJECT",
+            "GCP_PROJECT",
+            "CLOUDSDK_CORE_PROJECT",
+        ]
+        .into_iter()
+        .find_map(|key| var(key).filter(|value| !value.is_empty()))
+        .ok_or_else(|| Error::from(ErrorKind::ProjectIdNotFound))?;
+        Self::try_from(s)
     }
 }
 
-impl std::convert::TryFrom<String> for ProjectId {
-    type Error = Error;
+const WILDCARD: &str = "_";
 
-    fn try_from(s: String) -> Result<Self, Self::Error> {
-        // <https://cloud.google.com/resource-manager/docs/creating-managing-projects>
+pub(crate) const fn validate(s: &str) -> Result<(), ErrorKind> {
+    validate_with(s, &ParseOptions::strict())
+}
 
-        if !(6..=30).contains(&s.len()) {
-            return Err(Error::from(ErrorKind::LengthOutOfBounds));
-        }
+pub(crate) const fn validate_with(s: &str, options: &ParseOptions) -> Result<(), ErrorKind> {
+    // <https://cloud.google.com/resource-manager/docs/creating-managing-projects>
+
+    if options.wildcard_project && id_string::eq(s, WILDCARD) {
+        return Ok(());
+    }
 
-        if !s
-            .chars()
-            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
-        {
-            return Err(Error::from(ErrorKind::ContainsInvalidCharacter));
+    if options.relaxed_project_ids {
+        if s.is_empty() {
+            return Err(ErrorKind::LengthOutOfBounds);
         }
+        if id_string::contains(s, "/") {
+            return Err(ErrorKind::ContainsSlash);
+        }
+        return Ok(());
+    }
 
-        let first_char = s.chars().next().expect("already length checked");
-        if !first_char.is_ascii_lowercase() {
-            return Err(Error::from(ErrorKind::StartsWithNonLetter));
+    if is_number(s) {
+        if options.length_limits && s.len() > limits::MAX_PROJECT_ID_LEN {
+            return Err(ErrorKind::LengthOutOfBounds);
         }
+        return Ok(());
+    }
 
-        let last_char = s.chars().next_back().expect("already length checked");
-        if last_char == '-' {
-            return Err(Error::from(ErrorKind::EndsWithHyphen));
+    if s.is_empty()
+        || (options.length_limits
+            && (s.len() < limits::MIN_PROJECT_ID_LEN || s.len() > limits::MAX_PROJECT_ID_LEN))
+    {
+        return Err(ErrorKind::LengthOutOfBounds);
+    }
+
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if !(b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-') {
+            return Err(ErrorKind::ContainsInvalidCharacter);
         }
+        i += 1;
+    }
+
+    if !bytes[0].is_ascii_lowercase() {
+        return Err(ErrorKind::StartsWithNonLetter);
+    }
+
+    if bytes[bytes.len() - 1] == b'-' {
+        return Err(ErrorKind::EndsWithHyphen);
+    }
 
-        if s.contains("google")
-            || s.contains("null")
-            || s.contains("undefined")
-            || s.contains("ssl")
-        {
-            return Err(Error::from(ErrorKind::MatchesReservedIdPattern));
+    if options.reserved_ids
+        && (id_string::contains(s, "google")
+            || id_string::contains(s, "null")
+            || id_string::contains(s, "undefined")
+            || id_string::contains(s, "ssl"))
+    {
+        return Err(ErrorKind::ContainsRestrictedString);
+    }
+
+    Ok(())
+}
+
+const fn is_number(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    if bytes.is_empty() {
+        return false;
+    }
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return false;
         }
+        i += 1;
+    }
+    true
+}
+
+impl std::convert::AsRef<str> for ProjectId {
+    fn as_ref(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl std::convert::TryFrom<&str> for ProjectId {
+    type Error = Error;
 
-        Ok(Self(s))
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_from(s.to_string())
+    }
+}
+
+impl std::convert::TryFrom<String> for ProjectId {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        validate(&s)?;
+        Ok(Self(IdString::from(s)))
     }
 }
 
@@ -144,4 +422,181 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_error_kind() -> anyhow::Result<()> {
+        for (s, expected) in [
+            ("x".repeat(5), ErrorKind::LengthOutOfBounds),
+            ("x".repeat(31), ErrorKind::LengthOutOfBounds),
+            (
+                "chat/rooms".to_string(),
+                ErrorKind::ContainsInvalidCharacter,
+            ),
+            ("xAxxxx".to_string(), ErrorKind::ContainsInvalidCharacter),
+            ("0xxxxx".to_string(), ErrorKind::StartsWithNonLetter),
+            ("-xxxxx".to_string(), ErrorKind::StartsWithNonLetter),
+            ("xxxxx-".to_string(), ErrorKind::EndsWithHyphen),
+            (
+                "my-google-project".to_string(),
+                ErrorKind::ContainsRestrictedString,
+            ),
+            (
+                "my-null-project".to_string(),
+                ErrorKind::ContainsRestrictedString,
+            ),
+            (
+                "my-undefined-project".to_string(),
+                ErrorKind::ContainsRestrictedString,
+            ),
+            (
+                "my-ssl-project".to_string(),
+                ErrorKind::ContainsRestrictedString,
+            ),
+        ] {
+            assert_eq!(ProjectId::from_str(&s).unwrap_err().kind(), &expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_static() -> anyhow::Result<()> {
+        const ID: ProjectId = ProjectId::from_static("my-project");
+        assert_eq!(ID, ProjectId::from_str("my-project")?);
+        assert_eq!(ID.to_string(), "my-project");
+        assert!(std::panic::catch_unwind(|| ProjectId::from_static("my-google-project")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_wildcard() -> anyhow::Result<()> {
+        let wildcard = ProjectId::wildcard();
+        assert_eq!(wildcard.as_ref(), "_");
+        assert!(wildcard.is_wildcard());
+        assert!(ProjectId::from_str("_").is_err());
+        assert_eq!(
+            ProjectId::from_str_with("_", &ParseOptions::strict().wildcard_project(true))?,
+            wildcard
+        );
+        assert!(
+            ProjectId::from_str_with("__", &ParseOptions::strict().wildcard_project(true)).is_err()
+        );
+
+        let s = "projects/_/databases/(default)/documents/chatrooms/chatroom1";
+        let document_name = crate::DocumentName::from_str_with(s, &ParseOptions::lenient())?;
+        assert!(document_name.database_name().project_id().is_wildcard());
+        assert_eq!(document_name.to_string(), s);
+        Ok(())
+    }
+
+    #[test]
+    fn test_relaxed_project_ids() -> anyhow::Result<()> {
+        let options = ParseOptions::strict().relaxed_project_ids(true);
+        for (s, expected) in [
+            ("demo-my-project", true),
+            ("demo-My_Project", true),
+            ("demo", true),
+            ("my-ssl-project", true),
+            ("null-project", true),
+            ("0-project", true),
+            ("x".repeat(31).as_ref(), true),
+            ("", false),
+            ("demo/project", false),
+        ] {
+            assert_eq!(ProjectId::from_str_with(s, &options).is_ok(), expected);
+            assert_eq!(
+                ProjectId::from_str_with(s, &ParseOptions::lenient()).is_ok(),
+                expected
+            );
+        }
+        assert_eq!(
+            ProjectId::from_str_with("demo/project", &options)
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::ContainsSlash
+        );
+        assert!(ProjectId::from_str_with("demo-My_Project", &ParseOptions::strict()).is_err());
+
+        assert!(ProjectId::from_str_with("demo-My_Project", &options)?.is_demo());
+        assert!(!ProjectId::from_str("my-demo-project")?.is_demo());
+        Ok(())
+    }
+
+    #[test]
+    fn test_project_number() -> anyhow::Result<()> {
+        for (s, expected) in [
+            ("1", true),
+            ("123456789012", true),
+            ("0".repeat(30).as_ref(), true),
+            ("0".repeat(31).as_ref(), false),
+            ("123456-789", false),
+            ("0xxxxx", false),
+        ] {
+            assert_eq!(ProjectId::from_str(s).is_ok(), expected);
+            if expected {
+                assert!(ProjectId::from_str(s)?.is_number());
+            }
+        }
+        assert!(ProjectId::from_str_with(&"0".repeat(31), &ParseOptions::lenient()).is_ok());
+        assert!(!ProjectId::from_str("x12345")?.is_number());
+        assert!(!ProjectId::wildcard().is_number());
+
+        let s = "projects/123456789012/databases/(default)/documents/chatrooms/chatroom1";
+        let document_name = crate::DocumentName::from_str(s)?;
+        assert!(document_name.database_name().project_id().is_number());
+        assert_eq!(document_name.to_string(), s);
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> anyhow::Result<()> {
+        for s in [
+            "my-project".to_string(),
+            "123456789012".to_string(),
+            "_".to_string(),
+            "my-google-project".to_string(),
+            "x".repeat(31),
+        ] {
+            assert_eq!(ProjectId::validate(&s).err(), ProjectId::from_str(&s).err());
+            assert_eq!(ProjectId::is_valid(&s), ProjectId::from_str(&s).is_ok());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_env() -> anyhow::Result<()> {
+        let var = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(
+            ProjectId::from_env_with(var(&[("GOOGLE_CLOUD_PROJECT", "my-project")]))?,
+            ProjectId::from_str("my-project")?
+        );
+        assert_eq!(
+            ProjectId::from_env_with(var(&[
+                ("GCLOUD_PROJECT", "my-project2"),
+                ("GOOGLE_CLOUD_PROJECT", ""),
+                ("GCP_PROJECT", "my-project3"),
+            ]))?,
+            ProjectId::from_str("my-project2")?
+        );
+        assert_eq!(
+            ProjectId::from_env_with(var(&[("CLOUDSDK_CORE_PROJECT", "my-project")]))?,
+            ProjectId::from_str("my-project")?
+        );
+        assert_eq!(
+            ProjectId::from_env_with(var(&[])).unwrap_err().kind(),
+            &ErrorKind::ProjectIdNotFound
+        );
+        assert_eq!(
+            ProjectId::from_env_with(var(&[("GOOGLE_CLOUD_PROJECT", "My-Project")]))
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::ContainsInvalidCharacter
+        );
+        Ok(())
+    }
 }
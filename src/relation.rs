@@ -0,0 +1,36 @@
+/// The relation between two names.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{AnyName,CollectionName,DocumentName,Relation};
+/// use std::str::FromStr;
+///
+/// let any_name1 = AnyName::from(CollectionName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms"
+/// )?);
+/// let any_name2 = AnyName::from(DocumentName::from_str(
+///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+/// )?);
+/// assert_eq!(any_name1.relation(&any_name2), Relation::Ancestor);
+/// assert_eq!(any_name2.relation(&any_name1), Relation::Descendant);
+/// #     Ok(())
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Relation {
+    /// `self` is a (proper) ancestor of `other`.
+    Ancestor,
+    /// `self` is a (proper) descendant of `other`.
+    Descendant,
+    /// `self` and `other` are in different databases.
+    DifferentDatabase,
+    /// `self` and `other` are in the same database but are not related.
+    Disjoint,
+    /// `self` and `other` are equal.
+    Equal,
+    /// `self` and `other` have the same parent but are not equal.
+    Sibling,
+}
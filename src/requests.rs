@@ -0,0 +1,1311 @@
+// Constructors of the `google.firestore.v1` requests (`googleapis-tonic-google-firestore-v1`).
+
+use std::{borrow::Borrow, num::NonZeroUsize, str::FromStr};
+
+use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    partition_query_request, run_query_request::QueryType, structured_query,
+    structured_query::CollectionSelector, target, value::ValueType, BatchGetDocumentsRequest,
+    BeginTransactionRequest, CommitRequest, CreateDocumentRequest, DeleteDocumentRequest, Document,
+    DocumentMask, GetDocumentRequest, ListCollectionIdsRequest, ListCollectionIdsResponse,
+    ListDocumentsRequest, PartitionQueryRequest, PartitionQueryResponse, RunQueryRequest,
+    StructuredQuery, Target, UpdateDocumentRequest, Value, Write,
+};
+
+use crate::{
+    error::ErrorKind, limits, AnyName, CollectionId, CollectionName, DatabaseName, DocumentName,
+    Error, RootDocumentName,
+};
+
+fn documents_target(documents: Vec<String>) -> Target {
+    Target {
+        target_type: Some(target::TargetType::Documents(target::DocumentsTarget {
+            documents,
+        })),
+        ..Default::default()
+    }
+}
+
+impl CollectionId {
+    /// Parses the `collection_ids` of `response` into `CollectionId`s.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionId;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::ListCollectionIdsResponse;
+    /// use std::str::FromStr;
+    ///
+    /// let response = ListCollectionIdsResponse {
+    ///     collection_ids: vec!["messages".to_string(), "members".to_string()],
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     CollectionId::from_list_collection_ids_response(&response)?,
+    ///     [
+    ///         CollectionId::from_str("messages")?,
+    ///         CollectionId::from_str("members")?
+    ///     ]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_list_collection_ids_response(
+        response: &ListCollectionIdsResponse,
+    ) -> Result<Vec<CollectionId>, Error> {
+        response
+            .collection_ids
+            .iter()
+            .map(|collection_id| CollectionId::from_str(collection_id))
+            .collect()
+    }
+
+    /// Creates a new `CollectionSelector` that selects the collections with this id.
+    ///
+    /// If `all_descendants` is `true`, the selector selects all descendant collections of the `parent` of the query (a collection group query).
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionId;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_id = CollectionId::from_str("messages")?;
+    /// let selector = collection_id.to_collection_selector(true);
+    /// assert_eq!(selector.collection_id, "messages");
+    /// assert!(selector.all_descendants);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_collection_selector(&self, all_descendants: bool) -> CollectionSelector {
+        CollectionSelector {
+            collection_id: self.to_string(),
+            all_descendants,
+        }
+    }
+}
+
+impl CollectionName {
+    /// Returns the `parent` of the queries and the requests for this collection.
+    ///
+    /// The `parent` is the parent document name or, for a top-level collection, the root document name.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.query_parent(),
+    ///     "projects/my-project/databases/my-database/documents"
+    /// );
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.query_parent(),
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn query_parent(&self) -> &str {
+        let s = self.as_str();
+        &s[..s.len() - self.collection_id().as_ref().len() - 1]
+    }
+
+    /// Returns the `parent` of the queries and the requests for this collection as an `AnyName`.
+    ///
+    /// See [`CollectionName::query_parent`].
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{AnyName,CollectionName,DocumentName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.query_parent_name(),
+    ///     AnyName::from(RootDocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents"
+    ///     )?)
+    /// );
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.query_parent_name(),
+    ///     AnyName::from(DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    ///     )?)
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn query_parent_name(&self) -> AnyName {
+        self.parent()
+            .map(AnyName::from)
+            .unwrap_or_else(|| AnyName::from(self.root_document_name().clone()))
+    }
+
+    /// Creates a new `ListDocumentsRequest` that lists the documents in this collection.
+    ///
+    /// If `page_size` is `None`, the server default is used.
+    /// If `mask` is `None`, all fields of the documents are returned.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::DocumentMask;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// let request = collection_name.to_list_documents_request(None, None);
+    /// assert_eq!(
+    ///     request.parent,
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// );
+    /// assert_eq!(request.collection_id, "messages");
+    /// assert_eq!(request.page_size, 0);
+    /// assert_eq!(request.mask, None);
+    ///
+    /// let mask = DocumentMask {
+    ///     field_paths: vec!["content".to_string()],
+    /// };
+    /// let request = collection_name.to_list_documents_request(Some(100), Some(mask.clone()));
+    /// assert_eq!(request.page_size, 100);
+    /// assert_eq!(request.mask, Some(mask));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_list_documents_request(
+        &self,
+        page_size: Option<i32>,
+        mask: Option<DocumentMask>,
+    ) -> ListDocumentsRequest {
+        ListDocumentsRequest {
+            parent: self.query_parent().to_string(),
+            collection_id: self.collection_id().to_string(),
+            page_size: page_size.unwrap_or_default(),
+            mask,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `Target` of the `Listen` that listens to the result of `structured_query` on this collection.
+    ///
+    /// The `parent` is set to [`CollectionName::query_parent`] and the `from` of `structured_query` is set to this collection.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     target::{query_target, TargetType},
+    ///     StructuredQuery,
+    /// };
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// let target = collection_name.to_query_target(StructuredQuery::default());
+    /// let Some(TargetType::Query(query_target)) = target.target_type else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(
+    ///     query_target.parent,
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// );
+    /// assert_eq!(
+    ///     query_target.query_type,
+    ///     Some(query_target::QueryType::StructuredQuery(StructuredQuery {
+    ///         from: vec![collection_name.to_selector()],
+    ///         ..Default::default()
+    ///     }))
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_query_target(&self, structured_query: StructuredQuery) -> Target {
+        Target {
+            target_type: Some(target::TargetType::Query(target::QueryTarget {
+                parent: self.query_parent().to_string(),
+                query_type: Some(target::query_target::QueryType::StructuredQuery(
+                    StructuredQuery {
+                        from: vec![self.to_selector()],
+                        ..structured_query
+                    },
+                )),
+            })),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `RunQueryRequest` that runs `structured_query` on this collection.
+    ///
+    /// The `parent` is set to [`CollectionName::query_parent`] and the `from` of `structured_query` is set to this collection.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     run_query_request::QueryType, StructuredQuery,
+    /// };
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// let request = collection_name.to_run_query_request(StructuredQuery::default());
+    /// assert_eq!(
+    ///     request.parent,
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// );
+    /// assert_eq!(
+    ///     request.query_type,
+    ///     Some(QueryType::StructuredQuery(StructuredQuery {
+    ///         from: vec![collection_name.to_selector()],
+    ///         ..Default::default()
+    ///     }))
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_run_query_request(&self, structured_query: StructuredQuery) -> RunQueryRequest {
+        RunQueryRequest {
+            parent: self.query_parent().to_string(),
+            query_type: Some(QueryType::StructuredQuery(StructuredQuery {
+                from: vec![self.to_selector()],
+                ..structured_query
+            })),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `CollectionSelector` that selects only this collection (`all_descendants` is `false`).
+    ///
+    /// Use it with [`CollectionName::query_parent`] as the `parent` of the query.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages"
+    /// )?;
+    /// let selector = collection_name.to_selector();
+    /// assert_eq!(selector.collection_id, "messages");
+    /// assert!(!selector.all_descendants);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_selector(&self) -> CollectionSelector {
+        self.collection_id().to_collection_selector(false)
+    }
+}
+
+impl DatabaseName {
+    /// Creates a new `BatchGetDocumentsRequest` that gets the documents with `document_names` from this database.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of `document_names` is not in this database, or if there are more than
+    /// [`limits::MAX_DOCUMENTS_PER_BATCH_GET`] of them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseName,DocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let document_names = [
+    ///     database_name.doc("chatrooms/chatroom1")?,
+    ///     database_name.doc("chatrooms/chatroom2")?,
+    /// ];
+    /// let request = database_name.to_batch_get_documents_request(&document_names)?;
+    /// assert_eq!(request.database, "projects/my-project/databases/my-database");
+    /// assert_eq!(
+    ///     request.documents,
+    ///     [
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom2",
+    ///     ]
+    /// );
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/(default)/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert!(database_name.to_batch_get_documents_request([document_name]).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_batch_get_documents_request<I>(
+        &self,
+        document_names: I,
+    ) -> Result<BatchGetDocumentsRequest, Error>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<DocumentName>,
+    {
+        let documents = document_names
+            .into_iter()
+            .map(|document_name| self.document_name_in_database(document_name.borrow()))
+            .collect::<Result<Vec<String>, Error>>()?;
+        if documents.len() > limits::MAX_DOCUMENTS_PER_BATCH_GET {
+            return Err(Error::from(ErrorKind::LengthOutOfBounds));
+        }
+        Ok(BatchGetDocumentsRequest {
+            database: self.to_string(),
+            documents,
+            ..Default::default()
+        })
+    }
+
+    /// Creates new `BatchGetDocumentsRequest`s that get the documents with `document_names` from this database, with at most `chunk_size` documents per request.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of `document_names` is not in this database, or if `chunk_size` is
+    /// greater than [`limits::MAX_DOCUMENTS_PER_BATCH_GET`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DatabaseName;
+    /// use std::{num::NonZeroUsize, str::FromStr};
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let document_names = (1..=5)
+    ///     .map(|i| database_name.doc(format!("chatrooms/chatroom{}", i)))
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// let requests = database_name.to_batch_get_documents_requests(&document_names, NonZeroUsize::new(2).unwrap())?;
+    /// assert_eq!(
+    ///     requests
+    ///         .iter()
+    ///         .map(|request| request.documents.len())
+    ///         .collect::<Vec<_>>(),
+    ///     [2, 2, 1]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_batch_get_documents_requests<I>(
+        &self,
+        document_names: I,
+        chunk_size: NonZeroUsize,
+    ) -> Result<Vec<BatchGetDocumentsRequest>, Error>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<DocumentName>,
+    {
+        if chunk_size.get() > limits::MAX_DOCUMENTS_PER_BATCH_GET {
+            return Err(Error::from(ErrorKind::LengthOutOfBounds));
+        }
+        let documents = document_names
+            .into_iter()
+            .map(|document_name| self.document_name_in_database(document_name.borrow()))
+            .collect::<Result<Vec<String>, Error>>()?;
+        Ok(documents
+            .chunks(chunk_size.get())
+            .map(|documents| BatchGetDocumentsRequest {
+                database: self.to_string(),
+                documents: documents.to_vec(),
+                ..Default::default()
+            })
+            .collect())
+    }
+
+    /// Creates a new `BeginTransactionRequest` for this database.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DatabaseName;
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let request = database_name.to_begin_transaction_request();
+    /// assert_eq!(request.database, "projects/my-project/databases/my-database");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_begin_transaction_request(&self) -> BeginTransactionRequest {
+        BeginTransactionRequest {
+            database: self.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `CommitRequest` that applies `writes` to this database outside a transaction.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DatabaseName;
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let request = database_name.to_commit_request(vec![]);
+    /// assert_eq!(request.database, "projects/my-project/databases/my-database");
+    /// assert!(request.writes.is_empty());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_commit_request(&self, writes: Vec<Write>) -> CommitRequest {
+        CommitRequest {
+            database: self.to_string(),
+            writes,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `Target` of the `Listen` that listens to the documents with `document_names` in this database.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of `document_names` is not in this database, or if there are more than
+    /// [`limits::MAX_DOCUMENTS_PER_TARGET`] of them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseName,DocumentName};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::target::TargetType;
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let target = database_name.to_documents_target([database_name.doc("chatrooms/chatroom1")?])?;
+    /// let Some(TargetType::Documents(documents_target)) = target.target_type else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(
+    ///     documents_target.documents,
+    ///     ["projects/my-project/databases/my-database/documents/chatrooms/chatroom1"]
+    /// );
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/(default)/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert!(database_name.to_documents_target([document_name]).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_documents_target<I>(&self, document_names: I) -> Result<Target, Error>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<DocumentName>,
+    {
+        let documents = document_names
+            .into_iter()
+            .map(|document_name| self.document_name_in_database(document_name.borrow()))
+            .collect::<Result<Vec<String>, Error>>()?;
+        if documents.len() > limits::MAX_DOCUMENTS_PER_TARGET {
+            return Err(Error::from(ErrorKind::LengthOutOfBounds));
+        }
+        Ok(documents_target(documents))
+    }
+
+    /// Creates new `Target`s of the `Listen` that listen to the documents with `document_names` in this database, with at most `max_documents` documents per target.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of `document_names` is not in this database, or if `max_documents` is
+    /// greater than [`limits::MAX_DOCUMENTS_PER_TARGET`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DatabaseName;
+    /// use std::{num::NonZeroUsize, str::FromStr};
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let document_names = (1..=5)
+    ///     .map(|i| database_name.doc(format!("chatrooms/chatroom{}", i)))
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// let targets = database_name.to_documents_targets(&document_names, NonZeroUsize::new(2).unwrap())?;
+    /// assert_eq!(targets.len(), 3);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_documents_targets<I>(
+        &self,
+        document_names: I,
+        max_documents: NonZeroUsize,
+    ) -> Result<Vec<Target>, Error>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<DocumentName>,
+    {
+        if max_documents.get() > limits::MAX_DOCUMENTS_PER_TARGET {
+            return Err(Error::from(ErrorKind::LengthOutOfBounds));
+        }
+        let documents = document_names
+            .into_iter()
+            .map(|document_name| self.document_name_in_database(document_name.borrow()))
+            .collect::<Result<Vec<String>, Error>>()?;
+        Ok(documents
+            .chunks(max_documents.get())
+            .map(|documents| documents_target(documents.to_vec()))
+            .collect())
+    }
+
+    // Returns `document_name` as a string if it is in this database.
+    fn document_name_in_database(&self, document_name: &DocumentName) -> Result<String, Error> {
+        if document_name.database_name() != self {
+            return Err(Error::from(ErrorKind::DatabaseNameMismatch));
+        }
+        Ok(document_name.to_string())
+    }
+}
+
+impl DocumentName {
+    /// Parses the `partitions` (cursors) of `response` into `DocumentName`s.
+    ///
+    /// Each cursor has a reference value, which is the document name at the start of the partition.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, Cursor, PartitionQueryResponse, Value,
+    /// };
+    /// use std::str::FromStr;
+    ///
+    /// let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1";
+    /// let response = PartitionQueryResponse {
+    ///     partitions: vec![Cursor {
+    ///         values: vec![Value {
+    ///             value_type: Some(ValueType::ReferenceValue(s.to_string())),
+    ///         }],
+    ///         before: true,
+    ///     }],
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     DocumentName::from_partition_query_response(&response)?,
+    ///     [DocumentName::from_str(s)?]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_partition_query_response(
+        response: &PartitionQueryResponse,
+    ) -> Result<Vec<DocumentName>, Error> {
+        response
+            .partitions
+            .iter()
+            .map(|cursor| {
+                cursor
+                    .values
+                    .first()
+                    .ok_or_else(|| Error::from(ErrorKind::NotReferenceValue))
+                    .and_then(DocumentName::from_reference_value)
+            })
+            .collect()
+    }
+
+    /// Parses the reference value `value` into a `DocumentName`.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` is not a reference value or is not a valid document name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{value::ValueType, Value};
+    /// use std::str::FromStr;
+    ///
+    /// let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1";
+    /// let value = Value {
+    ///     value_type: Some(ValueType::ReferenceValue(s.to_string())),
+    /// };
+    /// assert_eq!(DocumentName::from_reference_value(&value)?, DocumentName::from_str(s)?);
+    ///
+    /// let value = Value {
+    ///     value_type: Some(ValueType::StringValue(s.to_string())),
+    /// };
+    /// assert!(DocumentName::from_reference_value(&value).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_reference_value(value: &Value) -> Result<DocumentName, Error> {
+        match value.value_type.as_ref() {
+            Some(ValueType::ReferenceValue(s)) => DocumentName::from_str(s),
+            _ => Err(Error::from(ErrorKind::NotReferenceValue)),
+        }
+    }
+
+    /// Creates a new `CreateDocumentRequest` that creates `document` with this name.
+    ///
+    /// The `name` of `document` is cleared because the server requires it to be empty.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::Document;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// let request = document_name.to_create_document_request(Document::default());
+    /// assert_eq!(
+    ///     request.parent,
+    ///     "projects/my-project/databases/my-database/documents"
+    /// );
+    /// assert_eq!(request.collection_id, "chatrooms");
+    /// assert_eq!(request.document_id, "chatroom1");
+    /// assert_eq!(request.document, Some(Document::default()));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_create_document_request(&self, document: Document) -> CreateDocumentRequest {
+        let s = self.as_str();
+        let collection_name_len = s.len() - self.document_id().as_ref().len() - 1;
+        let parent_len = collection_name_len - self.collection_id().as_ref().len() - 1;
+        CreateDocumentRequest {
+            parent: s[..parent_len].to_string(),
+            collection_id: self.collection_id().to_string(),
+            document_id: self.document_id().to_string(),
+            document: Some(Document {
+                name: String::new(),
+                ..document
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `DeleteDocumentRequest` that deletes the document with this name.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// let request = document_name.to_delete_document_request();
+    /// assert_eq!(request.name, document_name.as_str());
+    /// assert_eq!(request.current_document, None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_delete_document_request(&self) -> DeleteDocumentRequest {
+        DeleteDocumentRequest {
+            name: self.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `GetDocumentRequest` that gets the document with this name.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// let request = document_name.to_get_document_request();
+    /// assert_eq!(request.name, document_name.as_str());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_get_document_request(&self) -> GetDocumentRequest {
+        GetDocumentRequest {
+            name: self.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `ListCollectionIdsRequest` that lists the ids of the collections in this document.
+    ///
+    /// If `page_size` is `None`, the server default is used.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// let request = document_name.to_list_collection_ids_request(Some(100));
+    /// assert_eq!(
+    ///     request.parent,
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// );
+    /// assert_eq!(request.page_size, 100);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_list_collection_ids_request(
+        &self,
+        page_size: Option<i32>,
+    ) -> ListCollectionIdsRequest {
+        ListCollectionIdsRequest {
+            parent: self.to_string(),
+            page_size: page_size.unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the reference value of this document, which is used for the `__name__` cursors (`start_at` and `end_at`).
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::{
+    ///     value::ValueType, Cursor, StructuredQuery,
+    /// };
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// let value = document_name.to_reference_value();
+    /// assert_eq!(
+    ///     value.value_type,
+    ///     Some(ValueType::ReferenceValue(document_name.to_string()))
+    /// );
+    ///
+    /// let structured_query = StructuredQuery {
+    ///     start_at: Some(Cursor {
+    ///         values: vec![value],
+    ///         before: false,
+    ///     }),
+    ///     ..Default::default()
+    /// };
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_reference_value(&self) -> Value {
+        Value {
+            value_type: Some(ValueType::ReferenceValue(self.to_string())),
+        }
+    }
+
+    /// Creates a new `UpdateDocumentRequest` that updates (or creates) the document with this name to `document`.
+    ///
+    /// The `name` of `document` is set to this name.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::Document;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// let request = document_name.to_update_document_request(Document::default());
+    /// assert_eq!(
+    ///     request.document.map(|document| document.name),
+    ///     Some(document_name.to_string())
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_update_document_request(&self, document: Document) -> UpdateDocumentRequest {
+        UpdateDocumentRequest {
+            document: Some(Document {
+                name: self.to_string(),
+                ..document
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `DocumentName` from the `name` of `document`.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `name` of `document` is empty or is not a valid document name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::Document;
+    /// use std::str::FromStr;
+    ///
+    /// let s = "projects/my-project/databases/my-database/documents/chatrooms/chatroom1";
+    /// let document = Document {
+    ///     name: s.to_string(),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(DocumentName::try_from_document(&document)?, DocumentName::from_str(s)?);
+    ///
+    /// assert_eq!(
+    ///     DocumentName::try_from_document(&Document::default())
+    ///         .unwrap_err()
+    ///         .to_string(),
+    ///     "missing document name"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn try_from_document(document: &Document) -> Result<DocumentName, Error> {
+        if document.name.is_empty() {
+            return Err(Error::from(ErrorKind::MissingDocumentName));
+        }
+        DocumentName::from_str(&document.name)
+    }
+}
+
+impl RootDocumentName {
+    /// Creates a new `PartitionQueryRequest` that partitions the collection group `collection_id` in this database into at most `partition_count` partitions.
+    ///
+    /// The query selects all descendant collections with `collection_id` and is ordered by `__name__` ascending, as required by the server.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,RootDocumentName};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::partition_query_request::QueryType;
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name =
+    ///     RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?;
+    /// let collection_id = CollectionId::from_str("messages")?;
+    /// let request = root_document_name.to_partition_query_request(&collection_id, 10);
+    /// assert_eq!(
+    ///     request.parent,
+    ///     "projects/my-project/databases/my-database/documents"
+    /// );
+    /// assert_eq!(request.partition_count, 10);
+    /// let Some(QueryType::StructuredQuery(query)) = request.query_type else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(query.from, [collection_id.to_collection_selector(true)]);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_partition_query_request(
+        &self,
+        collection_id: &CollectionId,
+        partition_count: i64,
+    ) -> PartitionQueryRequest {
+        PartitionQueryRequest {
+            parent: self.to_string(),
+            partition_count,
+            query_type: Some(partition_query_request::QueryType::StructuredQuery(
+                StructuredQuery {
+                    from: vec![collection_id.to_collection_selector(true)],
+                    order_by: vec![structured_query::Order {
+                        field: Some(structured_query::FieldReference {
+                            field_path: "__name__".to_string(),
+                        }),
+                        direction: structured_query::Direction::Ascending as i32,
+                    }],
+                    ..Default::default()
+                },
+            )),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `ListCollectionIdsRequest` that lists the ids of the collections in the root document (the top-level collections).
+    ///
+    /// If `page_size` is `None`, the server default is used.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::RootDocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name =
+    ///     RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?;
+    /// let request = root_document_name.to_list_collection_ids_request(Some(100));
+    /// assert_eq!(
+    ///     request.parent,
+    ///     "projects/my-project/databases/my-database/documents"
+    /// );
+    /// assert_eq!(request.page_size, 100);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_list_collection_ids_request(
+        &self,
+        page_size: Option<i32>,
+    ) -> ListCollectionIdsRequest {
+        ListCollectionIdsRequest {
+            parent: self.to_string(),
+            page_size: page_size.unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `RunQueryRequest` that runs `structured_query` on this database.
+    ///
+    /// Use it for the collection group queries (`all_descendants` is `true`), which select the collections from the whole database.
+    ///
+    /// This method requires the `proto` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{CollectionId,RootDocumentName};
+    /// use googleapis_tonic_google_firestore_v1::google::firestore::v1::StructuredQuery;
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name =
+    ///     RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?;
+    /// let request = root_document_name.to_run_query_request(StructuredQuery {
+    ///     from: vec![CollectionId::from_str("messages")?.to_collection_selector(true)],
+    ///     ..Default::default()
+    /// });
+    /// assert_eq!(
+    ///     request.parent,
+    ///     "projects/my-project/databases/my-database/documents"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_run_query_request(&self, structured_query: StructuredQuery) -> RunQueryRequest {
+        RunQueryRequest {
+            parent: self.to_string(),
+            query_type: Some(QueryType::StructuredQuery(structured_query)),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use googleapis_tonic_google_firestore_v1::google::firestore::v1::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_to_batch_get_documents_requests() -> anyhow::Result<()> {
+        let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+        let document_names = (0..limits::MAX_DOCUMENTS_PER_BATCH_GET + 1)
+            .map(|i| database_name.doc(format!("chatrooms/chatroom{}", i)))
+            .collect::<Result<Vec<DocumentName>, Error>>()?;
+        let requests = database_name
+            .to_batch_get_documents_requests(&document_names, NonZeroUsize::new(500).unwrap())?;
+        assert_eq!(
+            requests
+                .iter()
+                .map(|request| request.documents.len())
+                .collect::<Vec<usize>>(),
+            [500, 500, 1]
+        );
+        assert_eq!(
+            requests
+                .into_iter()
+                .flat_map(|request| request.documents)
+                .collect::<Vec<String>>(),
+            document_names
+                .iter()
+                .map(DocumentName::to_string)
+                .collect::<Vec<String>>()
+        );
+        assert!(database_name
+            .to_batch_get_documents_requests(
+                Vec::<DocumentName>::new(),
+                NonZeroUsize::new(500).unwrap()
+            )?
+            .is_empty());
+        let other = DatabaseName::from_str("projects/my-project/databases/(default)")?;
+        assert!(database_name
+            .to_batch_get_documents_requests(
+                [other.doc("chatrooms/chatroom1")?],
+                NonZeroUsize::new(500).unwrap()
+            )
+            .is_err());
+        assert_eq!(
+            database_name
+                .to_batch_get_documents_requests(
+                    &document_names,
+                    NonZeroUsize::new(limits::MAX_DOCUMENTS_PER_BATCH_GET + 1).unwrap()
+                )
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::LengthOutOfBounds
+        );
+        assert_eq!(
+            database_name
+                .to_batch_get_documents_requests(
+                    &document_names,
+                    NonZeroUsize::new(limits::MAX_DOCUMENTS_PER_BATCH_GET).unwrap()
+                )?
+                .len(),
+            2
+        );
+        assert_eq!(
+            database_name
+                .to_batch_get_documents_request(&document_names)
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::LengthOutOfBounds
+        );
+        assert!(database_name
+            .to_batch_get_documents_request(&document_names[..limits::MAX_DOCUMENTS_PER_BATCH_GET])
+            .is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_list_collection_ids_response() -> anyhow::Result<()> {
+        let response = ListCollectionIdsResponse {
+            collection_ids: vec!["messages".to_string()],
+            next_page_token: "token".to_string(),
+        };
+        assert_eq!(
+            CollectionId::from_list_collection_ids_response(&response)?,
+            [CollectionId::from_str("messages")?]
+        );
+        let response = ListCollectionIdsResponse {
+            collection_ids: vec!["messages".to_string(), "__messages__".to_string()],
+            ..Default::default()
+        };
+        assert!(CollectionId::from_list_collection_ids_response(&response).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_partition_query_response() -> anyhow::Result<()> {
+        let response = PartitionQueryResponse {
+            partitions: vec![Cursor {
+                values: vec![Value {
+                    value_type: Some(ValueType::StringValue("message1".to_string())),
+                }],
+                before: true,
+            }],
+            ..Default::default()
+        };
+        assert!(DocumentName::from_partition_query_response(&response).is_err());
+        let response = PartitionQueryResponse {
+            partitions: vec![Cursor::default()],
+            ..Default::default()
+        };
+        assert!(DocumentName::from_partition_query_response(&response).is_err());
+        assert!(
+            DocumentName::from_partition_query_response(&PartitionQueryResponse::default())?
+                .is_empty()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_documents_targets() -> anyhow::Result<()> {
+        let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+        let document_names = (0..5)
+            .map(|i| database_name.doc(format!("chatrooms/chatroom{}", i)))
+            .collect::<Result<Vec<DocumentName>, Error>>()?;
+        let targets =
+            database_name.to_documents_targets(&document_names, NonZeroUsize::new(2).unwrap())?;
+        assert_eq!(
+            targets
+                .into_iter()
+                .map(|target| match target.target_type {
+                    Some(target::TargetType::Documents(documents_target)) => {
+                        documents_target.documents.len()
+                    }
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<usize>>(),
+            [2, 2, 1]
+        );
+        let other = DatabaseName::from_str("projects/my-project/databases/(default)")?;
+        assert!(database_name
+            .to_documents_targets(
+                [other.doc("chatrooms/chatroom1")?],
+                NonZeroUsize::new(2).unwrap()
+            )
+            .is_err());
+        assert!(database_name
+            .to_documents_targets(
+                &document_names,
+                NonZeroUsize::new(limits::MAX_DOCUMENTS_PER_TARGET + 1).unwrap()
+            )
+            .is_err());
+        let document_names = (0..limits::MAX_DOCUMENTS_PER_TARGET + 1)
+            .map(|i| database_name.doc(format!("chatrooms/chatroom{}", i)))
+            .collect::<Result<Vec<DocumentName>, Error>>()?;
+        assert_eq!(
+            database_name
+                .to_documents_target(&document_names)
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::LengthOutOfBounds
+        );
+        assert!(database_name
+            .to_documents_target(&document_names[..limits::MAX_DOCUMENTS_PER_TARGET])
+            .is_ok());
+        assert_eq!(
+            database_name
+                .to_documents_targets(
+                    &document_names,
+                    NonZeroUsize::new(limits::MAX_DOCUMENTS_PER_TARGET).unwrap()
+                )?
+                .len(),
+            2
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_parent() -> anyhow::Result<()> {
+        for (s, expected) in [
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms",
+                "projects/my-project/databases/my-database/documents",
+            ),
+            (
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages",
+                "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+            ),
+        ] {
+            let collection_name = CollectionName::from_str(s)?;
+            assert_eq!(collection_name.query_parent(), expected);
+            let request = collection_name.to_list_documents_request(None, None);
+            assert_eq!(
+                request.collection_id,
+                collection_name.collection_id().as_ref()
+            );
+            assert_eq!(request.parent, expected);
+            assert_eq!(collection_name.query_parent_name().to_string(), expected);
+            let request = collection_name.to_run_query_request(StructuredQuery::default());
+            assert_eq!(request.parent, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_create_document_request() -> anyhow::Result<()> {
+        for (s, parent, collection_id, document_id) in [
+            (
+                "projects/my-project/databases/(default)/documents/chatrooms/chatroom1",
+                "projects/my-project/databases/(default)/documents",
+                "chatrooms",
+                "chatroom1",
+            ),
+            (
+                "projects/my-project/databases/(default)/documents/chatrooms/chatroom1/messages/message1",
+                "projects/my-project/databases/(default)/documents/chatrooms/chatroom1",
+                "messages",
+                "message1",
+            ),
+        ] {
+            let document_name = DocumentName::from_str(s)?;
+            let request = document_name.to_create_document_request(Document {
+                name: s.to_string(),
+                ..Default::default()
+            });
+            assert_eq!(request.parent, parent);
+            assert_eq!(request.collection_id, collection_id);
+            assert_eq!(request.document_id, document_id);
+            assert_eq!(request.document.map(|document| document.name), Some(String::new()));
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
-use std::str::FromStr;
+use std::sync::Arc;
 
 use crate::{
-    error::ErrorKind, CollectionName, CollectionPath, DatabaseId, DatabaseName, DocumentName,
-    DocumentPath, Error, ProjectId,
+    database_id, document_path, error::ErrorKind, limits, project_id, CollectionId, CollectionName,
+    CollectionPath, DatabaseId, DatabaseName, DocumentId, DocumentName, DocumentPath, Error,
+    ParseOptions, ProjectId,
 };
 
 /// A root document name.
@@ -11,6 +12,12 @@ use crate::{
 ///
 /// `{database_name}/documents`
 ///
+/// # Sharing
+///
+/// The `DatabaseName` is held behind an `Arc`, so cloning a `RootDocumentName`
+/// (and the names derived from it with `doc`, `collection`, `parent`, ...) does not copy
+/// the project and database ids.
+///
 /// # Examples
 ///
 /// ```rust
@@ -31,7 +38,7 @@ use crate::{
 ///
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct RootDocumentName {
-    database_name: DatabaseName,
+    database_name: Arc<DatabaseName>,
 }
 
 impl RootDocumentName {
@@ -57,7 +64,96 @@ impl RootDocumentName {
     /// # }
     /// ```
     pub fn new(database_name: DatabaseName) -> Self {
-        Self { database_name }
+        Self {
+            database_name: Arc::new(database_name),
+        }
+    }
+
+    /// Creates a new `RootDocumentName` from `project_id` in the default database `(default)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `project_id` cannot be converted into a `ProjectId`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{ProjectId,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name = RootDocumentName::from_project_id("my-project")?;
+    /// assert_eq!(
+    ///     root_document_name.to_string(),
+    ///     "projects/my-project/databases/(default)/documents"
+    /// );
+    /// assert_eq!(
+    ///     RootDocumentName::from_project_id(ProjectId::from_str("my-project")?)?,
+    ///     root_document_name
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_project_id<P>(project_id: P) -> Result<Self, Error>
+    where
+        P: TryInto<ProjectId>,
+        P::Error: std::fmt::Display,
+    {
+        DatabaseName::from_project_id(project_id).map(Self::new)
+    }
+
+    /// Creates a new `RootDocumentName` from `s` with `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid `RootDocumentName` under `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{ParseOptions,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(
+    ///     RootDocumentName::from_str_with(
+    ///         "projects/my-project/databases/my-database/documents",
+    ///         &ParseOptions::strict()
+    ///     )?,
+    ///     RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?
+    /// );
+    ///
+    /// let s = "projects/my-google-project/databases/my-database/documents";
+    /// assert!(RootDocumentName::from_str(s).is_err());
+    /// assert!(RootDocumentName::from_str_with(s, &ParseOptions::lenient()).is_ok());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        options.parse_normalized(s, |s| {
+            if options.length_limits {
+                limits::check_name_length(s)?;
+            }
+
+            let mut parts = s.split('/');
+            let [Some(projects), Some(project_id), Some(databases), Some(database_id), Some(documents), None] =
+                std::array::from_fn(|_| parts.next())
+            else {
+                return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+            };
+            if projects != "projects" || databases != "databases" || documents != "documents" {
+                return Err(Error::from(ErrorKind::InvalidName));
+            }
+
+            let project_id_start = projects.len() + 1;
+            let database_id_start = project_id_start + project_id.len() + 1 + databases.len() + 1;
+            let project_id = ProjectId::from_str_with(project_id, options)
+                .map_err(|e| e.at_segment(1, project_id, project_id_start))?;
+            let database_id = DatabaseId::from_str_with(database_id, options)
+                .map_err(|e| e.at_segment(3, database_id, database_id_start))?;
+            let database_name = DatabaseName::new(project_id, database_id);
+            Ok(Self::new(database_name))
+        })
     }
 
     /// Creates a new `CollectionName` from this `RootDocumentName` and `collection_path`.
@@ -158,7 +254,7 @@ impl RootDocumentName {
         let collection_path = collection_path
             .try_into()
             .map_err(|e| Error::from(ErrorKind::CollectionPathConversion(e.to_string())))?;
-        Ok(CollectionName::new(self, collection_path))
+        CollectionName::try_new(self, collection_path)
     }
 
     /// Creates a new `DocumentName` from this `RootDocumentName` and `document_path`.
@@ -210,6 +306,54 @@ impl RootDocumentName {
         self.clone().into_doc(document_path)
     }
 
+    /// Creates a new `DocumentName` from this `RootDocumentName` and `(collection_id, document_id)` pairs ordered from the root to the leaf.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pairs` is empty, any of the ids cannot be converted or the new `DocumentName` is too deep or too long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name = RootDocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents"
+    /// )?;
+    /// assert_eq!(
+    ///     root_document_name.doc_from_ids([("chatrooms", "chatroom1"), ("messages", "message1")])?,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+    ///     )?
+    /// );
+    ///
+    /// let pairs: &[(&str, &str)] = &[("chatrooms", "chatroom1")];
+    /// assert_eq!(
+    ///     root_document_name.doc_from_ids(pairs.iter().copied())?,
+    ///     root_document_name.doc("chatrooms/chatroom1")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn doc_from_ids<C, D, I>(&self, pairs: I) -> Result<DocumentName, Error>
+    where
+        C: TryInto<CollectionId>,
+        C::Error: std::fmt::Display,
+        D: TryInto<DocumentId>,
+        D::Error: std::fmt::Display,
+        I: IntoIterator<Item = (C, D)>,
+    {
+        let pairs = pairs
+            .into_iter()
+            .map(|(collection_id, document_id)| {
+                document_path::try_into_pair(collection_id, document_id)
+            })
+            .collect::<Result<Vec<(CollectionId, DocumentId)>, Error>>()?;
+        DocumentName::from_pairs(self.clone(), pairs)
+    }
+
     /// Creates a new `DocumentName` by consuming the `RootDocumentName` with the provided `document_path`.
     ///
     /// # Examples
@@ -259,7 +403,246 @@ impl RootDocumentName {
         let document_path = document_path
             .try_into()
             .map_err(|e| Error::from(ErrorKind::DocumentPathConversion(e.to_string())))?;
-        Ok(DocumentName::new(self, document_path))
+        DocumentName::try_new(self, document_path)
+    }
+
+    /// Converts this `RootDocumentName` into its canonical string representation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::RootDocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name = RootDocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents"
+    /// )?;
+    /// assert_eq!(
+    ///     root_document_name.into_string(),
+    ///     "projects/my-project/databases/my-database/documents"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn into_string(self) -> String {
+        self.to_string()
+    }
+
+    /// Returns `true` if this `RootDocumentName` is in the default database `(default)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::RootDocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(RootDocumentName::from_str("projects/my-project/databases/(default)/documents")?.is_default_database());
+    /// assert!(!RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?.is_default_database());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_default_database(&self) -> bool {
+        self.database_name.is_default_database()
+    }
+
+    /// Returns `true` if `s` is a valid `RootDocumentName`.
+    ///
+    /// This is the same as `RootDocumentName::validate(s).is_ok()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::RootDocumentName;
+    ///
+    /// assert!(RootDocumentName::is_valid("projects/my-project/databases/my-database/documents"));
+    /// assert!(!RootDocumentName::is_valid("projects/my-project/databases/my-database"));
+    /// ```
+    pub fn is_valid(s: &str) -> bool {
+        Self::validate(s).is_ok()
+    }
+
+    /// Returns the value of the `x-goog-request-params` header that routes requests to the database of this `RootDocumentName`.
+    ///
+    /// See [`DatabaseName::routing_header_value`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::RootDocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name = RootDocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents"
+    /// )?;
+    /// assert_eq!(
+    ///     root_document_name.routing_header_value(),
+    ///     "database=projects%2Fmy-project%2Fdatabases%2Fmy-database"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn routing_header_value(&self) -> String {
+        self.database_name.routing_header_value()
+    }
+
+    /// Returns `true` if this `RootDocumentName` and `other` are in the same database.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::RootDocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name = RootDocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents"
+    /// )?;
+    /// assert!(root_document_name.same_database(&RootDocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents"
+    /// )?));
+    /// assert!(!root_document_name.same_database(&RootDocumentName::from_str(
+    ///     "projects/my-project/databases/(default)/documents"
+    /// )?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn same_database(&self, other: &RootDocumentName) -> bool {
+        self.database_name == other.database_name
+    }
+
+    /// Returns `true` if this `RootDocumentName` and `other` are in the same project.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::RootDocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name = RootDocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents"
+    /// )?;
+    /// assert!(root_document_name.same_project(&RootDocumentName::from_str(
+    ///     "projects/my-project/databases/(default)/documents"
+    /// )?));
+    /// assert!(!root_document_name.same_project(&RootDocumentName::from_str(
+    ///     "projects/my-project2/databases/my-database/documents"
+    /// )?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn same_project(&self, other: &RootDocumentName) -> bool {
+        self.database_name.project_id() == other.database_name.project_id()
+    }
+
+    /// Checks that `s` is a valid `RootDocumentName` without creating a `RootDocumentName`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that `RootDocumentName::from_str(s)` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::RootDocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(RootDocumentName::validate("projects/my-project/databases/my-database/documents").is_ok());
+    ///
+    /// let s = "projects/my-project/databases/db/documents";
+    /// assert_eq!(
+    ///     RootDocumentName::validate(s).unwrap_err(),
+    ///     RootDocumentName::from_str(s).unwrap_err()
+    /// );
+    /// ```
+    pub fn validate(s: &str) -> Result<(), Error> {
+        limits::check_name_length(s)?;
+
+        let mut parts = s.split('/');
+        let [Some(projects), Some(project_id), Some(databases), Some(database_id), Some(documents), None] =
+            std::array::from_fn(|_| parts.next())
+        else {
+            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+        };
+        if projects != "projects" || databases != "databases" || documents != "documents" {
+            return Err(Error::from(ErrorKind::InvalidName));
+        }
+
+        let project_id_start = projects.len() + 1;
+        let database_id_start = project_id_start + project_id.len() + 1 + databases.len() + 1;
+        project_id::validate(project_id)
+            .map_err(|e| Error::from(e).at_segment(1, project_id, project_id_start))?;
+        database_id::validate(database_id)
+            .map_err(|e| Error::from(e).at_segment(3, database_id, database_id_start))?;
+        Ok(())
+    }
+
+    /// Creates a new `RootDocumentName` with the same `ProjectId` and the provided `database_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `database_id` cannot be converted into a `DatabaseId`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseId,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name = RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?;
+    /// assert_eq!(
+    ///     root_document_name.with_database_id("my-database2")?,
+    ///     RootDocumentName::from_str("projects/my-project/databases/my-database2/documents")?
+    /// );
+    /// assert_eq!(
+    ///     root_document_name.with_database_id(DatabaseId::default())?,
+    ///     RootDocumentName::from_str("projects/my-project/databases/(default)/documents")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn with_database_id<E, T>(&self, database_id: T) -> Result<RootDocumentName, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<DatabaseId, Error = E>,
+    {
+        Ok(Self::new(self.database_name.with_database_id(database_id)?))
+    }
+
+    /// Creates a new `RootDocumentName` with the same `DatabaseId` and the provided `project_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `project_id` cannot be converted into a `ProjectId`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{ProjectId,RootDocumentName};
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name = RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?;
+    /// assert_eq!(
+    ///     root_document_name.with_project_id("my-project2")?,
+    ///     RootDocumentName::from_str("projects/my-project2/databases/my-database/documents")?
+    /// );
+    /// assert!(root_document_name.with_project_id("my-google-project").is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn with_project_id<E, T>(&self, project_id: T) -> Result<RootDocumentName, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<ProjectId, Error = E>,
+    {
+        Ok(Self::new(self.database_name.with_project_id(project_id)?))
     }
 
     pub(crate) fn as_database_name(&self) -> &DatabaseName {
@@ -269,13 +652,13 @@ impl RootDocumentName {
 
 impl std::convert::From<DatabaseName> for RootDocumentName {
     fn from(database_name: DatabaseName) -> Self {
-        Self { database_name }
+        Self::new(database_name)
     }
 }
 
 impl std::convert::From<RootDocumentName> for DatabaseName {
     fn from(root_document_name: RootDocumentName) -> Self {
-        root_document_name.database_name
+        Arc::unwrap_or_clone(root_document_name.database_name)
     }
 }
 
@@ -283,22 +666,7 @@ impl std::convert::TryFrom<&str> for RootDocumentName {
     type Error = Error;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        if !(1..=1_024 * 6).contains(&s.len()) {
-            return Err(Error::from(ErrorKind::LengthOutOfBounds));
-        }
-
-        let parts = s.split('/').collect::<Vec<&str>>();
-        if parts.len() != 5 {
-            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
-        }
-        if parts[0] != "projects" || parts[2] != "databases" || parts[4] != "documents" {
-            return Err(Error::from(ErrorKind::InvalidName));
-        }
-
-        let project_id = ProjectId::from_str(parts[1])?;
-        let database_id = DatabaseId::from_str(parts[3])?;
-        let database_name = DatabaseName::new(project_id, database_id);
-        Ok(Self { database_name })
+        Self::from_str_with(s, &ParseOptions::strict())
     }
 }
 
@@ -316,6 +684,58 @@ impl std::fmt::Display for RootDocumentName {
     }
 }
 
+/// Creates a new `CollectionName` by joining `rhs` to the `RootDocumentName`.
+///
+/// # Panics
+///
+/// Panics if `rhs` is not a valid `CollectionPath`. Use [`RootDocumentName::into_collection`] to handle the error instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{DocumentName,RootDocumentName};
+/// use std::str::FromStr;
+///
+/// let root_document_name = RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?;
+/// assert_eq!(
+///     root_document_name / "chatrooms" / "chatroom1",
+///     DocumentName::from_str(
+///         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+///     )?
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+impl std::ops::Div<&str> for RootDocumentName {
+    type Output = CollectionName;
+
+    fn div(self, rhs: &str) -> Self::Output {
+        self.into_collection(rhs)
+            .unwrap_or_else(|e| panic!("RootDocumentName / &str: {e}"))
+    }
+}
+
+/// Creates a new `CollectionName` by joining `rhs` to the `RootDocumentName`.
+impl std::ops::Div<CollectionPath> for RootDocumentName {
+    type Output = CollectionName;
+
+    fn div(self, rhs: CollectionPath) -> Self::Output {
+        self.into_collection(rhs)
+            .unwrap_or_else(|e| panic!("RootDocumentName / CollectionPath: {e}"))
+    }
+}
+
+/// Creates a new `DocumentName` by joining `rhs` to the `RootDocumentName`.
+impl std::ops::Div<DocumentPath> for RootDocumentName {
+    type Output = DocumentName;
+
+    fn div(self, rhs: DocumentPath) -> Self::Output {
+        self.into_doc(rhs)
+            .unwrap_or_else(|e| panic!("RootDocumentName / DocumentPath: {e}"))
+    }
+}
+
 impl std::str::FromStr for RootDocumentName {
     type Err = Error;
 
@@ -367,4 +787,197 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_same_database_and_same_project() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents";
+        for (other, same_database, same_project) in [
+            (
+                "projects/my-project/databases/my-database/documents",
+                true,
+                true,
+            ),
+            (
+                "projects/my-project/databases/my-database/documents",
+                true,
+                true,
+            ),
+            (
+                "projects/my-project/databases/(default)/documents",
+                false,
+                true,
+            ),
+            (
+                "projects/my-project2/databases/my-database/documents",
+                false,
+                false,
+            ),
+        ] {
+            let a = RootDocumentName::from_str(s)?;
+            let b = RootDocumentName::from_str(other)?;
+            assert_eq!(a.same_database(&b), same_database);
+            assert_eq!(a.same_project(&b), same_project);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_div() -> anyhow::Result<()> {
+        let root_document_name =
+            RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?;
+        assert_eq!(
+            (root_document_name.clone() / "chatrooms").to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms"
+        );
+        assert_eq!(
+            (root_document_name.clone() / CollectionPath::from_str("chatrooms")?).to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms"
+        );
+        assert_eq!(
+            (root_document_name / DocumentPath::from_str("chatrooms/chatroom1")?).to_string(),
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_clone_shares_database_name() -> anyhow::Result<()> {
+        let root_document_name =
+            RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?;
+        let cloned = root_document_name.clone();
+        assert!(std::ptr::eq(
+            root_document_name.as_database_name(),
+            cloned.as_database_name()
+        ));
+
+        let collection_name = root_document_name.collection("chatrooms")?;
+        let document_name = collection_name.doc("chatroom1")?;
+        assert!(std::ptr::eq(
+            root_document_name.as_database_name(),
+            document_name.root_document_name().as_database_name()
+        ));
+        assert_eq!(
+            DatabaseName::from(cloned),
+            DatabaseName::from_str("projects/my-project/databases/my-database")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_string() -> anyhow::Result<()> {
+        let s = "projects/my-project/databases/my-database/documents";
+        assert_eq!(RootDocumentName::from_str(s)?.into_string(), s);
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> anyhow::Result<()> {
+        for s in [
+            "projects/my-project/databases/my-database/documents".to_string(),
+            "projects/my-project/databases/my-database".to_string(),
+            "projects/my-project/databases/my-database/document".to_string(),
+            "projects/my-project/databases/db/documents".to_string(),
+            "".to_string(),
+        ] {
+            assert_eq!(
+                RootDocumentName::validate(&s).err(),
+                RootDocumentName::from_str(&s).err()
+            );
+            assert_eq!(
+                RootDocumentName::is_valid(&s),
+                RootDocumentName::from_str(&s).is_ok()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_database_id_and_with_project_id() -> anyhow::Result<()> {
+        let root_document_name =
+            RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?;
+        assert_eq!(
+            root_document_name
+                .with_database_id("(default)")?
+                .to_string(),
+            "projects/my-project/databases/(default)/documents"
+        );
+        assert_eq!(
+            root_document_name
+                .with_project_id("my-project2")?
+                .with_database_id("my-database2")?
+                .to_string(),
+            "projects/my-project2/databases/my-database2/documents"
+        );
+        assert_eq!(
+            root_document_name
+                .with_database_id("db")
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::DatabaseIdConversion("byte length exceeded".to_string())
+        );
+        assert!(matches!(
+            root_document_name
+                .with_project_id("My-Project")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::ProjectIdConversion(_)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_project_id() -> anyhow::Result<()> {
+        let root_document_name = RootDocumentName::from_project_id("my-project")?;
+        assert!(root_document_name.is_default_database());
+        assert_eq!(
+            root_document_name,
+            RootDocumentName::new(DatabaseName::from_project_id("my-project")?)
+        );
+        assert!(matches!(
+            RootDocumentName::from_project_id("my-google-project")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::ProjectIdConversion(_)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_doc_from_ids() -> anyhow::Result<()> {
+        let root_document_name =
+            RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?;
+        assert_eq!(
+            root_document_name.doc_from_ids([("chatrooms", "chatroom1")])?,
+            root_document_name.doc("chatrooms/chatroom1")?
+        );
+        assert_eq!(
+            root_document_name
+                .doc_from_ids(Vec::<(&str, &str)>::new())
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::InvalidNumberOfPathComponents
+        );
+        assert!(matches!(
+            root_document_name
+                .doc_from_ids([("chatrooms", "chatroom1"), ("__x__", "message1")])
+                .unwrap_err()
+                .kind(),
+            ErrorKind::CollectionIdConversion(_)
+        ));
+        assert!(matches!(
+            root_document_name
+                .doc_from_ids([("chatrooms", "..")])
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DocumentIdConversion(_)
+        ));
+        assert_eq!(
+            root_document_name
+                .doc_from_ids(std::iter::repeat_n(("c", "d"), limits::MAX_DEPTH + 1))
+                .unwrap_err()
+                .kind(),
+            &ErrorKind::DepthOutOfBounds
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,335 @@
+// Conversions between the names and the Security Rules paths (`/databases/{database_id}/documents/...`).
+//
+// The paths used inside the Security Rules have no project, so converting a path to a name
+// requires a `ProjectId`.
+
+use std::str::FromStr;
+
+use crate::{
+    error::ErrorKind, CollectionName, CollectionPath, DatabaseId, DatabaseName, DocumentName,
+    DocumentPath, Error, ProjectId, RootDocumentName,
+};
+
+// Removes `projects/{project_id}` from `name`.
+fn to_security_rules_path(name: &str) -> &str {
+    let s = name
+        .strip_prefix("projects/")
+        .expect("name to start with `projects/`");
+    &s[s.find('/').expect("name to contain `/databases/`")..]
+}
+
+// Splits the Security Rules path `path` into the `RootDocumentName` in `project_id` and the rest after `/documents`.
+//
+// The rest is `None` if `path` is a root document. It always starts at the segment 4 of `path`.
+// Only the database id and the rest are parsed, so `project_id` is used as is (e.g. `ProjectId::wildcard()`).
+fn split_security_rules_path<E, T>(
+    project_id: T,
+    path: &str,
+) -> Result<(RootDocumentName, Option<&str>), Error>
+where
+    E: std::fmt::Display,
+    T: TryInto<ProjectId, Error = E>,
+{
+    let project_id = project_id
+        .try_into()
+        .map_err(|e| Error::from(ErrorKind::ProjectIdConversion(e.to_string())))?;
+    let Some(s) = path.strip_prefix("/databases/") else {
+        return Err(Error::from(ErrorKind::PrefixMismatch));
+    };
+    let Some((database_id, s)) = s.split_once('/') else {
+        return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+    };
+    let database_id_start = "/databases/".len();
+    let database_id = DatabaseId::from_str(database_id)
+        .map_err(|e| e.at_segment(2, database_id, database_id_start))?;
+    let root_document_name = RootDocumentName::new(DatabaseName::new(project_id, database_id));
+    match s.split_once('/') {
+        None if s == "documents" => Ok((root_document_name, None)),
+        Some(("documents", rest)) => Ok((root_document_name, Some(rest))),
+        _ => Err(Error::from(ErrorKind::InvalidName)),
+    }
+}
+
+impl CollectionName {
+    /// Creates a new `CollectionName` from `project_id` and the Security Rules path `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_security_rules_path(
+    ///     "my-project",
+    ///     "/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name,
+    ///     CollectionName::from_str(
+    ///         "projects/my-project/databases/my-database/documents/chatrooms"
+    ///     )?
+    /// );
+    ///
+    /// assert!(CollectionName::from_security_rules_path(
+    ///     "my-project",
+    ///     "/documents/chatrooms"
+    /// ).is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_security_rules_path<E, T>(project_id: T, path: &str) -> Result<Self, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<ProjectId, Error = E>,
+    {
+        let (root_document_name, Some(rest)) = split_security_rules_path(project_id, path)? else {
+            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+        };
+        let collection_path =
+            CollectionPath::from_str(rest).map_err(|e| e.offset(4, path.len() - rest.len()))?;
+        CollectionName::try_new(root_document_name, collection_path)
+    }
+
+    /// Returns the Security Rules path (`/databases/{database_id}/documents/...`) of this `CollectionName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::CollectionName;
+    /// use std::str::FromStr;
+    ///
+    /// let collection_name = CollectionName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// )?;
+    /// assert_eq!(
+    ///     collection_name.to_security_rules_path(),
+    ///     "/databases/my-database/documents/chatrooms"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_security_rules_path(&self) -> String {
+        to_security_rules_path(self.as_str()).to_string()
+    }
+}
+
+impl DocumentName {
+    /// Creates a new `DocumentName` from `project_id` and the Security Rules path `path`.
+    ///
+    /// Only `path` is parsed, so `project_id` is used as is (e.g. `ProjectId::wildcard()` or a relaxed `ProjectId` of the emulator).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DocumentName,ProjectId};
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_security_rules_path(
+    ///     "my-project",
+    ///     "/databases/(default)/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name,
+    ///     DocumentName::from_str(
+    ///         "projects/my-project/databases/(default)/documents/chatrooms/chatroom1"
+    ///     )?
+    /// );
+    ///
+    /// let document_name = DocumentName::from_security_rules_path(
+    ///     ProjectId::wildcard(),
+    ///     "/databases/(default)/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.to_string(),
+    ///     "projects/_/databases/(default)/documents/chatrooms/chatroom1"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_security_rules_path<E, T>(project_id: T, path: &str) -> Result<Self, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<ProjectId, Error = E>,
+    {
+        let (root_document_name, Some(rest)) = split_security_rules_path(project_id, path)? else {
+            return Err(Error::from(ErrorKind::InvalidNumberOfPathComponents));
+        };
+        let document_path =
+            DocumentPath::from_str(rest).map_err(|e| e.offset(4, path.len() - rest.len()))?;
+        DocumentName::try_new(root_document_name, document_path)
+    }
+
+    /// Returns the Security Rules path (`/databases/{database_id}/documents/...`) of this `DocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::DocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let document_name = DocumentName::from_str(
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// )?;
+    /// assert_eq!(
+    ///     document_name.to_security_rules_path(),
+    ///     "/databases/my-database/documents/chatrooms/chatroom1"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_security_rules_path(&self) -> String {
+        to_security_rules_path(self.as_str()).to_string()
+    }
+}
+
+impl RootDocumentName {
+    /// Creates a new `RootDocumentName` from `project_id` and the Security Rules path `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::RootDocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name = RootDocumentName::from_security_rules_path(
+    ///     "my-project",
+    ///     "/databases/my-database/documents"
+    /// )?;
+    /// assert_eq!(
+    ///     root_document_name,
+    ///     RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_security_rules_path<E, T>(project_id: T, path: &str) -> Result<Self, Error>
+    where
+        E: std::fmt::Display,
+        T: TryInto<ProjectId, Error = E>,
+    {
+        match split_security_rules_path(project_id, path)? {
+            (root_document_name, None) => Ok(root_document_name),
+            (_, Some(_)) => Err(Error::from(ErrorKind::InvalidNumberOfPathComponents)),
+        }
+    }
+
+    /// Returns the Security Rules path (`/databases/{database_id}/documents`) of this `RootDocumentName`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::RootDocumentName;
+    /// use std::str::FromStr;
+    ///
+    /// let root_document_name =
+    ///     RootDocumentName::from_str("projects/my-project/databases/my-database/documents")?;
+    /// assert_eq!(
+    ///     root_document_name.to_security_rules_path(),
+    ///     "/databases/my-database/documents"
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn to_security_rules_path(&self) -> String {
+        to_security_rules_path(&self.to_string()).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseOptions;
+
+    #[test]
+    fn test_from_security_rules_path() -> anyhow::Result<()> {
+        for s in [
+            "/databases/(default)/documents/chatrooms",
+            "/databases/my-database/documents/chatrooms/chatroom1/messages",
+        ] {
+            let collection_name = CollectionName::from_security_rules_path("my-project", s)?;
+            assert_eq!(collection_name.to_security_rules_path(), s);
+            assert_eq!(
+                collection_name.database_name().project_id().as_ref(),
+                "my-project"
+            );
+        }
+        for s in [
+            "/databases/(default)/documents/chatrooms/chatroom1",
+            "/databases/my-database/documents/chatrooms/chatroom1/messages/message1",
+        ] {
+            let document_name = DocumentName::from_security_rules_path("my-project", s)?;
+            assert_eq!(document_name.to_security_rules_path(), s);
+        }
+        for s in [
+            "",
+            "/",
+            "databases/my-database/documents/chatrooms/chatroom1",
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+            "/projects/my-project/databases/my-database/documents/chatrooms/chatroom1",
+            "/databases/my-database/documents/chatrooms",
+        ] {
+            assert!(DocumentName::from_security_rules_path("my-project", s).is_err());
+        }
+        assert!(DocumentName::from_security_rules_path(
+            "x",
+            "/databases/my-database/documents/chatrooms/chatroom1"
+        )
+        .is_err());
+        assert!(RootDocumentName::from_security_rules_path(
+            "my-project",
+            "/databases/my-database/documents/chatrooms"
+        )
+        .is_err());
+        assert!(CollectionName::from_security_rules_path(
+            "my-project",
+            "/databases/my-database/documents"
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_security_rules_path_with_project_id() -> anyhow::Result<()> {
+        let s = "/databases/(default)/documents/chatrooms/chatroom1";
+        for project_id in [
+            ProjectId::wildcard(),
+            ProjectId::from_str_with("demo-My_Project", &ParseOptions::lenient())?,
+        ] {
+            let document_name = DocumentName::from_security_rules_path(project_id.clone(), s)?;
+            assert_eq!(document_name.database_name().project_id(), &project_id);
+            assert_eq!(document_name.to_security_rules_path(), s);
+            let collection_name = CollectionName::from_security_rules_path(
+                project_id.clone(),
+                "/databases/(default)/documents/chatrooms",
+            )?;
+            assert_eq!(collection_name, document_name.parent());
+            let root_document_name = RootDocumentName::from_security_rules_path(
+                project_id,
+                "/databases/(default)/documents",
+            )?;
+            assert_eq!(&root_document_name, document_name.root_document_name());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_security_rules_path_error_location() -> anyhow::Result<()> {
+        let s = "/databases/my-database/documents/chatrooms/..";
+        let error = DocumentName::from_security_rules_path("my-project", s).unwrap_err();
+        assert_eq!(error.segment_index(), Some(5));
+        assert_eq!(error.byte_range(), Some(43..45));
+        assert_eq!(error.segment(), Some(".."));
+
+        let s = "/databases/my_database/documents/chatrooms/chatroom1";
+        let error = DocumentName::from_security_rules_path("my-project", s).unwrap_err();
+        assert_eq!(error.segment_index(), Some(2));
+        assert_eq!(error.byte_range(), Some(11..22));
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{CollectionId, DocumentId};
+
+/// A segment of a path.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{CollectionId,DocumentId,DocumentPath,Segment};
+/// use std::str::FromStr;
+///
+/// let document_path = DocumentPath::from_str("chatrooms/chatroom1")?;
+/// let collection_id = CollectionId::from_str("chatrooms")?;
+/// let document_id = DocumentId::from_str("chatroom1")?;
+/// assert_eq!(
+///     document_path.segments().collect::<Vec<Segment>>(),
+///     vec![
+///         Segment::Collection(&collection_id),
+///         Segment::Document(&document_id),
+///     ]
+/// );
+/// assert_eq!(Segment::Collection(&collection_id).as_ref(), "chatrooms");
+/// assert_eq!(Segment::Document(&document_id).to_string(), "chatroom1");
+/// #     Ok(())
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Segment<'a> {
+    /// A collection id segment.
+    Collection(&'a CollectionId),
+    /// A document id segment.
+    Document(&'a DocumentId),
+}
+
+impl std::convert::AsRef<str> for Segment<'_> {
+    fn as_ref(&self) -> &str {
+        match self {
+            Segment::Collection(collection_id) => collection_id.as_ref(),
+            Segment::Document(document_id) => document_id.as_ref(),
+        }
+    }
+}
+
+impl std::fmt::Display for Segment<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Segment::Collection(collection_id) => collection_id.fmt(f),
+            Segment::Document(document_id) => document_id.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let collection_id = CollectionId::from_str("chatrooms")?;
+        let segment = Segment::Collection(&collection_id);
+        assert_eq!(segment.as_ref(), "chatrooms");
+        assert_eq!(segment.to_string(), "chatrooms");
+
+        let document_id = DocumentId::from_str("chatroom1")?;
+        let segment = Segment::Document(&document_id);
+        assert_eq!(segment.as_ref(), "chatroom1");
+        assert_eq!(segment.to_string(), "chatroom1");
+        Ok(())
+    }
+}
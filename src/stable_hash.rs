@@ -0,0 +1,58 @@
+// The 64-bit FNV-1a hash of the canonical string representation, used by `stable_hash`.
+//
+// The algorithm and the input (the UTF-8 bytes of the canonical string) are part of the public
+// contract, so the values never change between versions.
+//
+// <http://www.isthe.com/chongo/tech/comp/fnv/index.html#FNV-1a>
+
+const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+pub(crate) struct StableHasher(u64);
+
+impl StableHasher {
+    pub(crate) fn hash<F>(write_to: F) -> u64
+    where
+        F: FnOnce(&mut Self) -> std::fmt::Result,
+    {
+        let mut hasher = Self(OFFSET_BASIS);
+        write_to(&mut hasher).expect("writing to a StableHasher to succeed");
+        hasher.0
+    }
+}
+
+impl std::fmt::Write for StableHasher {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        for byte in s.bytes() {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(PRIME);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write as _;
+
+    use super::*;
+
+    #[test]
+    fn test_hash() -> anyhow::Result<()> {
+        // <http://www.isthe.com/chongo/src/fnv/test_fnv.c>
+        assert_eq!(StableHasher::hash(|_| Ok(())), 0xcbf29ce484222325);
+        assert_eq!(StableHasher::hash(|h| h.write_str("a")), 0xaf63dc4c8601ec8c);
+        assert_eq!(
+            StableHasher::hash(|h| h.write_str("foobar")),
+            0x85944171f73967e8
+        );
+        assert_eq!(
+            StableHasher::hash(|h| {
+                h.write_str("foo")?;
+                h.write_str("bar")
+            }),
+            StableHasher::hash(|h| h.write_str("foobar"))
+        );
+        Ok(())
+    }
+}
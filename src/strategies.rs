@@ -0,0 +1,224 @@
+//! [`proptest`](https://docs.rs/proptest) strategies for the ids, names and paths.
+//!
+//! The valid strategies only generate values that satisfy the limits of each type.
+//! The `invalid_*` strategies generate strings that are rejected by `FromStr` of each type.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # fn main() -> anyhow::Result<()> {
+//! use firestore_path::{strategies,DocumentName};
+//! use proptest::{prop_assert_eq,test_runner::TestRunner};
+//! use std::str::FromStr;
+//!
+//! let mut runner = TestRunner::default();
+//! runner.run(&strategies::document_name(), |document_name| {
+//!     let s = document_name.to_string();
+//!     prop_assert_eq!(DocumentName::from_str(&s)?, document_name);
+//!     Ok(())
+//! })?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::{
+    CollectionId, CollectionName, CollectionPath, DatabaseId, DatabaseName, DocumentId,
+    DocumentName, DocumentPath, ProjectId, RootDocumentName,
+};
+
+// Most ids are short, but ids up to the maximum length (1,500 bytes) are also generated.
+fn id_string() -> impl Strategy<Value = String> {
+    prop_oneof![9 => "[^/]{1,16}", 1 => "[^/]{1,375}"]
+}
+
+// <https://firebase.google.com/docs/firestore/quotas#collections_documents_and_fields>
+fn invalid_id_string() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(String::new()),
+        Just(".".to_string()),
+        Just("..".to_string()),
+        "__[^/]*__",
+        "[^/]{0,8}/[^/]{0,8}",
+        "x{1501,1600}",
+    ]
+}
+
+/// Returns a strategy that generates valid `CollectionId`s.
+pub fn collection_id() -> impl Strategy<Value = CollectionId> {
+    id_string().prop_filter_map("invalid collection id", |s| CollectionId::try_from(s).ok())
+}
+
+/// Returns a strategy that generates valid `CollectionName`s.
+pub fn collection_name() -> impl Strategy<Value = CollectionName> {
+    (root_document_name(), collection_path()).prop_filter_map(
+        "too long collection name",
+        |(root_document_name, collection_path)| {
+            CollectionName::try_new(root_document_name, collection_path).ok()
+        },
+    )
+}
+
+/// Returns a strategy that generates valid `CollectionPath`s with a depth of 1 to 4.
+pub fn collection_path() -> impl Strategy<Value = CollectionPath> {
+    (vec((collection_id(), document_id()), 0..4), collection_id()).prop_map(
+        |(pairs, collection_id)| {
+            let parent = (!pairs.is_empty())
+                .then(|| DocumentPath::from_pairs(pairs).expect("pairs to be non-empty and valid"));
+            CollectionPath::new(parent, collection_id)
+        },
+    )
+}
+
+/// Returns a strategy that generates valid `DatabaseId`s (including `(default)`).
+pub fn database_id() -> impl Strategy<Value = DatabaseId> {
+    prop_oneof![
+        Just("(default)".to_string()),
+        "[a-z][a-z0-9-]{2,61}[a-z0-9]"
+    ]
+    .prop_filter_map("invalid database id", |s| DatabaseId::try_from(s).ok())
+}
+
+/// Returns a strategy that generates valid `DatabaseName`s.
+pub fn database_name() -> impl Strategy<Value = DatabaseName> {
+    (project_id(), database_id())
+        .prop_map(|(project_id, database_id)| DatabaseName::new(project_id, database_id))
+}
+
+/// Returns a strategy that generates valid `DocumentId`s.
+pub fn document_id() -> impl Strategy<Value = DocumentId> {
+    id_string().prop_filter_map("invalid document id", |s| DocumentId::try_from(s).ok())
+}
+
+/// Returns a strategy that generates valid `DocumentName`s.
+pub fn document_name() -> impl Strategy<Value = DocumentName> {
+    (root_document_name(), document_path()).prop_filter_map(
+        "too long document name",
+        |(root_document_name, document_path)| {
+            DocumentName::try_new(root_document_name, document_path).ok()
+        },
+    )
+}
+
+/// Returns a strategy that generates valid `DocumentPath`s with a depth of 1 to 4.
+pub fn document_path() -> impl Strategy<Value = DocumentPath> {
+    (collection_path(), document_id())
+        .prop_map(|(collection_path, document_id)| DocumentPath::new(collection_path, document_id))
+}
+
+/// Returns a strategy that generates strings that are not valid `CollectionId`s.
+pub fn invalid_collection_id() -> impl Strategy<Value = String> {
+    invalid_id_string().prop_filter("valid collection id", |s| {
+        CollectionId::try_from(s.as_str()).is_err()
+    })
+}
+
+/// Returns a strategy that generates strings that are not valid `DatabaseId`s.
+pub fn invalid_database_id() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-z][a-z0-9-]{0,2}",
+        "[a-z][a-z0-9-]{63,70}",
+        "[a-z][a-z0-9-]{2,8}[A-Z_.]",
+        "[0-9-][a-z0-9-]{3,8}",
+        "[a-z][a-z0-9-]{3,8}-",
+    ]
+    .prop_filter("valid database id", |s| {
+        DatabaseId::try_from(s.as_str()).is_err()
+    })
+}
+
+/// Returns a strategy that generates strings that are not valid `DocumentId`s.
+pub fn invalid_document_id() -> impl Strategy<Value = String> {
+    invalid_id_string().prop_filter("valid document id", |s| {
+        DocumentId::try_from(s.as_str()).is_err()
+    })
+}
+
+/// Returns a strategy that generates strings that are not valid `ProjectId`s.
+pub fn invalid_project_id() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-z][a-z0-9-]{0,4}",
+        "[a-z][a-z0-9-]{30,40}",
+        "[a-z][a-z0-9-]{4,8}[A-Z_.]",
+        "[0-9-][a-z0-9-]{5,8}",
+        "[a-z][a-z0-9-]{5,8}-",
+        "[a-z]{1,4}(google|null|undefined|ssl)",
+    ]
+    .prop_filter("valid project id", |s| {
+        ProjectId::try_from(s.as_str()).is_err()
+    })
+}
+
+/// Returns a strategy that generates valid `ProjectId`s.
+pub fn project_id() -> impl Strategy<Value = ProjectId> {
+    "[a-z][a-z0-9-]{4,28}[a-z0-9]"
+        .prop_filter_map("invalid project id", |s| ProjectId::try_from(s).ok())
+}
+
+/// Returns a strategy that generates valid `RootDocumentName`s.
+pub fn root_document_name() -> impl Strategy<Value = RootDocumentName> {
+    database_name().prop_map(RootDocumentName::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_ids(
+            collection_id in collection_id(),
+            database_id in database_id(),
+            document_id in document_id(),
+            project_id in project_id(),
+        ) {
+            prop_assert_eq!(CollectionId::from_str(collection_id.as_ref())?, collection_id);
+            prop_assert_eq!(DatabaseId::from_str(database_id.as_ref())?, database_id);
+            prop_assert_eq!(DocumentId::from_str(document_id.as_ref())?, document_id);
+            prop_assert_eq!(ProjectId::from_str(project_id.as_ref())?, project_id);
+        }
+
+        #[test]
+        fn test_invalid_ids(
+            collection_id in invalid_collection_id(),
+            database_id in invalid_database_id(),
+            document_id in invalid_document_id(),
+            project_id in invalid_project_id(),
+        ) {
+            prop_assert!(CollectionId::from_str(&collection_id).is_err());
+            prop_assert!(DatabaseId::from_str(&database_id).is_err());
+            prop_assert!(DocumentId::from_str(&document_id).is_err());
+            prop_assert!(ProjectId::from_str(&project_id).is_err());
+        }
+
+        #[test]
+        fn test_names(
+            collection_name in collection_name(),
+            database_name in database_name(),
+            document_name in document_name(),
+            root_document_name in root_document_name(),
+        ) {
+            prop_assert_eq!(CollectionName::from_str(collection_name.as_str())?, collection_name);
+            prop_assert_eq!(DatabaseName::from_str(&database_name.to_string())?, database_name);
+            prop_assert_eq!(DocumentName::from_str(document_name.as_str())?, document_name);
+            prop_assert_eq!(
+                RootDocumentName::from_str(&root_document_name.to_string())?,
+                root_document_name
+            );
+        }
+
+        #[test]
+        fn test_paths(
+            collection_path in collection_path(),
+            document_path in document_path(),
+        ) {
+            prop_assert!((1..=4).contains(&collection_path.depth()));
+            prop_assert!((1..=4).contains(&document_path.depth()));
+            prop_assert_eq!(CollectionPath::from_str(&collection_path.to_string())?, collection_path);
+            prop_assert_eq!(DocumentPath::from_str(&document_path.to_string())?, document_path);
+        }
+    }
+}
@@ -0,0 +1,318 @@
+//! Ready-made values for tests and examples.
+//!
+//! All values belong to one family, which is also used throughout this documentation:
+//!
+//! - project id: `my-project`
+//! - database id: `my-database`
+//! - collection: `chatrooms`
+//! - document: `chatrooms/chatroom1`
+//! - nested collection: `chatrooms/chatroom1/messages`
+//! - nested document: `chatrooms/chatroom1/messages/message1`
+//!
+//! Each type also has an `example()` constructor that returns the member of the family of that type.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # fn main() -> anyhow::Result<()> {
+//! use firestore_path::{testing,DatabaseName,DocumentName};
+//! use std::str::FromStr;
+//!
+//! assert_eq!(
+//!     DatabaseName::example(),
+//!     DatabaseName::from_str("projects/my-project/databases/my-database")?
+//! );
+//! assert_eq!(
+//!     testing::nested_document_name(),
+//!     DocumentName::from_str(
+//!         "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1"
+//!     )?
+//! );
+//! assert_eq!(testing::nested_document_name().parent(), testing::nested_collection_name());
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::{
+    CollectionId, CollectionName, CollectionPath, DatabaseId, DatabaseName, DocumentId,
+    DocumentName, DocumentPath, ProjectId, RootDocumentName,
+};
+
+/// Returns the `CollectionName` `projects/my-project/databases/my-database/documents/chatrooms`.
+pub fn collection_name() -> CollectionName {
+    CollectionName::new(root_document_name(), collection_path())
+}
+
+/// Returns the `CollectionPath` `chatrooms`.
+pub fn collection_path() -> CollectionPath {
+    CollectionPath::new(None, CollectionId::from_static("chatrooms"))
+}
+
+/// Returns the `DatabaseName` `projects/my-project/databases/my-database`.
+pub fn database_name() -> DatabaseName {
+    DatabaseName::new(
+        ProjectId::from_static("my-project"),
+        DatabaseId::from_static("my-database"),
+    )
+}
+
+/// Returns the `DocumentName` `projects/my-project/databases/my-database/documents/chatrooms/chatroom1`.
+pub fn document_name() -> DocumentName {
+    DocumentName::new(root_document_name(), document_path())
+}
+
+/// Returns the `DocumentPath` `chatrooms/chatroom1`.
+pub fn document_path() -> DocumentPath {
+    DocumentPath::new(collection_path(), DocumentId::from_static("chatroom1"))
+}
+
+/// Returns the `CollectionName` `projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages`.
+pub fn nested_collection_name() -> CollectionName {
+    CollectionName::new(root_document_name(), nested_collection_path())
+}
+
+/// Returns the `CollectionPath` `chatrooms/chatroom1/messages`.
+pub fn nested_collection_path() -> CollectionPath {
+    CollectionPath::new(Some(document_path()), CollectionId::from_static("messages"))
+}
+
+/// Returns the `DocumentName` `projects/my-project/databases/my-database/documents/chatrooms/chatroom1/messages/message1`.
+pub fn nested_document_name() -> DocumentName {
+    DocumentName::new(root_document_name(), nested_document_path())
+}
+
+/// Returns the `DocumentPath` `chatrooms/chatroom1/messages/message1`.
+pub fn nested_document_path() -> DocumentPath {
+    DocumentPath::new(
+        nested_collection_path(),
+        DocumentId::from_static("message1"),
+    )
+}
+
+/// Returns the `RootDocumentName` `projects/my-project/databases/my-database/documents`.
+pub fn root_document_name() -> RootDocumentName {
+    RootDocumentName::new(database_name())
+}
+
+impl CollectionId {
+    /// Returns the example `CollectionId` `chatrooms`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::CollectionId;
+    ///
+    /// assert_eq!(CollectionId::example().as_ref(), "chatrooms");
+    /// ```
+    pub fn example() -> Self {
+        collection_path().collection_id().clone()
+    }
+}
+
+impl CollectionName {
+    /// Returns the example `CollectionName` (see [`testing::collection_name`](crate::testing::collection_name)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::CollectionName;
+    ///
+    /// assert_eq!(
+    ///     CollectionName::example().to_string(),
+    ///     "projects/my-project/databases/my-database/documents/chatrooms"
+    /// );
+    /// ```
+    pub fn example() -> Self {
+        collection_name()
+    }
+}
+
+impl CollectionPath {
+    /// Returns the example `CollectionPath` (see [`testing::collection_path`](crate::testing::collection_path)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::CollectionPath;
+    ///
+    /// assert_eq!(CollectionPath::example().to_string(), "chatrooms");
+    /// ```
+    pub fn example() -> Self {
+        collection_path()
+    }
+}
+
+impl DatabaseId {
+    /// Returns the example `DatabaseId` `my-database`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DatabaseId;
+    ///
+    /// assert_eq!(DatabaseId::example().as_ref(), "my-database");
+    /// ```
+    pub fn example() -> Self {
+        database_name().database_id().clone()
+    }
+}
+
+impl DatabaseName {
+    /// Returns the example `DatabaseName` (see [`testing::database_name`](crate::testing::database_name)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DatabaseName;
+    ///
+    /// assert_eq!(
+    ///     DatabaseName::example().to_string(),
+    ///     "projects/my-project/databases/my-database"
+    /// );
+    /// ```
+    pub fn example() -> Self {
+        database_name()
+    }
+}
+
+impl DocumentId {
+    /// Returns the example `DocumentId` `chatroom1`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DocumentId;
+    ///
+    /// assert_eq!(DocumentId::example().as_ref(), "chatroom1");
+    /// ```
+    pub fn example() -> Self {
+        document_path().document_id().clone()
+    }
+}
+
+impl DocumentName {
+    /// Returns the example `DocumentName` (see [`testing::document_name`](crate::testing::document_name)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DocumentName;
+    ///
+    /// assert_eq!(
+    ///     DocumentName::example().to_string(),
+    ///     "projects/my-project/databases/my-database/documents/chatrooms/chatroom1"
+    /// );
+    /// ```
+    pub fn example() -> Self {
+        document_name()
+    }
+}
+
+impl DocumentPath {
+    /// Returns the example `DocumentPath` (see [`testing::document_path`](crate::testing::document_path)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::DocumentPath;
+    ///
+    /// assert_eq!(DocumentPath::example().to_string(), "chatrooms/chatroom1");
+    /// ```
+    pub fn example() -> Self {
+        document_path()
+    }
+}
+
+impl ProjectId {
+    /// Returns the example `ProjectId` `my-project`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::ProjectId;
+    ///
+    /// assert_eq!(ProjectId::example().as_ref(), "my-project");
+    /// ```
+    pub fn example() -> Self {
+        database_name().project_id().clone()
+    }
+}
+
+impl RootDocumentName {
+    /// Returns the example `RootDocumentName` (see [`testing::root_document_name`](crate::testing::root_document_name)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use firestore_path::RootDocumentName;
+    ///
+    /// assert_eq!(
+    ///     RootDocumentName::example().to_string(),
+    ///     "projects/my-project/databases/my-database/documents"
+    /// );
+    /// ```
+    pub fn example() -> Self {
+        root_document_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let root = "projects/my-project/databases/my-database/documents";
+        assert_eq!(
+            database_name(),
+            DatabaseName::from_str("projects/my-project/databases/my-database")?
+        );
+        assert_eq!(root_document_name(), RootDocumentName::from_str(root)?);
+        assert_eq!(collection_path(), CollectionPath::from_str("chatrooms")?);
+        assert_eq!(
+            document_path(),
+            DocumentPath::from_str("chatrooms/chatroom1")?
+        );
+        assert_eq!(
+            nested_collection_path(),
+            CollectionPath::from_str("chatrooms/chatroom1/messages")?
+        );
+        assert_eq!(
+            nested_document_path(),
+            DocumentPath::from_str("chatrooms/chatroom1/messages/message1")?
+        );
+        assert_eq!(
+            collection_name(),
+            CollectionName::from_str(&format!("{root}/chatrooms"))?
+        );
+        assert_eq!(
+            document_name(),
+            DocumentName::from_str(&format!("{root}/chatrooms/chatroom1"))?
+        );
+        assert_eq!(
+            nested_collection_name(),
+            CollectionName::from_str(&format!("{root}/chatrooms/chatroom1/messages"))?
+        );
+        assert_eq!(
+            nested_document_name(),
+            DocumentName::from_str(&format!("{root}/chatrooms/chatroom1/messages/message1"))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(CollectionId::example(), *collection_name().collection_id());
+        assert_eq!(CollectionName::example(), collection_name());
+        assert_eq!(CollectionPath::example(), collection_path());
+        assert_eq!(DatabaseId::example(), *database_name().database_id());
+        assert_eq!(DatabaseName::example(), database_name());
+        assert_eq!(DocumentId::example(), *document_name().document_id());
+        assert_eq!(DocumentName::example(), document_name());
+        assert_eq!(DocumentPath::example(), document_path());
+        assert_eq!(ProjectId::example(), *database_name().project_id());
+        assert_eq!(RootDocumentName::example(), root_document_name());
+    }
+}
@@ -0,0 +1,107 @@
+// Conversion from `Error` into `tonic::Status`.
+
+use std::collections::HashMap;
+
+use tonic::{Code, Status};
+use tonic_types::{ErrorDetails, StatusExt};
+
+use crate::Error;
+
+/// The `domain` of the `google.rpc.ErrorInfo` in the `Status` converted from an `Error`.
+const DOMAIN: &str = "firestore-path";
+
+/// Converts `error` into a `Status` with the `INVALID_ARGUMENT` code.
+///
+/// The `message` of the `Status` is the `Display` of `error`.
+/// The `details` of the `Status` contain a `google.rpc.ErrorInfo` with the `ErrorKind` as the `reason` (e.g. `LENGTH_OUT_OF_BOUNDS`),
+/// `firestore-path` as the `domain` and the location of the segment (`segmentIndex`, `segment`, `byteStart` and `byteEnd`) as the `metadata` if any.
+///
+/// This implementation requires the `tonic` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// use firestore_path::DocumentName;
+/// use std::str::FromStr;
+/// use tonic_types::StatusExt;
+///
+/// fn handler(name: &str) -> Result<DocumentName, tonic::Status> {
+///     Ok(DocumentName::from_str(name)?)
+/// }
+///
+/// let status = handler("projects/my-project/databases/my-database/documents/chatrooms/..").unwrap_err();
+/// assert_eq!(status.code(), tonic::Code::InvalidArgument);
+/// assert_eq!(
+///     status.message(),
+///     "segment 6 (`..`) at bytes 62..64: single period or double periods"
+/// );
+/// let error_info = status.get_details_error_info().unwrap();
+/// assert_eq!(error_info.reason, "SINGLE_PERIOD_OR_DOUBLE_PERIODS");
+/// assert_eq!(error_info.domain, "firestore-path");
+/// assert_eq!(error_info.metadata["segmentIndex"], "6");
+/// assert_eq!(error_info.metadata["segment"], "..");
+/// ```
+impl std::convert::From<Error> for Status {
+    fn from(error: Error) -> Self {
+        let mut metadata = HashMap::new();
+        if let (Some(index), Some(segment), Some(byte_range)) =
+            (error.segment_index(), error.segment(), error.byte_range())
+        {
+            metadata.insert("segmentIndex".to_string(), index.to_string());
+            metadata.insert("segment".to_string(), segment.to_string());
+            metadata.insert("byteStart".to_string(), byte_range.start.to_string());
+            metadata.insert("byteEnd".to_string(), byte_range.end.to_string());
+        }
+        Status::with_error_details(
+            Code::InvalidArgument,
+            error.to_string(),
+            ErrorDetails::with_error_info(error.kind().reason(), DOMAIN, metadata),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{CollectionId, DocumentName};
+
+    #[test]
+    fn test_from_error() -> anyhow::Result<()> {
+        let error = DocumentName::from_str(
+            "projects/my-project/databases/my-database/documents/chatrooms/chatroom1/__messages__/message1",
+        )
+        .unwrap_err();
+        let status = Status::from(error.clone());
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(status.message(), error.to_string());
+        let error_info = status
+            .get_details_error_info()
+            .expect("status to have an error info");
+        assert_eq!(error_info.reason, "MATCHES_RESERVED_ID_PATTERN");
+        assert_eq!(error_info.domain, "firestore-path");
+        assert_eq!(
+            error_info.metadata,
+            HashMap::from(
+                [
+                    ("segmentIndex", "7"),
+                    ("segment", "__messages__"),
+                    ("byteStart", "72"),
+                    ("byteEnd", "84"),
+                ]
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+            )
+        );
+
+        let status = Status::from(CollectionId::from_str("").unwrap_err());
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(status.message(), "byte length exceeded");
+        let error_info = status
+            .get_details_error_info()
+            .expect("status to have an error info");
+        assert_eq!(error_info.reason, "LENGTH_OUT_OF_BOUNDS");
+        assert!(error_info.metadata.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{DatabaseName, DocumentName};
+
+/// A batch of distinct `DocumentName`s in one database, which can be written in one commit.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use firestore_path::{DatabaseName,WriteBatch};
+/// use std::str::FromStr;
+///
+/// let database_name1 = DatabaseName::from_str("projects/my-project/databases/(default)")?;
+/// let database_name2 = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+/// let batches = WriteBatch::group([
+///     database_name2.doc("chatrooms/chatroom1")?,
+///     database_name1.doc("chatrooms/chatroom1")?,
+///     database_name2.doc("chatrooms/chatroom2")?,
+///     database_name2.doc("chatrooms/chatroom1")?,
+/// ]);
+/// assert_eq!(batches.len(), 2);
+/// assert_eq!(batches[0].database_name(), &database_name1);
+/// assert_eq!(batches[0].document_names(), [database_name1.doc("chatrooms/chatroom1")?]);
+/// assert_eq!(batches[1].database_name(), &database_name2);
+/// assert_eq!(
+///     batches[1].document_names(),
+///     [
+///         database_name2.doc("chatrooms/chatroom1")?,
+///         database_name2.doc("chatrooms/chatroom2")?
+///     ]
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WriteBatch {
+    database_name: DatabaseName,
+    document_names: Vec<DocumentName>,
+}
+
+impl WriteBatch {
+    /// The maximum number of writes in one commit.
+    ///
+    /// <https://firebase.google.com/docs/firestore/quotas#writes_and_transactions>
+    pub const MAX_WRITES: usize = 500;
+
+    /// Returns the `DatabaseName` of this `WriteBatch`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseName,WriteBatch};
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let batches = WriteBatch::group([database_name.doc("chatrooms/chatroom1")?]);
+    /// assert_eq!(batches[0].database_name(), &database_name);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn database_name(&self) -> &DatabaseName {
+        &self.database_name
+    }
+
+    /// Returns the `DocumentName`s of this `WriteBatch`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseName,WriteBatch};
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let document_name = database_name.doc("chatrooms/chatroom1")?;
+    /// let batches = WriteBatch::group([document_name.clone()]);
+    /// assert_eq!(batches[0].document_names(), [document_name]);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn document_names(&self) -> &[DocumentName] {
+        &self.document_names
+    }
+
+    /// Groups `document_names` into `WriteBatch`es.
+    ///
+    /// The `document_names` are grouped by `DatabaseName` and deduplicated, and each group is split into `WriteBatch`es of at most [`WriteBatch::MAX_WRITES`] `DocumentName`s.
+    /// The `WriteBatch`es are ordered by `DatabaseName`, and the `DocumentName`s in each `WriteBatch` keep the order of their first occurrence.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseName,WriteBatch};
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let document_names = (0..1_200)
+    ///     .map(|i| database_name.doc(format!("chatrooms/chatroom{}", i)))
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// let batches = WriteBatch::group(document_names);
+    /// assert_eq!(
+    ///     batches
+    ///         .iter()
+    ///         .map(|batch| batch.document_names().len())
+    ///         .collect::<Vec<_>>(),
+    ///     [500, 500, 200]
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn group<I>(document_names: I) -> Vec<WriteBatch>
+    where
+        I: IntoIterator<Item = DocumentName>,
+    {
+        let mut seen = HashSet::new();
+        let mut groups = BTreeMap::<DatabaseName, Vec<DocumentName>>::new();
+        for document_name in document_names {
+            if seen.insert(document_name.clone()) {
+                groups
+                    .entry(document_name.database_name().clone())
+                    .or_default()
+                    .push(document_name);
+            }
+        }
+        groups
+            .into_iter()
+            .flat_map(|(database_name, document_names)| {
+                document_names
+                    .chunks(Self::MAX_WRITES)
+                    .map(|document_names| WriteBatch {
+                        database_name: database_name.clone(),
+                        document_names: document_names.to_vec(),
+                    })
+                    .collect::<Vec<WriteBatch>>()
+            })
+            .collect()
+    }
+
+    /// Converts this `WriteBatch` into the `DocumentName`s.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use firestore_path::{DatabaseName,WriteBatch};
+    /// use std::str::FromStr;
+    ///
+    /// let database_name = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+    /// let document_name = database_name.doc("chatrooms/chatroom1")?;
+    /// let batches = WriteBatch::group([document_name.clone()]);
+    /// assert_eq!(
+    ///     batches.into_iter().next().map(|batch| batch.into_document_names()),
+    ///     Some(vec![document_name])
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_document_names(self) -> Vec<DocumentName> {
+        self.document_names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_group() -> anyhow::Result<()> {
+        let database_name1 = DatabaseName::from_str("projects/my-project/databases/(default)")?;
+        let database_name2 = DatabaseName::from_str("projects/my-project/databases/my-database")?;
+        let document_names = (0..1_001)
+            .flat_map(|i| {
+                [
+                    database_name2.doc(format!("chatrooms/chatroom{}", i)),
+                    database_name1.doc(format!("chatrooms/chatroom{}", i % 3)),
+                    database_name2.doc(format!("chatrooms/chatroom{}", i)),
+                ]
+            })
+            .collect::<Result<Vec<DocumentName>, crate::Error>>()?;
+        let batches = WriteBatch::group(document_names);
+        assert_eq!(
+            batches
+                .iter()
+                .map(|batch| (batch.database_name(), batch.document_names().len()))
+                .collect::<Vec<(&DatabaseName, usize)>>(),
+            [
+                (&database_name1, 3),
+                (&database_name2, 500),
+                (&database_name2, 500),
+                (&database_name2, 1),
+            ]
+        );
+        assert_eq!(
+            batches[3].document_names(),
+            [database_name2.doc("chatrooms/chatroom1000")?]
+        );
+        assert!(WriteBatch::group(vec![]).is_empty());
+        Ok(())
+    }
+}
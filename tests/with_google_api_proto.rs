@@ -40,14 +40,11 @@ fn test_create_document_request() -> anyhow::Result<()> {
     ] {
         let document_name = DocumentName::from_str(s)?;
 
+        let (parent, collection_id, document_id) = document_name.to_create_document_parts();
         let request = CreateDocumentRequest {
-            parent: document_name
-                .parent()
-                .parent()
-                .map(|parent_document_name| parent_document_name.to_string())
-                .unwrap_or_else(|| document_name.root_document_name().to_string()),
-            collection_id: document_name.collection_id().to_string(),
-            document_id: document_name.document_id().to_string(),
+            parent: parent.to_string(),
+            collection_id: collection_id.to_string(),
+            document_id: document_id.to_string(),
             document: Some(Document {
                 name: "".to_string(),
                 fields: BTreeMap::new(),